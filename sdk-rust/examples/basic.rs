@@ -11,7 +11,7 @@
 //! POPSIGNER_API_KEY=psk_live_xxx NAMESPACE_ID=... cargo run --example basic
 //! ```
 
-use popsigner::{Client, CreateKeyRequest};
+use popsigner::{Client, CreateKeyRequest, NamespaceId};
 use uuid::Uuid;
 
 #[tokio::main]
@@ -19,7 +19,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get API key from environment
     let api_key = std::env::var("POPSIGNER_API_KEY")
         .expect("POPSIGNER_API_KEY environment variable required");
-    let namespace_id: Uuid = std::env::var("NAMESPACE_ID")
+    let namespace_id: NamespaceId = std::env::var("NAMESPACE_ID")
         .expect("NAMESPACE_ID environment variable required")
         .parse()
         .expect("Invalid NAMESPACE_ID format");
@@ -38,6 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             algorithm: Some("secp256k1".to_string()),
             exportable: Some(false),
             metadata: None,
+            ..Default::default()
         })
         .await?;
 
@@ -9,7 +9,7 @@
 //! POPSIGNER_API_KEY=psk_live_xxx NAMESPACE_ID=... cargo run --example parallel_workers
 //! ```
 
-use popsigner::{BatchSignItem, BatchSignRequest, Client, CreateBatchRequest};
+use popsigner::{BatchSignItem, BatchSignRequest, Client, CreateBatchRequest, NamespaceId};
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get API key from environment
     let api_key = std::env::var("POPSIGNER_API_KEY")
         .expect("POPSIGNER_API_KEY environment variable required");
-    let namespace_id: Uuid = std::env::var("NAMESPACE_ID")
+    let namespace_id: NamespaceId = std::env::var("NAMESPACE_ID")
         .expect("NAMESPACE_ID environment variable required")
         .parse()
         .expect("Invalid NAMESPACE_ID format");
@@ -73,7 +73,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 })
                 .collect(),
         })
-        .await?;
+        .await?
+        .into_result()?;
 
     let batch_duration = start.elapsed();
     println!("Batch signing completed in {:?}", batch_duration);
@@ -2,9 +2,13 @@
 //!
 //! This module provides the AuditClient for accessing audit logs.
 
-use crate::client::Client;
-use crate::error::Result;
-use crate::types::{AuditLog, ListAuditLogsQuery, PaginatedResponse};
+use crate::client::{encode_query_value, Client};
+use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::types::{AuditLog, KeyEvent, ListAuditLogsQuery, PaginatedResponse};
+use futures::future;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 /// Client for audit log operations.
@@ -51,6 +55,10 @@ impl AuditClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "audit.list", skip_all)
+    )]
     pub async fn list(
         &self,
         query: Option<ListAuditLogsQuery>,
@@ -106,6 +114,10 @@ impl AuditClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "audit.get", skip_all, fields(log_id = %log_id))
+    )]
     pub async fn get(&self, log_id: &Uuid) -> Result<AuditLog> {
         self.client.get(&format!("/v1/audit/{}", log_id)).await
     }
@@ -135,6 +147,14 @@ impl AuditClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "audit.list_for_resource",
+            skip_all,
+            fields(resource_type = %resource_type, resource_id = %resource_id)
+        )
+    )]
     pub async fn list_for_resource(
         &self,
         resource_type: &str,
@@ -147,6 +167,213 @@ impl AuditClient {
         }))
         .await
     }
+
+    /// Stream the operations performed on a key, newest first.
+    ///
+    /// Pages of the audit log are fetched lazily as the stream is polled. With
+    /// `since`, only events at or after that time are yielded: the bound is
+    /// sent to the server as `start_time` and re-checked locally, so servers
+    /// that ignore the parameter still produce the right events.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - The key's unique identifier
+    /// * `since` - Optional lower bound on the event time
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use popsigner::{Client, KeyId};
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///     let since = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+    ///
+    ///     let events = client.audit().list_key_events(&key_id, Some(since));
+    ///     futures::pin_mut!(events);
+    ///     while let Some(event) = events.try_next().await? {
+    ///         println!("{} by {:?}", event.operation, event.actor_id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_key_events(
+        &self,
+        key_id: &KeyId,
+        since: Option<SystemTime>,
+    ) -> impl Stream<Item = Result<KeyEvent>> + Send + 'static {
+        let client = self.client.clone();
+        let mut base = format!("/v1/audit/logs?resource_type=key&resource_id={}", key_id);
+        if let Some(since) = since {
+            base.push_str(&format!("&start_time={}", format_rfc3339(since)));
+        }
+
+        // State is the cursor of the next page to fetch; `None` once exhausted.
+        stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+            let client = client.clone();
+            let mut path = base.clone();
+            async move {
+                let Some(cursor) = cursor else {
+                    return Ok(None);
+                };
+                if let Some(cursor) = cursor {
+                    path.push_str(&format!("&cursor={}", encode_query_value(&cursor)));
+                }
+                client
+                    .get_page::<AuditLog>(&path)
+                    .await
+                    .map(|page| Some((page.items, page.next_cursor.map(Some))))
+            }
+        })
+        .map_ok(|logs| stream::iter(logs.into_iter().map(key_event)))
+        .try_flatten()
+        .try_filter(move |event| future::ready(since.is_none_or(|since| event.timestamp >= since)))
+    }
+}
+
+/// Convert an audit log entry into a [`KeyEvent`].
+fn key_event(log: AuditLog) -> Result<KeyEvent> {
+    let timestamp = parse_rfc3339(&log.created_at).ok_or_else(|| {
+        POPSignerError::Decode(format!("invalid audit log timestamp: {}", log.created_at))
+    })?;
+    let request_id = log
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("request_id"))
+        .and_then(|id| id.as_str())
+        .map(str::to_string);
+
+    Ok(KeyEvent {
+        id: log.id,
+        timestamp,
+        operation: log.event,
+        actor_id: log.actor_id,
+        actor_type: log.actor_type,
+        request_id,
+    })
+}
+
+/// Format `time` as an RFC 3339 UTC timestamp, truncated to whole seconds.
+fn format_rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let secs_of_day = secs.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Parse an RFC 3339 timestamp such as `2025-01-02T03:04:05.678+01:00`.
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let (year, month, day) = (
+        digits(&value[0..4])?,
+        digits(&value[5..7])?,
+        digits(&value[8..10])?,
+    );
+    let (hour, minute, second) = (
+        digits(&value[11..13])?,
+        digits(&value[14..16])?,
+        digits(&value[17..19])?,
+    );
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &value[19..];
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        // Precision beyond nanoseconds is dropped.
+        let kept = &fraction[..len.min(9)];
+        nanos = kept.parse::<u32>().ok()? * 10u32.pow(9 - kept.len() as u32);
+        rest = &fraction[len..];
+    }
+
+    let offset = if rest.eq_ignore_ascii_case("z") {
+        0
+    } else {
+        let sign = match rest.as_bytes().first()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+            return None;
+        }
+        sign * (digits(&rest[1..3])? * 3600 + digits(&rest[4..6])? * 60)
+    };
+
+    let secs =
+        days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    Some(UNIX_EPOCH + Duration::new(u64::try_from(secs).ok()?, nanos))
+}
+
+fn digits(value: &str) -> Option<i64> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
 }
 
 #[cfg(test)]
@@ -167,4 +394,74 @@ mod tests {
         assert!(query.resource_type.is_none());
         assert!(query.limit.is_none());
     }
+
+    #[test]
+    fn test_rfc3339_round_trip() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_735_787_045);
+        assert_eq!(format_rfc3339(time), "2025-01-02T03:04:05Z");
+        assert_eq!(parse_rfc3339("2025-01-02T03:04:05Z"), Some(time));
+        assert_eq!(parse_rfc3339("2025-01-02T04:04:05+01:00"), Some(time));
+        assert_eq!(
+            parse_rfc3339("2025-01-02T03:04:05.25Z"),
+            Some(time + Duration::from_millis(250))
+        );
+        assert_eq!(parse_rfc3339("2025-01-02 03:04"), None);
+        assert_eq!(parse_rfc3339("2025-13-02T03:04:05Z"), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_key_events() {
+        let mock = crate::testing::MockTransport::new();
+        let log = |event: &str, created_at: &str| {
+            serde_json::json!({
+                "id": Uuid::new_v4(),
+                "event": event,
+                "actor_id": Uuid::nil(),
+                "actor_type": "api_key",
+                "resource_type": "key",
+                "resource_id": Uuid::nil(),
+                "metadata": { "request_id": "req-1" },
+                "created_at": created_at
+            })
+        };
+        mock.push_response(crate::transport::Response::json(
+            200,
+            &serde_json::json!({
+                "data": [log("key.signed", "2025-01-03T00:00:00Z")],
+                "meta": { "next_cursor": "c2" }
+            }),
+        ));
+        // A server that ignores start_time returns an older event too.
+        mock.push_data(serde_json::json!([
+            log("key.signed", "2025-01-02T12:00:00.5Z"),
+            log("key.created", "2024-12-31T00:00:00Z")
+        ]));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let since = UNIX_EPOCH + Duration::from_secs(1_735_776_000); // 2025-01-02
+        let events: Vec<KeyEvent> = client
+            .audit()
+            .list_key_events(&Uuid::nil().into(), Some(since))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.operation == "key.signed"));
+        assert_eq!(events[0].request_id.as_deref(), Some("req-1"));
+
+        let requests = mock.requests();
+        assert_eq!(
+            requests[0].url.query(),
+            Some(
+                "resource_type=key&resource_id=00000000-0000-0000-0000-000000000000\
+                 &start_time=2025-01-02T00:00:00Z"
+            )
+        );
+        assert!(requests[1].url.query().unwrap().ends_with("&cursor=c2"));
+    }
 }
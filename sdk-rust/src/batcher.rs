@@ -0,0 +1,275 @@
+//! Automatic batching of individual sign requests.
+//!
+//! A [`SignBatcher`] collects requests enqueued from any number of tasks and
+//! sends them with [`SignClient::sign_batch`](crate::sign::SignClient::sign_batch)
+//! once `max_batch_size` requests are waiting or the oldest has waited
+//! `max_latency`, whichever comes first. Each caller gets the result for its
+//! own request.
+//!
+//! No background task is spawned: the waiting callers send the batch
+//! themselves, so the batcher works with any executor.
+//! [`Client::shutdown`](crate::Client::shutdown) sends any waiting requests
+//! before the client stops.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::batcher::SignBatcher;
+//! use popsigner::{BatchSignItem, Client, KeyId};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! let batcher = SignBatcher::new(client, 100, Duration::from_millis(50))?;
+//! let response = batcher
+//!     .enqueue(BatchSignItem {
+//!         key_id,
+//!         data: b"tx".to_vec(),
+//!         prehashed: false,
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{POPSignerError, Result};
+use crate::runtime::{self, Instant};
+use crate::types::{BatchSignItem, SignResponse};
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use std::pin::pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+type Waiter = oneshot::Sender<Result<SignResponse>>;
+
+/// Coalesces individual sign requests into batches.
+///
+/// Clones share the same queue.
+#[derive(Debug, Clone)]
+pub struct SignBatcher {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Inner {
+    client: Client,
+    max_batch_size: usize,
+    max_latency: Duration,
+    pending: Mutex<Pending>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    /// Incremented each time a batch is taken for sending.
+    generation: u64,
+    /// When the current batch must be sent.
+    deadline: Instant,
+    items: Vec<(BatchSignItem, Waiter)>,
+}
+
+impl SignBatcher {
+    /// Create a batcher that sends at most `max_batch_size` requests at a
+    /// time and holds a request for at most `max_latency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `max_batch_size` is
+    /// zero.
+    pub fn new(client: Client, max_batch_size: usize, max_latency: Duration) -> Result<Self> {
+        if max_batch_size == 0 {
+            return Err(POPSignerError::InvalidRequest(
+                "max_batch_size must be greater than zero".to_string(),
+            ));
+        }
+        let inner = Arc::new(Inner {
+            client,
+            max_batch_size,
+            max_latency,
+            pending: Mutex::new(Pending {
+                generation: 0,
+                deadline: Instant::now(),
+                items: Vec::new(),
+            }),
+        });
+        inner.client.register_batcher(&inner);
+        Ok(Self { inner })
+    }
+
+    /// Add a request to the current batch and wait for its result.
+    ///
+    /// The request joins a batch when the returned future is first polled.
+    /// Dropping the future before the batch is sent does not remove the
+    /// request from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the item's error from the batch. If the whole batch failed,
+    /// the first item gets the error and the others a
+    /// [`POPSignerError::SigningError`] describing it. Once the client has
+    /// been shut down, returns [`POPSignerError::InvalidRequest`] without
+    /// waiting.
+    pub async fn enqueue(&self, item: BatchSignItem) -> Result<SignResponse> {
+        self.inner.client.check_open()?;
+        let (tx, mut rx) = oneshot::channel();
+        let (generation, deadline, full) = {
+            let mut pending = self.inner.pending.lock().unwrap();
+            if pending.items.is_empty() {
+                pending.deadline = Instant::now() + self.inner.max_latency;
+            }
+            pending.items.push((item, tx));
+            let full = pending.items.len() >= self.inner.max_batch_size;
+            (pending.generation, pending.deadline, full)
+        };
+
+        if full {
+            self.send(Some(generation)).await;
+        } else {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            if let Either::Left((result, _)) =
+                future::select(&mut rx, pin!(runtime::sleep(wait))).await
+            {
+                return received(result);
+            }
+            // Whichever waiter wakes first sends the batch.
+            self.send(Some(generation)).await;
+        }
+        received(rx.await)
+    }
+
+    /// Send the requests waiting in the current batch now.
+    pub async fn flush(&self) {
+        self.send(None).await;
+    }
+
+    /// Take the current batch, if it is still `generation`, and send it.
+    async fn send(&self, generation: Option<u64>) {
+        let items = {
+            let mut pending = self.inner.pending.lock().unwrap();
+            if generation.is_some_and(|g| g != pending.generation) || pending.items.is_empty() {
+                return;
+            }
+            pending.generation += 1;
+            std::mem::take(&mut pending.items)
+        };
+
+        let (requests, waiters): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+        let count = requests.len();
+        match self.inner.client.sign().sign_batch(requests).await {
+            Ok(batch) => {
+                for (waiter, result) in waiters.into_iter().zip(batch.results) {
+                    let _ = waiter.send(result);
+                }
+            }
+            Err(e) => {
+                let message = e.to_string();
+                let mut waiters = waiters.into_iter();
+                if let Some(first) = waiters.next() {
+                    let _ = first.send(Err(e));
+                }
+                for waiter in waiters {
+                    let _ = waiter.send(Err(POPSignerError::SigningError(format!(
+                        "batch of {} failed: {}",
+                        count, message
+                    ))));
+                }
+            }
+        }
+    }
+}
+
+/// Send the requests waiting in each batcher that is still alive.
+pub(crate) async fn flush_all(batchers: Vec<Weak<Inner>>) {
+    for inner in batchers.iter().filter_map(Weak::upgrade) {
+        SignBatcher { inner }.flush().await;
+    }
+}
+
+fn received(
+    result: std::result::Result<Result<SignResponse>, oneshot::Canceled>,
+) -> Result<SignResponse> {
+    result.unwrap_or_else(|_| {
+        Err(POPSignerError::SigningError(
+            "batch was dropped before it was sent".to_string(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use uuid::Uuid;
+
+    fn item(i: u128) -> BatchSignItem {
+        BatchSignItem {
+            key_id: Uuid::from_u128(i).into(),
+            data: b"tx".to_vec(),
+            prehashed: false,
+        }
+    }
+
+    fn signatures(ids: &[u128]) -> serde_json::Value {
+        let signatures: Vec<_> = ids
+            .iter()
+            .map(|&i| {
+                serde_json::json!({
+                    "key_id": Uuid::from_u128(i),
+                    "signature": BASE64.encode([i as u8]),
+                    "public_key": "pk"
+                })
+            })
+            .collect();
+        serde_json::json!({ "signatures": signatures })
+    }
+
+    #[tokio::test]
+    async fn test_flushes_by_size_and_latency() {
+        let mock = MockTransport::new();
+        mock.push_data(signatures(&[1, 2]));
+        mock.push_data(signatures(&[3]));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let batcher = SignBatcher::new(client.clone(), 2, Duration::from_secs(60)).unwrap();
+        let (a, b) = futures::join!(batcher.enqueue(item(1)), batcher.enqueue(item(2)));
+        assert_eq!(a.unwrap().signature, vec![1]);
+        assert_eq!(b.unwrap().signature, vec![2]);
+        assert_eq!(mock.requests().len(), 1);
+
+        let batcher = SignBatcher::new(client, 10, Duration::from_millis(10)).unwrap();
+        assert_eq!(batcher.enqueue(item(3)).await.unwrap().signature, vec![3]);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_pending_batch() {
+        let mock = MockTransport::new();
+        mock.push_data(signatures(&[1]));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let batcher = SignBatcher::new(client.clone(), 10, Duration::from_secs(60)).unwrap();
+        let (response, abandoned) = futures::join!(
+            batcher.enqueue(item(1)),
+            client.shutdown(Duration::from_secs(1))
+        );
+        assert_eq!(response.unwrap().signature, vec![1]);
+        assert_eq!(abandoned, 0);
+        assert_eq!(mock.requests().len(), 1);
+
+        let err = batcher.enqueue(item(2)).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(ref m) if m == "client shut down"));
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
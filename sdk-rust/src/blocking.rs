@@ -0,0 +1,824 @@
+//! Blocking (synchronous) client.
+//!
+//! This module mirrors the async API for programs that do not run an async
+//! runtime, similar to `reqwest::blocking`. Each [`Client`] drives its calls
+//! on a dedicated internal single-threaded Tokio runtime, shared by all of its
+//! clones and sub-clients.
+//!
+//! Requires the `blocking` feature.
+//!
+//! # Panics
+//!
+//! Calling a blocking method from inside an existing Tokio runtime panics,
+//! as does dropping the last clone of a blocking [`Client`] there. Use the
+//! async [`crate::Client`] in async code instead.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::KeyId;
+//! use popsigner::blocking::Client;
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("psk_live_xxxxx");
+//!     let key_id: KeyId = "...".parse()?;
+//!
+//!     let result = client.sign().sign(&key_id, b"payload", false)?;
+//!     println!("Signature: {} bytes", result.signature.len());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::client::ClientConfig;
+use crate::cose::CborMap;
+use crate::error::Result;
+use crate::ids::{KeyId, NamespaceId};
+use crate::jobs::{SignJobHandle, SignJobStatus};
+use crate::jwks::Jwks;
+use crate::jwt::JwtHeader;
+use crate::merkle::MerkleSignature;
+use crate::types::{
+    AuditLog, BatchOptions, BatchResult, BatchSignItem, CostEstimate, CreateBatchRequest,
+    CreateKeyRequest, DigestAlgorithm, HealthStatus, Key, KeyEvent, KeySpec, KeyVersion,
+    LabelSelector, ListAuditLogsQuery, Namespace, NamespaceSettings, Organization, Page,
+    PaginatedResponse, PublicKeys, QuotaStatus, SelfTestReport, SignRequest, SignResponse, Timed,
+    ValidationReport, VerifyRequest, VerifyResult,
+};
+use futures::stream::{Stream, StreamExt};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Blocking POPSigner client.
+///
+/// Cloning is cheap: clones share the connection pool, state, and runtime.
+/// The `Debug` output never shows the credentials.
+#[derive(Clone, Debug)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new blocking client with default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client or the internal runtime cannot be created.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self::from_async(crate::Client::new(api_key))
+    }
+
+    /// Create a new blocking client with custom configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.base_url` is not a valid URL or the internal
+    /// runtime cannot be created.
+    pub fn with_config(api_key: impl Into<String>, config: ClientConfig) -> Self {
+        Self::from_async(crate::Client::with_config(api_key, config))
+    }
+
+    /// Wrap an async client built with [`crate::Client::builder`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> popsigner::Result<()> {
+    /// let client = popsigner::Client::builder()
+    ///     .api_key("psk_live_xxxxx")
+    ///     .build()?;
+    /// let client = popsigner::blocking::Client::from_async(client);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal runtime cannot be created.
+    pub fn from_async(client: crate::Client) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create blocking runtime");
+        Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client this wraps.
+    pub fn as_async(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Get the base URL for the API.
+    pub fn base_url(&self) -> &str {
+        self.inner.base_url()
+    }
+
+    /// Get the blocking keys client.
+    pub fn keys(&self) -> KeysClient {
+        KeysClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the blocking signing client.
+    pub fn sign(&self) -> SignClient {
+        SignClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get a blocking handle for operations within one namespace.
+    pub fn namespace(&self, namespace_id: &NamespaceId) -> NamespaceClient {
+        NamespaceClient {
+            client: self.clone(),
+            namespace_id: *namespace_id,
+        }
+    }
+
+    /// Get the blocking organizations client.
+    pub fn orgs(&self) -> OrgsClient {
+        OrgsClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the blocking audit client.
+    pub fn audit(&self) -> AuditClient {
+        AuditClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Blocking counterpart of [`crate::Client::create_namespace`].
+    pub fn create_namespace(
+        &self,
+        org_id: &Uuid,
+        name: &str,
+        settings: NamespaceSettings,
+    ) -> Result<Namespace> {
+        self.block_on(self.inner.create_namespace(org_id, name, settings))
+    }
+
+    /// Blocking counterpart of [`crate::Client::delete_namespace`].
+    pub fn delete_namespace(&self, org_id: &Uuid, name: &str, force: bool) -> Result<()> {
+        self.block_on(self.inner.delete_namespace(org_id, name, force))
+    }
+
+    /// Blocking counterpart of [`crate::Client::estimate_cost`].
+    pub fn estimate_cost(&self, requests: &[SignRequest]) -> Result<CostEstimate> {
+        self.block_on(self.inner.estimate_cost(requests))
+    }
+
+    /// Blocking counterpart of [`crate::Client::health_check`].
+    pub fn health_check(&self) -> Result<HealthStatus> {
+        self.block_on(self.inner.health_check())
+    }
+
+    /// Blocking counterpart of [`crate::Client::shutdown`].
+    pub fn shutdown(&self, grace: Duration) -> usize {
+        self.block_on(self.inner.shutdown(grace))
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+/// Blocking counterpart of [`crate::keys::KeysClient`].
+pub struct KeysClient {
+    client: Client,
+}
+
+impl KeysClient {
+    /// Create a new key.
+    pub fn create(&self, request: CreateKeyRequest) -> Result<Key> {
+        self.client
+            .block_on(self.client.inner.keys().create(request))
+    }
+
+    /// Create multiple keys in parallel.
+    pub fn create_batch(&self, request: CreateBatchRequest) -> Result<Vec<Key>> {
+        self.client
+            .block_on(self.client.inner.keys().create_batch(request))
+    }
+
+    /// Create keys from individual specs, one result per spec.
+    pub fn create_keys(&self, namespace_id: &NamespaceId, specs: Vec<KeySpec>) -> BatchResult<Key> {
+        self.client
+            .block_on(self.client.inner.keys().create_keys(namespace_id, specs))
+    }
+
+    /// Create keys from individual specs, stopping when `cancel` fires.
+    pub fn create_keys_with_cancel(
+        &self,
+        namespace_id: &NamespaceId,
+        specs: Vec<KeySpec>,
+        cancel: CancellationToken,
+    ) -> BatchResult<Key> {
+        self.client
+            .block_on(
+                self.client
+                    .inner
+                    .keys()
+                    .create_keys_with_cancel(namespace_id, specs, cancel),
+            )
+    }
+
+    /// Get a key by ID.
+    pub fn get(&self, key_id: &KeyId) -> Result<Key> {
+        self.client.block_on(self.client.inner.keys().get(key_id))
+    }
+
+    /// Get a key by name within a namespace.
+    pub fn get_by_name(&self, namespace_id: &NamespaceId, name: &str) -> Result<Key> {
+        self.client
+            .block_on(self.client.inner.keys().get_by_name(namespace_id, name))
+    }
+
+    /// List all keys, optionally filtered by namespace.
+    pub fn list(&self, namespace_id: Option<&NamespaceId>) -> Result<Vec<Key>> {
+        self.client
+            .block_on(self.client.inner.keys().list(namespace_id))
+    }
+
+    /// Fetch a single page of keys.
+    pub fn list_page(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        cursor: Option<&str>,
+    ) -> Result<Page<Key>> {
+        self.client
+            .block_on(self.client.inner.keys().list_page(namespace_id, cursor))
+    }
+
+    /// Iterate over all keys, fetching further pages as needed.
+    ///
+    /// Blocking counterpart of [`crate::keys::KeysClient::list_stream`].
+    pub fn list_iter(&self, namespace_id: Option<&NamespaceId>) -> KeyIter {
+        KeyIter::new(
+            self.client.clone(),
+            self.client.inner.keys().list_stream(namespace_id),
+        )
+    }
+
+    /// List every key carrying all labels of `selector`.
+    pub fn list_matching(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: &LabelSelector,
+    ) -> Result<Vec<Key>> {
+        self.client.block_on(
+            self.client
+                .inner
+                .keys()
+                .list_matching(namespace_id, selector),
+        )
+    }
+
+    /// Iterate over every key carrying all labels of `selector`.
+    pub fn list_iter_matching(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: &LabelSelector,
+    ) -> KeyIter {
+        KeyIter::new(
+            self.client.clone(),
+            self.client
+                .inner
+                .keys()
+                .list_stream_matching(namespace_id, selector),
+        )
+    }
+
+    /// Delete a key.
+    pub fn delete(&self, key_id: &KeyId) -> Result<()> {
+        self.client
+            .block_on(self.client.inner.keys().delete(key_id))
+    }
+
+    /// Disable a key.
+    pub fn disable(&self, key_id: &KeyId) -> Result<Key> {
+        self.client
+            .block_on(self.client.inner.keys().disable(key_id))
+    }
+
+    /// Enable a disabled key.
+    pub fn enable(&self, key_id: &KeyId) -> Result<Key> {
+        self.client
+            .block_on(self.client.inner.keys().enable(key_id))
+    }
+
+    /// List the versions of a key.
+    pub fn list_versions(&self, key_id: &KeyId) -> Result<Vec<KeyVersion>> {
+        self.client
+            .block_on(self.client.inner.keys().list_versions(key_id))
+    }
+
+    /// Replace the labels of a key.
+    pub fn set_labels(&self, key_id: &KeyId, labels: BTreeMap<String, String>) -> Result<Key> {
+        self.client
+            .block_on(self.client.inner.keys().set_labels(key_id, labels))
+    }
+}
+
+/// Blocking counterpart of [`crate::sign::SignClient`].
+pub struct SignClient {
+    client: Client,
+}
+
+impl SignClient {
+    /// Sign data with a key.
+    pub fn sign(&self, key_id: &KeyId, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+        self.client
+            .block_on(self.client.inner.sign().sign(key_id, data, prehashed))
+    }
+
+    /// Sign a precomputed digest, checking its length first.
+    pub fn sign_digest(
+        &self,
+        key_id: &KeyId,
+        digest: &[u8],
+        algorithm: DigestAlgorithm,
+    ) -> Result<SignResponse> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_digest(key_id, digest, algorithm),
+        )
+    }
+
+    /// Sign a SHA-512 prehash with Ed25519ph, optionally with a context.
+    pub fn sign_digest_ed25519ph(
+        &self,
+        key_id: &KeyId,
+        digest: &[u8],
+        context: Option<&[u8]>,
+    ) -> Result<SignResponse> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_digest_ed25519ph(key_id, digest, context),
+        )
+    }
+
+    /// Sign data using a [`SignRequest`].
+    pub fn sign_request(&self, request: &SignRequest) -> Result<SignResponse> {
+        self.client
+            .block_on(self.client.inner.sign().sign_request(request))
+    }
+
+    /// Sign data using a [`SignRequest`], also returning timing and the
+    /// attempt count.
+    pub fn sign_request_timed(&self, request: &SignRequest) -> Result<Timed<SignResponse>> {
+        self.client
+            .block_on(self.client.inner.sign().sign_request_timed(request))
+    }
+
+    /// Submit a sign request as a job without waiting for the signature.
+    pub fn sign_async(&self, request: &SignRequest) -> Result<SignJobHandle> {
+        self.client
+            .block_on(self.client.inner.sign().sign_async(request))
+    }
+
+    /// Check on a job submitted with [`sign_async`](Self::sign_async).
+    pub fn poll_sign_job(&self, job: &SignJobHandle) -> Result<SignJobStatus> {
+        self.client
+            .block_on(self.client.inner.sign().poll_sign_job(job))
+    }
+
+    /// Poll a job every `poll_interval` until it finishes.
+    pub fn await_sign_job(
+        &self,
+        job: &SignJobHandle,
+        poll_interval: Duration,
+    ) -> Result<SignResponse> {
+        self.client.block_on(job.await_completion(poll_interval))
+    }
+
+    /// Sign multiple messages in a single API call.
+    pub fn sign_batch(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+    ) -> Result<BatchResult> {
+        self.client
+            .block_on(self.client.inner.sign().sign_batch(requests))
+    }
+
+    /// Sign a batch of any size in chunks of at most `max_chunk` items.
+    pub fn sign_batch_chunked(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        max_chunk: usize,
+    ) -> Result<BatchResult> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_batch_chunked(requests, max_chunk),
+        )
+    }
+
+    /// Sign a batch with [`BatchOptions`].
+    pub fn sign_batch_with_options(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        options: &BatchOptions,
+    ) -> Result<BatchResult> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_batch_with_options(requests, options),
+        )
+    }
+
+    /// Sign a batch in chunks with [`BatchOptions`].
+    pub fn sign_batch_chunked_with_options(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        max_chunk: usize,
+        options: &BatchOptions,
+    ) -> Result<BatchResult> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_batch_chunked_with_options(requests, max_chunk, options),
+        )
+    }
+
+    /// Sign a JWT and return its compact serialization.
+    pub fn sign_jwt(
+        &self,
+        key_id: &KeyId,
+        header: JwtHeader,
+        claims: serde_json::Value,
+    ) -> Result<String> {
+        self.client
+            .block_on(self.client.inner.sign().sign_jwt(key_id, header, claims))
+    }
+
+    /// Validate a sign request without signing.
+    pub fn validate(&self, request: &SignRequest) -> Result<ValidationReport> {
+        self.client
+            .block_on(self.client.inner.sign().validate(request))
+    }
+
+    /// Validate a batch without signing.
+    pub fn validate_batch(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+    ) -> Result<Vec<ValidationReport>> {
+        self.client
+            .block_on(self.client.inner.sign().validate_batch(requests))
+    }
+
+    /// Sign several messages with one key in a single call.
+    pub fn sign_multi(
+        &self,
+        key_id: &KeyId,
+        messages: Vec<Vec<u8>>,
+        prehashed: bool,
+    ) -> Result<Vec<SignResponse>> {
+        self.client.block_on(
+            self.client
+                .inner
+                .sign()
+                .sign_multi(key_id, messages, prehashed),
+        )
+    }
+
+    /// Sign many requests with bounded concurrency.
+    pub fn sign_batch_concurrent(
+        &self,
+        requests: impl IntoIterator<Item = SignRequest>,
+        concurrency: usize,
+    ) -> BatchResult {
+        self.client.block_on(
+            self.client
+                .client
+                .sign()
+                .sign_batch_concurrent(requests, concurrency),
+        )
+    }
+
+    /// Sign many requests concurrently, stopping when `cancel` fires.
+    pub fn sign_batch_concurrent_with_cancel(
+        &self,
+        requests: impl IntoIterator<Item = SignRequest>,
+        concurrency: usize,
+        cancel: CancellationToken,
+    ) -> BatchResult {
+        self.client
+            .block_on(self.client.inner.sign().sign_batch_concurrent_with_cancel(
+                requests,
+                concurrency,
+                cancel,
+            ))
+    }
+
+    /// Verify a signature.
+    pub fn verify(
+        &self,
+        key_id: &KeyId,
+        data: &[u8],
+        signature: &[u8],
+        prehashed: bool,
+    ) -> Result<bool> {
+        self.client.block_on(
+            self.client
+                .client
+                .sign()
+                .verify(key_id, data, signature, prehashed),
+        )
+    }
+
+    /// Verify many signatures in a single API call.
+    pub fn verify_batch(
+        &self,
+        requests: impl IntoIterator<Item = VerifyRequest>,
+    ) -> Result<BatchResult<VerifyResult>> {
+        self.client
+            .block_on(self.client.inner.sign().verify_batch(requests))
+    }
+}
+
+/// Blocking counterpart of [`crate::namespace::NamespaceClient`].
+pub struct NamespaceClient {
+    client: Client,
+    namespace_id: NamespaceId,
+}
+
+impl NamespaceClient {
+    /// The namespace this handle is bound to.
+    pub fn namespace_id(&self) -> &NamespaceId {
+        &self.namespace_id
+    }
+
+    /// List all keys in the namespace.
+    pub fn list_keys(&self) -> Result<Vec<Key>> {
+        self.client.keys().list(Some(&self.namespace_id))
+    }
+
+    /// Iterate over all keys in the namespace, fetching pages as needed.
+    pub fn list_keys_iter(&self) -> KeyIter {
+        self.client.keys().list_iter(Some(&self.namespace_id))
+    }
+
+    /// List the keys in the namespace carrying all labels of `selector`.
+    pub fn list_keys_matching(&self, selector: &LabelSelector) -> Result<Vec<Key>> {
+        self.client
+            .keys()
+            .list_matching(Some(&self.namespace_id), selector)
+    }
+
+    /// Iterate over the keys in the namespace carrying all labels of
+    /// `selector`.
+    pub fn list_keys_iter_matching(&self, selector: &LabelSelector) -> KeyIter {
+        self.client
+            .keys()
+            .list_iter_matching(Some(&self.namespace_id), selector)
+    }
+
+    /// Get a key in the namespace by name.
+    pub fn get_key(&self, name: &str) -> Result<Key> {
+        self.client.keys().get_by_name(&self.namespace_id, name)
+    }
+
+    /// Sign data with the key of the given name.
+    pub fn sign(&self, key_name: &str, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.sign(key_name, data, prehashed))
+    }
+
+    /// Sign data with `key_id` and verify the signature locally.
+    pub fn sign_verified(&self, key_id: &KeyId, message: &[u8]) -> Result<SignResponse> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.sign_verified(key_id, message))
+    }
+
+    /// Sign with both the outgoing and the incoming key of a rotation.
+    pub fn sign_dual(
+        &self,
+        old_key: &KeyId,
+        new_key: &KeyId,
+        message: &[u8],
+    ) -> Result<(SignResponse, SignResponse)> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.sign_dual(old_key, new_key, message))
+    }
+
+    /// Sign the root of a Merkle tree over `leaves`.
+    pub fn sign_merkle_root(
+        &self,
+        key_id: &KeyId,
+        leaves: Vec<Vec<u8>>,
+        hash: DigestAlgorithm,
+    ) -> Result<MerkleSignature> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.sign_merkle_root(key_id, leaves, hash))
+    }
+
+    /// Sign `payload` as a COSE_Sign1 message.
+    pub fn sign_cose_sign1(
+        &self,
+        key_id: &KeyId,
+        protected: CborMap,
+        payload: &[u8],
+    ) -> Result<Vec<u8>> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.sign_cose_sign1(key_id, protected, payload))
+    }
+
+    /// Check end-to-end health with `key_id`.
+    pub fn self_test(&self, key_id: &KeyId) -> Result<SelfTestReport> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client.block_on(namespace.self_test(key_id))
+    }
+
+    /// Fetch the public keys of `key_ids` in one request.
+    pub fn get_public_keys(&self, key_ids: &[KeyId]) -> Result<PublicKeys> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client.block_on(namespace.get_public_keys(key_ids))
+    }
+
+    /// List the versions of a key in the namespace.
+    pub fn list_key_versions(&self, key_id: &KeyId) -> Result<Vec<KeyVersion>> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client.block_on(namespace.list_key_versions(key_id))
+    }
+
+    /// Replace the labels of a key in the namespace.
+    pub fn set_key_labels(&self, key_id: &KeyId, labels: BTreeMap<String, String>) -> Result<Key> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client
+            .block_on(namespace.set_key_labels(key_id, labels))
+    }
+
+    /// Export the namespace's public keys as a JSON Web Key Set.
+    pub fn export_jwks(&self) -> Result<Jwks> {
+        let namespace = self.client.inner.namespace(&self.namespace_id);
+        self.client.block_on(namespace.export_jwks())
+    }
+}
+
+/// Blocking counterpart of [`crate::orgs::OrgsClient`].
+pub struct OrgsClient {
+    client: Client,
+}
+
+impl OrgsClient {
+    /// Get the current organization.
+    pub fn get_current(&self) -> Result<Organization> {
+        self.client.block_on(self.client.inner.orgs().get_current())
+    }
+
+    /// Get the organization's signing quota usage.
+    pub fn get_quota(&self, org_id: &Uuid) -> Result<QuotaStatus> {
+        self.client
+            .block_on(self.client.inner.orgs().get_quota(org_id))
+    }
+
+    /// List all namespaces in the organization.
+    pub fn list_namespaces(&self) -> Result<Vec<Namespace>> {
+        self.client
+            .block_on(self.client.inner.orgs().list_namespaces())
+    }
+
+    /// Get a namespace by ID.
+    pub fn get_namespace(&self, namespace_id: &NamespaceId) -> Result<Namespace> {
+        self.client
+            .block_on(self.client.inner.orgs().get_namespace(namespace_id))
+    }
+
+    /// Create a new namespace.
+    pub fn create_namespace(&self, name: &str) -> Result<Namespace> {
+        self.client
+            .block_on(self.client.inner.orgs().create_namespace(name))
+    }
+
+    /// Delete a namespace.
+    pub fn delete_namespace(&self, namespace_id: &NamespaceId) -> Result<()> {
+        self.client
+            .block_on(self.client.inner.orgs().delete_namespace(namespace_id))
+    }
+}
+
+/// Blocking counterpart of [`crate::audit::AuditClient`].
+pub struct AuditClient {
+    client: Client,
+}
+
+impl AuditClient {
+    /// List audit logs with optional filtering.
+    pub fn list(&self, query: Option<ListAuditLogsQuery>) -> Result<PaginatedResponse<AuditLog>> {
+        self.client.block_on(self.client.inner.audit().list(query))
+    }
+
+    /// Get a specific audit log entry.
+    pub fn get(&self, log_id: &Uuid) -> Result<AuditLog> {
+        self.client.block_on(self.client.inner.audit().get(log_id))
+    }
+
+    /// List audit logs for a specific resource.
+    pub fn list_for_resource(
+        &self,
+        resource_type: &str,
+        resource_id: &Uuid,
+    ) -> Result<PaginatedResponse<AuditLog>> {
+        self.client.block_on(
+            self.client
+                .client
+                .audit()
+                .list_for_resource(resource_type, resource_id),
+        )
+    }
+
+    /// Blocking counterpart of [`crate::audit::AuditClient::list_key_events`].
+    pub fn list_key_events_iter(&self, key_id: &KeyId, since: Option<SystemTime>) -> KeyEventIter {
+        KeyEventIter {
+            client: self.client.clone(),
+            stream: Box::pin(self.client.inner.audit().list_key_events(key_id, since)),
+        }
+    }
+}
+
+/// Blocking iterator over keys, returned by [`KeysClient::list_iter`].
+///
+/// Each page is fetched on the client's runtime when the previous one is
+/// exhausted. Iteration ends after the first error.
+pub struct KeyIter {
+    client: Client,
+    stream: Pin<Box<dyn Stream<Item = Result<Key>> + Send>>,
+}
+
+impl KeyIter {
+    fn new(client: Client, stream: impl Stream<Item = Result<Key>> + Send + 'static) -> Self {
+        Self {
+            client,
+            stream: Box::pin(stream),
+        }
+    }
+}
+
+impl Iterator for KeyIter {
+    type Item = Result<Key>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.client.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Blocking iterator over key events, returned by
+/// [`AuditClient::list_key_events_iter`].
+///
+/// Iteration ends after the first error.
+pub struct KeyEventIter {
+    client: Client,
+    stream: Pin<Box<dyn Stream<Item = Result<KeyEvent>> + Send>>,
+}
+
+impl Iterator for KeyEventIter {
+    type Item = Result<KeyEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.client.runtime.block_on(self.stream.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_client_shares_async_client() {
+        let client = Client::with_config(
+            "test_key",
+            ClientConfig {
+                base_url: Some("https://custom.api.com".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(client.base_url(), "https://custom.api.com");
+
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(&client.runtime, &clone.runtime));
+        assert_eq!(clone.as_async().base_url(), "https://custom.api.com");
+    }
+}
@@ -0,0 +1,285 @@
+//! Client-side key metadata and verification caches.
+//!
+//! Both are shared by every clone of a [`Client`](crate::Client). Key
+//! metadata reads and, optionally, verification results are cached; signing
+//! always goes to the server.
+
+use crate::ids::{KeyId, NamespaceId};
+use crate::runtime::Instant;
+use crate::types::{Key, KeyState};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bounded TTL cache of keys, identified by `(namespace_id, key_id)`.
+///
+/// Key IDs are globally unique, so entries are indexed by ID alone and the
+/// namespace is checked on invalidation.
+#[derive(Debug)]
+pub(crate) struct KeyCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<KeyId, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: Key,
+    expires_at: Instant,
+}
+
+impl KeyCache {
+    /// Create a cache holding up to `max_entries` keys for `ttl` each.
+    pub(crate) fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the cached key, if present and not expired.
+    pub(crate) fn get(&self, key_id: &KeyId) -> Option<Key> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key_id) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.key.clone()),
+            Some(_) => {
+                entries.remove(key_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache `key`, evicting expired entries and then the oldest if full.
+    pub(crate) fn insert(&self, key: Key) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+
+        if !entries.contains_key(&key.id) && entries.len() >= self.max_entries {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(id, _)| *id);
+                if let Some(id) = oldest {
+                    entries.remove(&id);
+                }
+            }
+        }
+
+        entries.insert(
+            key.id,
+            Entry {
+                key,
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    /// Drop the entry for `key_id`, whatever its namespace.
+    pub(crate) fn remove(&self, key_id: &KeyId) {
+        self.entries.lock().unwrap().remove(key_id);
+    }
+
+    /// Drop the entry for `key_id` if it belongs to `namespace_id`.
+    pub(crate) fn remove_in(&self, namespace_id: &NamespaceId, key_id: &KeyId) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries
+            .get(key_id)
+            .is_some_and(|entry| entry.key.namespace_id == *namespace_id)
+        {
+            entries.remove(key_id);
+        }
+    }
+}
+
+/// Bounded LRU cache of verification results, valid or not.
+///
+/// Entries are dropped when a key is seen disabled, deleted or rotated.
+#[derive(Debug)]
+pub(crate) struct VerifyCache {
+    max_entries: usize,
+    state: Mutex<VerifyState>,
+}
+
+#[derive(Debug, Default)]
+struct VerifyState {
+    /// Advanced on every access, to find the least recently used entry.
+    clock: u64,
+    entries: HashMap<Verification, VerifyEntry>,
+    /// `rotated_at` of each key when it was last seen.
+    rotations: HashMap<KeyId, Option<String>>,
+}
+
+#[derive(Debug)]
+struct VerifyEntry {
+    valid: bool,
+    used: u64,
+}
+
+/// A verification, identified by key and the SHA-256 of its inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Verification {
+    key_id: KeyId,
+    prehashed: bool,
+    data: [u8; 32],
+    signature: [u8; 32],
+}
+
+impl Verification {
+    pub(crate) fn new(key_id: &KeyId, data: &[u8], signature: &[u8], prehashed: bool) -> Self {
+        Self {
+            key_id: *key_id,
+            prehashed,
+            data: Sha256::digest(data).into(),
+            signature: Sha256::digest(signature).into(),
+        }
+    }
+}
+
+impl VerifyCache {
+    /// Create a cache holding up to `max_entries` results.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            state: Mutex::new(VerifyState::default()),
+        }
+    }
+
+    /// Return the cached result, marking it as recently used.
+    pub(crate) fn get(&self, verification: &Verification) -> Option<bool> {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(verification)?;
+        entry.used = clock;
+        Some(entry.valid)
+    }
+
+    /// Cache a result, evicting the least recently used entry if full.
+    pub(crate) fn insert(&self, verification: Verification, valid: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let used = state.clock;
+
+        if !state.entries.contains_key(&verification) && state.entries.len() >= self.max_entries {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(verification, _)| verification.clone());
+            if let Some(verification) = oldest {
+                state.entries.remove(&verification);
+            }
+        }
+        let entry = VerifyEntry { valid, used };
+        state.entries.insert(verification, entry);
+    }
+
+    /// Drop every result for `key_id`.
+    pub(crate) fn remove_key(&self, key_id: &KeyId) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.retain(|v, _| v.key_id != *key_id);
+    }
+
+    /// Drop the results for `key` if it is not active or has rotated since
+    /// it was last seen.
+    pub(crate) fn observe(&self, key: &Key) {
+        let mut state = self.state.lock().unwrap();
+        let rotated = match state.rotations.get(&key.id) {
+            Some(seen) => *seen != key.rotated_at,
+            None => key.rotated_at.is_some(),
+        };
+        if rotated || key.state != KeyState::Active {
+            state.entries.retain(|v, _| v.key_id != key.id);
+        }
+        if state.rotations.len() >= self.max_entries {
+            // Forgetting a key only costs one spurious invalidation.
+            state.rotations.clear();
+        }
+        state.rotations.insert(key.id, key.rotated_at.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::KeyUsage;
+    use uuid::Uuid;
+
+    fn key(id: u128) -> Key {
+        Key {
+            id: Uuid::from_u128(id).into(),
+            name: format!("key-{}", id),
+            namespace_id: Uuid::from_u128(100).into(),
+            public_key: "pk".to_string(),
+            address: "addr".to_string(),
+            algorithm: "secp256k1".to_string(),
+            exportable: false,
+            state: KeyState::Active,
+            usage: KeyUsage::Signing,
+            description: None,
+            labels: Default::default(),
+            metadata: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            rotated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = KeyCache::new(Duration::ZERO, 10);
+        cache.insert(key(1));
+        assert!(cache.get(&Uuid::from_u128(1).into()).is_none());
+    }
+
+    #[test]
+    fn test_evicts_oldest_when_full() {
+        let cache = KeyCache::new(Duration::from_secs(60), 2);
+        cache.insert(key(1));
+        std::thread::sleep(Duration::from_millis(1));
+        cache.insert(key(2));
+        std::thread::sleep(Duration::from_millis(1));
+        cache.insert(key(3));
+
+        assert!(cache.get(&Uuid::from_u128(1).into()).is_none());
+        assert!(cache.get(&Uuid::from_u128(2).into()).is_some());
+        assert!(cache.get(&Uuid::from_u128(3).into()).is_some());
+    }
+
+    #[test]
+    fn test_remove_in_checks_namespace() {
+        let cache = KeyCache::new(Duration::from_secs(60), 10);
+        cache.insert(key(1));
+
+        cache.remove_in(&Uuid::from_u128(999).into(), &Uuid::from_u128(1).into());
+        assert!(cache.get(&Uuid::from_u128(1).into()).is_some());
+
+        cache.remove_in(&Uuid::from_u128(100).into(), &Uuid::from_u128(1).into());
+        assert!(cache.get(&Uuid::from_u128(1).into()).is_none());
+    }
+
+    #[test]
+    fn test_verify_cache_lru_and_rotation() {
+        let cache = VerifyCache::new(2);
+        let id = Uuid::from_u128(1).into();
+        let a = Verification::new(&id, b"a", b"sig", false);
+        let b = Verification::new(&id, b"b", b"sig", false);
+        cache.insert(a.clone(), true);
+        cache.insert(b.clone(), false);
+        assert_eq!(cache.get(&a), Some(true));
+        cache.insert(Verification::new(&id, b"c", b"sig", false), true);
+        assert_eq!(cache.get(&a), Some(true));
+        assert_eq!(cache.get(&b), None);
+
+        let mut key = key(1);
+        cache.observe(&key);
+        assert_eq!(cache.get(&a), Some(true));
+        key.rotated_at = Some("2025-06-01T00:00:00Z".to_string());
+        cache.observe(&key);
+        assert_eq!(cache.get(&a), None);
+    }
+}
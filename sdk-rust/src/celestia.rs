@@ -50,11 +50,11 @@
 
 use crate::client::{Client as POPSignerClient, ClientConfig as POPSignerClientConfig};
 use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
 use async_trait::async_trait;
 use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
-use uuid::Uuid;
 
 // Re-export types
 pub use crate::types::Key;
@@ -144,14 +144,17 @@ impl std::error::Error for SignerError {}
 impl From<POPSignerError> for SignerError {
     fn from(err: POPSignerError) -> Self {
         match err {
-            POPSignerError::Unauthorized => {
+            POPSignerError::Unauthorized { .. } => {
                 SignerError::Authentication("invalid API key".to_string())
             }
-            POPSignerError::RateLimited => SignerError::RateLimited,
+            POPSignerError::RateLimited { .. } => SignerError::RateLimited,
             POPSignerError::KeyNotFound(msg) => SignerError::KeyNotFound(msg),
             POPSignerError::Http(e) => SignerError::Network(e.to_string()),
+            POPSignerError::Timeout => SignerError::Network("request timed out".to_string()),
+            POPSignerError::Connect(msg) => SignerError::Network(msg),
             POPSignerError::SigningError(msg) => SignerError::SigningFailed(msg),
             POPSignerError::Api { message, .. } => SignerError::SigningFailed(message),
+            POPSignerError::RetriesExhausted { last, .. } => (*last).into(),
             other => SignerError::SigningFailed(other.to_string()),
         }
     }
@@ -167,7 +170,7 @@ impl From<POPSignerError> for SignerError {
 /// Private keys never leave POPSigner.
 pub struct POPSignerSigner {
     client: POPSignerClient,
-    key_id: Uuid,
+    key_id: KeyId,
     key_name: String,
     public_key: Vec<u8>,
     celestia_address: String,
@@ -219,7 +222,7 @@ impl POPSignerSigner {
     }
 
     async fn resolve_key(client: &POPSignerClient, key_name_or_id: &str) -> Result<Key> {
-        if let Ok(key_id) = Uuid::parse_str(key_name_or_id) {
+        if let Ok(key_id) = key_name_or_id.parse::<KeyId>() {
             return client.keys().get(&key_id).await;
         }
 
@@ -249,7 +252,7 @@ impl POPSignerSigner {
     }
 
     /// Returns the key ID.
-    pub fn key_id(&self) -> Uuid {
+    pub fn key_id(&self) -> KeyId {
         self.key_id
     }
 
@@ -0,0 +1,150 @@
+//! Client-side circuit breaker.
+//!
+//! Shared by every clone of a [`Client`](crate::Client). After
+//! `failure_threshold` consecutive retryable failures the breaker opens and
+//! requests fail fast with
+//! [`POPSignerError::CircuitOpen`](crate::POPSignerError::CircuitOpen) for
+//! `open_duration`. Then a single probe is let through: success closes the
+//! breaker, failure opens it again.
+
+use crate::runtime::Instant;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Consecutive-failure circuit breaker.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+enum State {
+    /// Requests are sent; counts consecutive failures.
+    Closed { failures: u32 },
+    /// Requests fail fast until `until`.
+    Open { until: Instant },
+    /// A probe has been in flight since `since`.
+    HalfOpen { since: Instant },
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker.
+    pub(crate) fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Check whether a request may be sent.
+    ///
+    /// Returns the time until the next probe is allowed if the breaker is
+    /// open, or half-open with a probe already in flight.
+    pub(crate) fn try_acquire(&self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let until = match *state {
+            State::Closed { .. } => return Ok(()),
+            State::Open { until } => until,
+            // A probe that never reports back, e.g. because its future was
+            // dropped, must not keep the breaker half-open forever.
+            State::HalfOpen { since } => since + self.open_duration,
+        };
+        if now < until {
+            return Err(until - now);
+        }
+        *state = State::HalfOpen { since: now };
+        Ok(())
+    }
+
+    /// Record a response that does not indicate an outage.
+    pub(crate) fn on_success(&self) {
+        *self.state.lock().unwrap() = State::Closed { failures: 0 };
+    }
+
+    /// Record a retryable failure, opening the breaker at the threshold.
+    pub(crate) fn on_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            // A failed probe reopens the breaker straight away.
+            State::HalfOpen { .. } => self.failure_threshold,
+            // Requests sent before the breaker opened do not extend it.
+            State::Open { .. } => return,
+        };
+        *state = if failures >= self.failure_threshold {
+            State::Open {
+                until: Instant::now() + self.open_duration,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+    use crate::transport::Response;
+    use crate::{Client, POPSignerError};
+
+    #[test]
+    fn test_opens_then_probes_once() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+        breaker.on_failure();
+        assert!(breaker.try_acquire().is_ok());
+        breaker.on_failure();
+        assert!(breaker.try_acquire().is_err());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.try_acquire().is_ok());
+        assert!(breaker.try_acquire().is_err(), "only one probe at a time");
+
+        breaker.on_success();
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_failed_probe_reopens() {
+        let breaker = CircuitBreaker::new(3, Duration::from_millis(20));
+        for _ in 0..3 {
+            breaker.on_failure();
+        }
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.try_acquire().is_ok());
+
+        breaker.on_failure();
+        assert!(breaker.try_acquire().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shared_across_clones() {
+        let mock = MockTransport::new();
+        let body = serde_json::json!({
+            "error": { "code": "service_unavailable", "message": "down" }
+        });
+        for _ in 0..2 {
+            mock.push_response(Response::json(503, &body));
+        }
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .circuit_breaker(2, Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let err = client.orgs().get_current().await.unwrap_err();
+            assert_eq!(err.status_code(), Some(503));
+        }
+
+        let err = client.clone().orgs().get_current().await.unwrap_err();
+        assert!(matches!(err, POPSignerError::CircuitOpen { .. }));
+        assert!(err.retry_after().is_some());
+        assert_eq!(mock.requests().len(), 2);
+    }
+}
@@ -3,32 +3,90 @@
 //! The main entry point for interacting with the POPSigner Control Plane API.
 
 use crate::audit::AuditClient;
-use crate::error::{POPSignerError, Result};
+use crate::batcher;
+use crate::cache::{KeyCache, VerifyCache};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::compression;
+use crate::error::{ApiErrorCode, POPSignerError, QuotaInfo, Result};
+use crate::ids::{KeyId, NamespaceId};
 use crate::keys::KeysClient;
+use crate::metrics::Metrics;
+use crate::namespace::NamespaceClient;
 use crate::orgs::OrgsClient;
+use crate::rate_limit::RateLimiter;
+use crate::retry::{self, RetryPolicy};
+use crate::runtime::{self, Instant};
 use crate::sign::SignClient;
+use crate::trace;
+use crate::transport::{Interceptor, Request, ReqwestTransport, Response, Transport};
+use crate::types::{
+    CostEstimate, HealthStatus, Key, Namespace, NamespaceSettings, Page, Priority, ResponseMeta,
+    ServerWarning, SignRequest, SigningAlgorithm, Timed,
+};
 use reqwest::{header, Client as HttpClient};
-use serde::Deserialize;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// API version this SDK's requests and types are written against, matching
+/// the `/v1` path prefix.
+///
+/// Pin it with [`ClientBuilder::api_version`] to have the server reject
+/// requests instead of silently answering with a different version.
+pub const API_VERSION: &str = "1";
 
 const DEFAULT_BASE_URL: &str = "https://api.popsigner.io";
+#[cfg(not(target_arch = "wasm32"))]
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+const DEFAULT_MAX_PAYLOAD_BYTES: usize = 1024 * 1024;
+/// Weight of each new sample in [`Client::recent_latency`].
+const DEFAULT_LATENCY_SMOOTHING: f64 = 0.2;
+/// Longest `Retry-After` honored unless [`ClientBuilder::max_retry_after`]
+/// says otherwise.
+const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// Request header pinning the API version.
+const API_VERSION_HEADER: &str = "accept-version";
+
+/// Request header carrying the [`Priority`] hint.
+const PRIORITY_HEADER: &str = "x-priority";
+
+/// Response header carrying the server's request ID.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Response header carrying the largest batch the server accepts.
+const MAX_BATCH_SIZE_HEADER: &str = "x-max-batch-size";
+
+/// Response header carrying when an exceeded quota resets, as Unix seconds
+/// or an HTTP date.
+const QUOTA_RESET_HEADER: &str = "x-quota-reset";
+
+/// How often [`Client::shutdown`] checks whether in-flight calls are done.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// POPSigner API client.
 ///
 /// # Example
 ///
 /// ```rust,no_run
-/// use popsigner::Client;
+/// use popsigner::{Client, NamespaceId};
 /// use popsigner::types::CreateKeyRequest;
-/// use uuid::Uuid;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     let client = Client::new("psk_live_xxxxx");
 ///     
 ///     // Create a key
-///     let namespace_id = Uuid::parse_str("...")?;
+///     let namespace_id: NamespaceId = "...".parse()?;
 ///     let key = client.keys().create(CreateKeyRequest {
 ///         name: "sequencer".to_string(),
 ///         namespace_id,
@@ -41,11 +99,189 @@ const DEFAULT_TIMEOUT_SECS: u64 = 30;
 ///     Ok(())
 /// }
 /// ```
+///
+/// Cloning is cheap: clones share the connection pool and all client-side
+/// state, such as the rate limiter, circuit breaker and key cache.
+///
+/// The `Debug` output shows the configuration but never the credentials.
 #[derive(Clone)]
 pub struct Client {
-    pub(crate) http: HttpClient,
-    pub(crate) base_url: String,
-    pub(crate) api_key: String,
+    inner: Arc<ClientInner>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = &self.inner;
+        f.debug_struct("Client")
+            .field("base_url", &inner.base_url)
+            .field("auth", &inner.credentials)
+            .field("timeout", &inner.timeout)
+            .field("retry_policy", &inner.retry_policy)
+            .field("api_version", &inner.api_version)
+            .finish_non_exhaustive()
+    }
+}
+
+struct ClientInner {
+    /// `None` once the client has been shut down.
+    transport: Mutex<Option<Arc<dyn Transport>>>,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    base_url: String,
+    credentials: Credentials,
+    default_headers: header::HeaderMap,
+    timeout: Option<Duration>,
+    rate_limiter: Option<RateLimiter>,
+    circuit_breaker: Option<CircuitBreaker>,
+    key_cache: Option<KeyCache>,
+    verify_cache: Option<VerifyCache>,
+    retry_policy: RetryPolicy,
+    retry_classifier: Option<RetryClassifier>,
+    jitter_source: Option<JitterSource>,
+    metrics: Option<Arc<dyn Metrics>>,
+    raw_response_hook: Option<RawResponseHook>,
+    raw_responses_include_success: bool,
+    quota_hook: Option<QuotaHook>,
+    warning_hook: Option<WarningHook>,
+    /// Smallest request body to gzip; `None` when compression is off.
+    compression_threshold: Option<usize>,
+    redact: bool,
+    strict_schema: bool,
+    /// Pinned API version, sent as `Accept-Version`.
+    api_version: Option<String>,
+    max_response_bytes: usize,
+    /// Largest data to sign; `None` when the check is off.
+    max_payload_bytes: Option<usize>,
+    /// Batch size limit last announced by the server; 0 if unknown.
+    max_batch_size: AtomicUsize,
+    /// Moving average of request latency; `None` until a response arrives.
+    latency: Mutex<Option<Duration>>,
+    latency_smoothing: f64,
+    max_retry_after: Duration,
+    /// Batchers built on this client, flushed by [`Client::shutdown`].
+    batchers: Mutex<Vec<Weak<batcher::Inner>>>,
+    /// Calls started and not yet finished.
+    in_flight: AtomicUsize,
+    shut_down: AtomicBool,
+}
+
+/// Client certificate material for mutual TLS, parsed when the client is
+/// built.
+#[cfg(not(target_arch = "wasm32"))]
+enum TlsIdentity {
+    /// Certificate chain followed by the private key, for rustls.
+    Pem(Vec<u8>),
+    /// A PKCS#12 archive and its password, for native-tls.
+    #[cfg(feature = "native-tls")]
+    Pkcs12 { der: Vec<u8>, password: String },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TlsIdentity {
+    /// Configure `http` to present this identity, with the TLS backend
+    /// that can load it.
+    fn apply(self, http: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+        let invalid = |e: reqwest::Error| {
+            POPSignerError::InvalidRequest(format!("invalid client certificate or key: {}", e))
+        };
+        match self {
+            TlsIdentity::Pem(pem) => {
+                let identity = reqwest::Identity::from_pem(&pem).map_err(invalid)?;
+                Ok(http.use_rustls_tls().identity(identity))
+            }
+            #[cfg(feature = "native-tls")]
+            TlsIdentity::Pkcs12 { der, password } => {
+                let identity =
+                    reqwest::Identity::from_pkcs12_der(&der, &password).map_err(invalid)?;
+                Ok(http.use_native_tls().identity(identity))
+            }
+        }
+    }
+}
+
+/// Callback returning the current credentials.
+type AuthProvider = Arc<dyn Fn() -> Auth + Send + Sync>;
+
+/// Callback deciding whether a failed attempt is retried.
+type RetryClassifier = Arc<dyn Fn(&POPSignerError) -> bool + Send + Sync>;
+
+/// Callback returning uniform random values in `[0, 1)` for retry jitter.
+type JitterSource = Arc<dyn Fn() -> f64 + Send + Sync>;
+
+/// Callback receiving a raw response body and status.
+type RawResponseHook = Arc<dyn Fn(&str, u16) + Send + Sync>;
+
+/// Callback observing an exceeded quota.
+type QuotaHook = Arc<dyn Fn(&QuotaInfo) + Send + Sync>;
+
+/// Callback observing a server warning.
+type WarningHook = Arc<dyn Fn(&ServerWarning) + Send + Sync>;
+
+/// Header carrying an [`Auth::ApiKey`].
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Credentials sent with every request, and the header scheme they use.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// An API key, sent in the `X-API-Key` header.
+    ApiKey(String),
+    /// A bearer token, e.g. a short-lived OIDC access token, sent as
+    /// `Authorization: Bearer <token>`. Keys set with
+    /// [`ClientBuilder::api_key`] are sent this way too.
+    Bearer(String),
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the secret itself.
+        let scheme = match self {
+            Auth::ApiKey(_) => "ApiKey",
+            Auth::Bearer(_) => "Bearer",
+        };
+        f.debug_tuple(scheme).field(&"***").finish()
+    }
+}
+
+impl Auth {
+    /// The header name and value carrying these credentials.
+    fn header(&self) -> Result<(header::HeaderName, header::HeaderValue)> {
+        let (name, value) = match self {
+            Auth::ApiKey(key) => (
+                header::HeaderName::from_static(API_KEY_HEADER),
+                header::HeaderValue::from_str(key),
+            ),
+            Auth::Bearer(token) => (
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&format!("Bearer {}", token)),
+            ),
+        };
+        let value =
+            value.map_err(|_| POPSignerError::InvalidRequest("invalid credentials".to_string()))?;
+        Ok((name, value))
+    }
+}
+
+/// Where the credentials for each request come from.
+enum Credentials {
+    Static(Auth),
+    Provider(AuthProvider),
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credentials::Static(auth) => auth.fmt(f),
+            Credentials::Provider(_) => f.write_str("Provider(..)"),
+        }
+    }
+}
+
+impl Credentials {
+    fn current(&self) -> Cow<'_, Auth> {
+        match self {
+            Credentials::Static(auth) => Cow::Borrowed(auth),
+            Credentials::Provider(provider) => Cow::Owned(provider()),
+        }
+    }
 }
 
 /// Configuration options for the client.
@@ -69,6 +305,670 @@ impl Default for ClientConfig {
     }
 }
 
+/// Builder for [`Client`].
+///
+/// Obtain one via [`Client::builder`]. Unset options fall back to the
+/// defaults used by [`Client::new`].
+#[derive(Default)]
+pub struct ClientBuilder {
+    auth: Option<Auth>,
+    auth_provider: Option<AuthProvider>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    resolve_overrides: Vec<(String, SocketAddr)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    identity: Option<TlsIdentity>,
+    /// PEM CA certificates trusted in addition to the system roots.
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<Vec<u8>>,
+    http_client: Option<HttpClient>,
+    transport: Option<Box<dyn Transport>>,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    default_headers: Vec<(String, String)>,
+    rate_limit: Option<(u32, u32)>,
+    circuit_breaker: Option<(u32, Duration)>,
+    key_cache: Option<(Duration, usize)>,
+    verify_cache: Option<usize>,
+    retry_policy: Option<RetryPolicy>,
+    retry_classifier: Option<RetryClassifier>,
+    jitter_source: Option<JitterSource>,
+    metrics: Option<Arc<dyn Metrics>>,
+    raw_response_hook: Option<RawResponseHook>,
+    raw_responses_include_success: bool,
+    quota_hook: Option<QuotaHook>,
+    warning_hook: Option<WarningHook>,
+    compression: bool,
+    compression_threshold: Option<usize>,
+    redact: bool,
+    strict_schema: bool,
+    api_version: Option<String>,
+    max_response_bytes: Option<usize>,
+    max_payload_bytes: Option<usize>,
+    skip_payload_check: bool,
+    latency_smoothing: Option<f64>,
+    max_retry_after: Option<Duration>,
+}
+
+impl ClientBuilder {
+    /// Create a new builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the API key, sent as `Authorization: Bearer <key>`.
+    ///
+    /// Credentials are required: set this, [`auth`](Self::auth) or one of
+    /// the providers. Shorthand for `auth(Auth::Bearer(api_key))`.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.auth = Some(Auth::Bearer(api_key.into()));
+        self
+    }
+
+    /// Set the credentials and the header scheme used to send them.
+    ///
+    /// Replaces any earlier [`api_key`](Self::api_key).
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Fetch the API key from `provider` before every request.
+    ///
+    /// Lets a long-lived client pick up rotated credentials without being
+    /// rebuilt. If a request fails with [`POPSignerError::Unauthorized`], the
+    /// provider is called again and the request is retried once, so a
+    /// rotation that happens mid-flight does not fail the call.
+    ///
+    /// The closure is called for every request, so it must be cheap, e.g.
+    /// reading a value refreshed in the background. Takes precedence over
+    /// [`api_key`](Self::api_key). Keys are sent as bearer tokens; use
+    /// [`auth_provider`](Self::auth_provider) to pick the scheme.
+    pub fn api_key_provider(
+        mut self,
+        provider: impl Fn() -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.auth_provider = Some(Arc::new(move || Auth::Bearer(provider())));
+        self
+    }
+
+    /// Fetch the credentials from `provider` before every request.
+    ///
+    /// Behaves like [`api_key_provider`](Self::api_key_provider), including
+    /// the retry on [`POPSignerError::Unauthorized`], but `provider` also
+    /// chooses the scheme, e.g. to send a refreshed OIDC token with
+    /// [`Auth::Bearer`]. Takes precedence over [`auth`](Self::auth).
+    pub fn auth_provider(mut self, provider: impl Fn() -> Auth + Send + Sync + 'static) -> Self {
+        self.auth_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Set the base URL for the API (default: https://api.popsigner.io).
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set the request timeout (default: 30 seconds).
+    ///
+    /// When combined with [`with_http_client`](Self::with_http_client), this
+    /// timeout is applied to every request via `RequestBuilder::timeout` and
+    /// takes precedence over any timeout configured on the injected client.
+    ///
+    /// On `wasm32` the default transport ignores timeouts, and there is no
+    /// default; a custom [`transport`](Self::transport) still receives it.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Use an existing `reqwest::Client` for all requests.
+    ///
+    /// Useful for sharing a tuned connection pool and TLS configuration with
+    /// the rest of your application. The injected client's own settings are
+    /// used as-is, except that the API key header is always added, and
+    /// [`timeout`](Self::timeout) overrides its timeout when set.
+    /// [`user_agent`](Self::user_agent) has no effect on an injected client.
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http_client = Some(http);
+        self
+    }
+
+    /// Send requests through a custom [`Transport`].
+    ///
+    /// Replaces the HTTP layer entirely, e.g. with
+    /// [`MockTransport`](crate::testing::MockTransport) in tests. Takes
+    /// precedence over [`with_http_client`](Self::with_http_client), and
+    /// [`user_agent`](Self::user_agent) has no effect. Retries, rate limiting
+    /// and [`timeout`](Self::timeout) still apply.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /// Send a header with every request, e.g. a tenant header required by a
+    /// gateway.
+    ///
+    /// Per-request headers with the same name take precedence. Reserved
+    /// headers such as `Authorization` are rejected by
+    /// [`build`](Self::build).
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Largest response body, in bytes, the client reads before failing
+    /// with [`POPSignerError::ResponseTooLarge`]. Defaults to 10 MiB.
+    ///
+    /// Applies to success and error responses alike, and to gzipped bodies
+    /// after decompression.
+    pub fn max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = Some(bytes);
+        self
+    }
+
+    /// Largest data, in bytes, a sign request may carry. Defaults to 1 MiB.
+    ///
+    /// Larger requests fail with [`POPSignerError::InvalidRequest`] before
+    /// anything is sent, saving the round trip and quota. Applies to
+    /// [`SignClient::sign`](crate::sign::SignClient::sign), each item of
+    /// [`SignClient::sign_batch`](crate::sign::SignClient::sign_batch) and
+    /// the calls built on them; digests are checked against their
+    /// algorithm's length instead.
+    pub fn max_payload_bytes(mut self, bytes: usize) -> Self {
+        self.max_payload_bytes = Some(bytes);
+        self
+    }
+
+    /// Check payload sizes locally before sending (default: true). Turn it
+    /// off to leave enforcement to the server, e.g. one with a higher
+    /// limit than [`max_payload_bytes`](Self::max_payload_bytes) allows for.
+    pub fn check_payload_size(mut self, check: bool) -> Self {
+        self.skip_payload_check = !check;
+        self
+    }
+
+    /// Pin the API version, sent as `Accept-Version` with every request.
+    ///
+    /// The server rejects versions it cannot serve, which the client
+    /// reports as [`POPSignerError::UnsupportedApiVersion`]. Pass
+    /// [`API_VERSION`] to pin the version this SDK was written against.
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    /// Set the User-Agent header value. Ignored on `wasm32`, where the
+    /// browser sets it.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Connect to `addr` whenever a request goes to `host`, bypassing DNS,
+    /// e.g. to reach a staging IP from a container whose hosts file cannot
+    /// be changed.
+    ///
+    /// The URL keeps the real hostname, so TLS SNI and certificate checks
+    /// still use it. Only the IP of `addr` is used; requests still go to
+    /// the port in the base URL. Call repeatedly to override several hosts. Like
+    /// [`user_agent`](Self::user_agent), this has no effect on an injected
+    /// client or a custom transport.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.resolve_overrides.push((host.to_string(), addr));
+        self
+    }
+
+    /// Present a client certificate for mutual TLS, e.g. to a zero-trust
+    /// gateway in front of POPSigner. The API key is still sent.
+    ///
+    /// `cert_pem` holds the certificate, optionally followed by its chain,
+    /// and `key_pem` its PKCS#8, PKCS#1 or SEC1 private key. Connections
+    /// then use rustls, through reqwest's `rustls-tls` feature, which this
+    /// SDK always enables. [`build`](Self::build) returns
+    /// [`POPSignerError::InvalidRequest`] if the material is malformed.
+    /// Like [`resolve`](Self::resolve), this has no effect on an injected
+    /// client or a custom transport.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn client_identity(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Self {
+        let mut pem = cert_pem.to_vec();
+        pem.push(b'\n');
+        pem.extend_from_slice(key_pem);
+        self.identity = Some(TlsIdentity::Pem(pem));
+        self
+    }
+
+    /// Like [`client_identity`](Self::client_identity), from a PKCS#12
+    /// archive (`.p12` or `.pfx`) protected by `password`.
+    ///
+    /// Requires this crate's `native-tls` feature, which turns on
+    /// reqwest's `native-tls` backend; connections then use the platform's
+    /// TLS library.
+    #[cfg(all(feature = "native-tls", not(target_arch = "wasm32")))]
+    pub fn client_identity_pkcs12(mut self, der: &[u8], password: &str) -> Self {
+        self.identity = Some(TlsIdentity::Pkcs12 {
+            der: der.to_vec(),
+            password: password.to_string(),
+        });
+        self
+    }
+
+    /// Trust the PEM CA certificate `pem` in addition to the system roots,
+    /// e.g. for a deployment behind a private CA. Call repeatedly to add
+    /// several.
+    ///
+    /// [`build`](Self::build) returns [`POPSignerError::InvalidRequest`]
+    /// if the certificate is malformed. Like [`resolve`](Self::resolve),
+    /// this has no effect on an injected client or a custom transport.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Enforce a client-side rate limit before requests leave the process.
+    ///
+    /// Uses a token bucket refilled at `permits_per_second` that holds up to
+    /// `burst` permits. Requests wait for a permit instead of failing, and the
+    /// bucket is shared by every clone of the built [`Client`].
+    pub fn rate_limit(mut self, permits_per_second: u32, burst: u32) -> Self {
+        self.rate_limit = Some((permits_per_second, burst));
+        self
+    }
+
+    /// Fail fast while the server is unhealthy.
+    ///
+    /// After `failure_threshold` consecutive retryable failures, requests
+    /// fail with [`POPSignerError::CircuitOpen`] without being sent for
+    /// `open_duration`. Then one probe request is let through: if it
+    /// succeeds the breaker closes, otherwise it opens again. Any response
+    /// that is not a retryable failure resets the count. The breaker is
+    /// shared by every clone of the built [`Client`].
+    pub fn circuit_breaker(mut self, failure_threshold: u32, open_duration: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, open_duration));
+        self
+    }
+
+    /// Cache key metadata returned by [`KeysClient::get`] and
+    /// [`KeysClient::get_by_name`] for `ttl`, holding at most `max_entries`
+    /// keys.
+    ///
+    /// Repeated `get` calls for a cached key ID are served without a
+    /// request. Entries are dropped when the server reports the key missing,
+    /// when it is deleted through this client, or on
+    /// [`Client::invalidate_key`]. Signing is never cached. The cache is
+    /// shared by every clone of the built [`Client`].
+    pub fn key_cache(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.key_cache = Some((ttl, max_entries));
+        self
+    }
+
+    /// Remember the results of [`SignClient::verify`], valid or not, for up
+    /// to `max_entries` distinct (key, message, signature) triples.
+    ///
+    /// Verifying a cached triple again is answered without a request; the
+    /// least recently used result is dropped when the cache is full. A key's
+    /// results are dropped when it is disabled, enabled or deleted through
+    /// this client, when it is fetched and found disabled or rotated, and on
+    /// [`Client::invalidate_key`]. The cache is shared by every clone of the
+    /// built [`Client`].
+    pub fn verify_cache(mut self, max_entries: usize) -> Self {
+        self.verify_cache = Some(max_entries);
+        self
+    }
+
+    /// Retry failed requests according to `policy`.
+    ///
+    /// Only errors where [`POPSignerError::is_retryable`] is true are retried;
+    /// a `Retry-After` from the server overrides the computed backoff.
+    /// Defaults to [`RetryPolicy::none`].
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Weight each new request's latency by `alpha` in
+    /// [`Client::recent_latency`], between 0 (exclusive) and 1.
+    ///
+    /// Higher values follow changes faster; `alpha` of about `2 / (n + 1)`
+    /// averages over roughly the last `n` requests. Defaults to 0.2.
+    pub fn latency_smoothing(mut self, alpha: f64) -> Self {
+        self.latency_smoothing = Some(alpha);
+        self
+    }
+
+    /// Cap the delay taken from a server's `Retry-After` header at `max`.
+    ///
+    /// An HTTP-date `Retry-After` is measured against the local clock, so a
+    /// skewed clock can turn it into an absurd delay; dates in the past
+    /// count as zero. The cap applies to both the retry loop and
+    /// [`POPSignerError::retry_after`]. Defaults to 5 minutes.
+    pub fn max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = Some(max);
+        self
+    }
+
+    /// Decide which errors are retried, replacing
+    /// [`POPSignerError::is_retryable`] in the retry loop.
+    ///
+    /// Useful when a proxy returns transient errors the SDK does not know
+    /// about. Only consulted when a [`retry`](Self::retry) policy allows more
+    /// attempts.
+    pub fn retry_classifier(
+        mut self,
+        classifier: impl Fn(&POPSignerError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Draw retry jitter from `source`, which must return values in `[0, 1)`.
+    ///
+    /// Defaults to a randomly seeded source. A fixed sequence makes backoff
+    /// deterministic in tests.
+    pub fn jitter_source(mut self, source: impl Fn() -> f64 + Send + Sync + 'static) -> Self {
+        self.jitter_source = Some(Arc::new(source));
+        self
+    }
+
+    /// Gzip request bodies and accept gzipped responses.
+    ///
+    /// Only bodies of at least [`compression_threshold`](Self::compression_threshold)
+    /// bytes are compressed. If the server rejects a compressed body with
+    /// `415 Unsupported Media Type`, that request is sent again uncompressed.
+    /// Defaults to off.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Smallest request body, in bytes, to compress when
+    /// [`compression`](Self::compression) is on. Defaults to 1 KiB.
+    pub fn compression_threshold(mut self, bytes: usize) -> Self {
+        self.compression_threshold = Some(bytes);
+        self
+    }
+
+    /// Add an interceptor that may modify every request before it is sent.
+    ///
+    /// Interceptors run in the order they are added, on every attempt, after
+    /// authentication and default headers are applied. See [`Interceptor`].
+    pub fn add_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Report every HTTP attempt to `metrics`.
+    ///
+    /// See [`Metrics`] for when each callback fires.
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Pass raw response bodies and status codes to `hook` before they are
+    /// deserialized, for diagnosing unexpected response shapes.
+    ///
+    /// Only error responses are passed unless
+    /// [`raw_responses_include_success`](Self::raw_responses_include_success)
+    /// is set. The hook cannot affect the request; bodies are decoded
+    /// lossily as UTF-8.
+    pub fn on_raw_response(mut self, hook: impl Fn(&str, u16) + Send + Sync + 'static) -> Self {
+        self.raw_response_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` with the details of an exceeded quota, e.g. to start an
+    /// upgrade workflow.
+    ///
+    /// The hook runs once per failed call, after any retries and just
+    /// before the [`POPSignerError::QuotaExceeded`] error is returned. It
+    /// only observes the error; the call fails either way.
+    pub fn on_quota_exceeded(mut self, hook: impl Fn(&QuotaInfo) + Send + Sync + 'static) -> Self {
+        self.quota_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` with each warning the server attaches to a successful
+    /// response, e.g. to log that a key is nearing its rotation deadline.
+    ///
+    /// Warnings never fail a call; they are also returned in
+    /// [`ResponseMeta::warnings`] and
+    /// [`SignResponse::warnings`](crate::SignResponse::warnings).
+    pub fn on_warning(mut self, hook: impl Fn(&ServerWarning) + Send + Sync + 'static) -> Self {
+        self.warning_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Also pass successful responses to the
+    /// [`on_raw_response`](Self::on_raw_response) hook.
+    pub fn raw_responses_include_success(mut self, include: bool) -> Self {
+        self.raw_responses_include_success = include;
+        self
+    }
+
+    /// Redact server messages and response snippets from returned errors;
+    /// see [`POPSignerError::redacted`].
+    ///
+    /// Off by default, so errors stay verbose for local debugging. Also
+    /// applies to errors recorded by the `tracing` feature.
+    pub fn redact(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Fail with [`POPSignerError::Deserialize`] when a successful response
+    /// has a field the SDK does not know (default: ignore it).
+    ///
+    /// By default responses are parsed leniently: unknown fields are
+    /// skipped and missing optional fields take their defaults, so a server
+    /// that adds fields does not break older SDK versions. Turn this on in
+    /// tests or staging to notice schema changes early. Error responses are
+    /// always parsed leniently.
+    pub fn strict_schema(mut self, strict: bool) -> Self {
+        self.strict_schema = strict;
+        self
+    }
+
+    /// Apply every option set in a [`ClientConfig`].
+    pub fn config(mut self, config: ClientConfig) -> Self {
+        if config.base_url.is_some() {
+            self.base_url = config.base_url;
+        }
+        if config.timeout.is_some() {
+            self.timeout = config.timeout;
+        }
+        if config.user_agent.is_some() {
+            self.user_agent = config.user_agent;
+        }
+        self
+    }
+
+    /// Build the client.
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if neither an API key nor a
+    /// provider was set, the base URL does not parse, or a default header is
+    /// invalid or reserved.
+    pub fn build(self) -> Result<Client> {
+        let credentials = match (self.auth_provider, self.auth) {
+            (Some(provider), _) => Credentials::Provider(provider),
+            (None, Some(auth)) => Credentials::Static(auth),
+            (None, None) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "API key is required".to_string(),
+                ));
+            }
+        };
+
+        let base_url = self
+            .base_url
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        reqwest::Url::parse(&base_url).map_err(|e| {
+            POPSignerError::InvalidRequest(format!("invalid base URL '{}': {}", base_url, e))
+        })?;
+
+        let mut default_headers = custom_headers(self.default_headers.iter().map(|(k, v)| (k, v)))?;
+        let threshold = self
+            .compression_threshold
+            .unwrap_or(compression::DEFAULT_THRESHOLD);
+        let compression_threshold = self.compression.then_some(threshold);
+        if compression_threshold.is_some() {
+            default_headers.insert(
+                header::ACCEPT_ENCODING,
+                header::HeaderValue::from_static("gzip"),
+            );
+        }
+        if let Some(version) = &self.api_version {
+            let value = header::HeaderValue::from_str(version).map_err(|_| {
+                POPSignerError::InvalidRequest(format!("invalid API version {:?}", version))
+            })?;
+            default_headers.insert(API_VERSION_HEADER, value);
+        }
+
+        let max_response_bytes = match self.max_response_bytes {
+            Some(0) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "max response bytes must be greater than zero".to_string(),
+                ));
+            }
+            Some(bytes) => bytes,
+            None => DEFAULT_MAX_RESPONSE_BYTES,
+        };
+        let max_payload_bytes = match self.max_payload_bytes {
+            Some(0) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "max payload bytes must be greater than zero".to_string(),
+                ));
+            }
+            _ if self.skip_payload_check => None,
+            Some(bytes) => Some(bytes),
+            None => Some(DEFAULT_MAX_PAYLOAD_BYTES),
+        };
+
+        let rate_limiter = match self.rate_limit {
+            Some((0, _)) | Some((_, 0)) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "rate limit and burst must be greater than zero".to_string(),
+                ));
+            }
+            Some((permits_per_second, burst)) => Some(RateLimiter::new(permits_per_second, burst)),
+            None => None,
+        };
+
+        let circuit_breaker = match self.circuit_breaker {
+            Some((0, _)) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "circuit breaker failure threshold must be greater than zero".to_string(),
+                ));
+            }
+            Some((threshold, open_duration)) => Some(CircuitBreaker::new(threshold, open_duration)),
+            None => None,
+        };
+
+        let key_cache = match self.key_cache {
+            Some((_, 0)) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "key cache size must be greater than zero".to_string(),
+                ));
+            }
+            Some((ttl, max_entries)) => Some(KeyCache::new(ttl, max_entries)),
+            None => None,
+        };
+        let verify_cache = match self.verify_cache {
+            Some(0) => {
+                return Err(POPSignerError::InvalidRequest(
+                    "verify cache size must be greater than zero".to_string(),
+                ));
+            }
+            Some(max_entries) => Some(VerifyCache::new(max_entries)),
+            None => None,
+        };
+        let latency_smoothing = self.latency_smoothing.unwrap_or(DEFAULT_LATENCY_SMOOTHING);
+        if !(latency_smoothing > 0.0 && latency_smoothing <= 1.0) {
+            return Err(POPSignerError::InvalidRequest(
+                "latency smoothing must be in (0, 1]".to_string(),
+            ));
+        }
+
+        let (transport, timeout): (Box<dyn Transport>, _) = match (self.transport, self.http_client)
+        {
+            (Some(transport), _) => (transport, self.timeout),
+            (None, Some(http)) => (Box::new(ReqwestTransport::new(http)), self.timeout),
+            #[cfg(not(target_arch = "wasm32"))]
+            (None, None) => {
+                let timeout = self
+                    .timeout
+                    .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+                let user_agent = self
+                    .user_agent
+                    .unwrap_or_else(|| format!("popsigner-rust/{}", env!("CARGO_PKG_VERSION")));
+                let mut http = HttpClient::builder()
+                    .timeout(timeout)
+                    .user_agent(user_agent);
+                for (host, addr) in &self.resolve_overrides {
+                    http = http.resolve(host, *addr);
+                }
+                for pem in &self.root_certificates {
+                    let certificate = reqwest::Certificate::from_pem(pem).map_err(|e| {
+                        POPSignerError::InvalidRequest(format!("invalid root certificate: {}", e))
+                    })?;
+                    http = http.add_root_certificate(certificate);
+                }
+                if let Some(identity) = self.identity {
+                    http = identity.apply(http)?;
+                }
+                let http = http.build()?;
+                (Box::new(ReqwestTransport::new(http)), Some(timeout))
+            }
+            // The browser sets the User-Agent and owns request timeouts.
+            #[cfg(target_arch = "wasm32")]
+            (None, None) => {
+                let _ = self.user_agent;
+                let http = HttpClient::builder().build()?;
+                (Box::new(ReqwestTransport::new(http)), None)
+            }
+        };
+
+        Ok(Client {
+            inner: Arc::new(ClientInner {
+                transport: Mutex::new(Some(Arc::from(transport))),
+                interceptors: self.interceptors,
+                base_url,
+                credentials,
+                default_headers,
+                timeout,
+                rate_limiter,
+                circuit_breaker,
+                key_cache,
+                verify_cache,
+                retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+                retry_classifier: self.retry_classifier,
+                jitter_source: self.jitter_source,
+                metrics: self.metrics,
+                raw_response_hook: self.raw_response_hook,
+                raw_responses_include_success: self.raw_responses_include_success,
+                quota_hook: self.quota_hook,
+                warning_hook: self.warning_hook,
+                compression_threshold,
+                redact: self.redact,
+                strict_schema: self.strict_schema,
+                api_version: self.api_version,
+                max_response_bytes,
+                max_payload_bytes,
+                max_batch_size: AtomicUsize::new(0),
+                latency: Mutex::new(None),
+                latency_smoothing,
+                max_retry_after: self.max_retry_after.unwrap_or(DEFAULT_MAX_RETRY_AFTER),
+                batchers: Mutex::new(Vec::new()),
+                in_flight: AtomicUsize::new(0),
+                shut_down: AtomicBool::new(false),
+            }),
+        })
+    }
+}
+
 impl Client {
     /// Create a new POPSigner client with default configuration.
     ///
@@ -84,12 +984,43 @@ impl Client {
     /// let client = Client::new("psk_live_xxxxx");
     /// ```
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self::with_config(api_key, ClientConfig::default())
+        Self::builder()
+            .api_key(api_key)
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a builder for configuring a client.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::Client;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> popsigner::Result<()> {
+    /// let client = Client::builder()
+    ///     .api_key("psk_live_xxxxx")
+    ///     .base_url("https://popsigner.internal.example.com")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
     }
 
     /// Get the base URL for the API.
     pub fn base_url(&self) -> &str {
-        &self.base_url
+        &self.inner.base_url
+    }
+
+    /// Get the request timeout applied by the SDK.
+    ///
+    /// Returns `None` when an injected HTTP client's own timeout is in effect.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
     }
 
     /// Create a new POPSigner client with custom configuration.
@@ -99,6 +1030,11 @@ impl Client {
     /// * `api_key` - Your POPSigner API key
     /// * `config` - Client configuration options
     ///
+    /// # Panics
+    ///
+    /// Panics if `config.base_url` is not a valid URL. Use [`Client::builder`]
+    /// to handle that case as an error instead.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -112,28 +1048,93 @@ impl Client {
     /// });
     /// ```
     pub fn with_config(api_key: impl Into<String>, config: ClientConfig) -> Self {
-        let timeout = config
-            .timeout
-            .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
-        let user_agent = config
-            .user_agent
-            .unwrap_or_else(|| format!("popsigner-rust/{}", env!("CARGO_PKG_VERSION")));
-
-        let http = HttpClient::builder()
-            .timeout(timeout)
-            .user_agent(user_agent)
+        Self::builder()
+            .api_key(api_key)
+            .config(config)
             .build()
-            .expect("Failed to create HTTP client");
+            .expect("Failed to create HTTP client")
+    }
 
-        Self {
-            http,
-            base_url: config
-                .base_url
-                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
-            api_key: api_key.into(),
+    /// Number of client-side rate limit permits available right now.
+    ///
+    /// Returns `None` if no rate limit was configured.
+    pub fn available_permits(&self) -> Option<u32> {
+        self.inner
+            .rate_limiter
+            .as_ref()
+            .map(|limiter| limiter.available_permits())
+    }
+
+    /// Exponential moving average of request latency, as seen by this
+    /// client and its clones.
+    ///
+    /// Each attempt that gets a response, successful or not, updates it;
+    /// transport errors and requests not yet sent do not. Returns `None`
+    /// until the first response. Tune with
+    /// [`ClientBuilder::latency_smoothing`].
+    pub fn recent_latency(&self) -> Option<Duration> {
+        *self.inner.latency.lock().unwrap()
+    }
+
+    /// Drop a key from the metadata cache and its results from the
+    /// verification cache, if they are configured.
+    ///
+    /// Use this after changing a key out of band so the next
+    /// [`KeysClient::get`] fetches it again.
+    pub fn invalidate_key(&self, namespace_id: &NamespaceId, key_id: &KeyId) {
+        if let Some(cache) = &self.inner.key_cache {
+            cache.remove_in(namespace_id, key_id);
+        }
+        self.evict_verifications(key_id);
+    }
+
+    /// Return a cached key, if the cache is enabled and holds it.
+    pub(crate) fn cached_key(&self, key_id: &KeyId) -> Option<Key> {
+        self.inner.key_cache.as_ref()?.get(key_id)
+    }
+
+    /// Update the key cache from the result of a request about `key_id`.
+    ///
+    /// Successful lookups are cached; a key-not-found error evicts the key.
+    pub(crate) fn observe_key(&self, key_id: &KeyId, result: &Result<Key>) {
+        if let Some(cache) = &self.inner.verify_cache {
+            match result {
+                Ok(key) => cache.observe(key),
+                Err(e) if is_key_not_found(e) => cache.remove_key(key_id),
+                Err(_) => {}
+            }
+        }
+        let Some(cache) = &self.inner.key_cache else {
+            return;
+        };
+        match result {
+            Ok(key) => cache.insert(key.clone()),
+            Err(e) if is_key_not_found(e) => cache.remove(key_id),
+            Err(_) => {}
+        }
+    }
+
+    /// Evict `key_id` from the key and verification caches, e.g. after it
+    /// was deleted.
+    pub(crate) fn evict_key(&self, key_id: &KeyId) {
+        if let Some(cache) = &self.inner.key_cache {
+            cache.remove(key_id);
+        }
+        self.evict_verifications(key_id);
+    }
+
+    /// Drop the cached verification results for `key_id`.
+    pub(crate) fn evict_verifications(&self, key_id: &KeyId) {
+        if let Some(cache) = &self.inner.verify_cache {
+            cache.remove_key(key_id);
         }
     }
 
+    /// The verification cache, if one is configured.
+    pub(crate) fn verify_cache(&self) -> Option<&VerifyCache> {
+        self.inner.verify_cache.as_ref()
+    }
+
     /// Get the keys client for key management operations.
     ///
     /// # Example
@@ -162,6 +1163,23 @@ impl Client {
         SignClient::new(self.clone())
     }
 
+    /// Get a handle for operations within a single namespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::Client;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let production = client.namespace(&"...".parse()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn namespace(&self, namespace_id: &NamespaceId) -> NamespaceClient {
+        NamespaceClient::new(self.clone(), *namespace_id)
+    }
+
     /// Get the orgs client for organization management.
     ///
     /// # Example
@@ -190,108 +1208,1061 @@ impl Client {
         AuditClient::new(self.clone())
     }
 
-    /// Make an authenticated GET request.
-    pub(crate) async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path);
-
-        let response = self
-            .http
-            .get(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .header(header::CONTENT_TYPE, "application/json")
-            .send()
-            .await?;
-
-        self.handle_response(response).await
-    }
-
-    /// Make an authenticated POST request.
-    pub(crate) async fn post<T, B>(&self, path: &str, body: &B) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-        B: serde::Serialize,
-    {
-        let url = format!("{}{}", self.base_url, path);
+    /// Create a namespace in an organization, e.g. when onboarding a tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the organization
+    /// already has a namespace called `name`, and
+    /// [`POPSignerError::OrgNotFound`] if the organization does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, NamespaceSettings};
+    ///
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let org = client.orgs().get_current().await?;
+    ///
+    /// let settings = NamespaceSettings {
+    ///     description: Some("Acme rollup signers".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let namespace = client.create_namespace(&org.id, "acme", settings).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "client.create_namespace",
+            skip_all,
+            fields(org_id = %org_id, name = %name)
+        )
+    )]
+    pub async fn create_namespace(
+        &self,
+        org_id: &Uuid,
+        name: &str,
+        settings: NamespaceSettings,
+    ) -> Result<Namespace> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            name: &'a str,
+            #[serde(flatten)]
+            settings: NamespaceSettings,
+        }
 
-        let response = self
-            .http
-            .post(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(body)
-            .send()
-            .await?;
+        let path = format!("/v1/organizations/{}/namespaces", org_id);
+        self.post(&path, &Request { name, settings })
+            .await
+            .map_err(|e| match e.status_code() {
+                Some(409) => POPSignerError::InvalidRequest(format!(
+                    "namespace {:?} already exists in organization {}",
+                    name, org_id
+                )),
+                Some(404) => POPSignerError::OrgNotFound(org_id.to_string()),
+                _ => e,
+            })
+    }
 
-        self.handle_response(response).await
+    /// Delete an organization's namespace by name.
+    ///
+    /// A namespace that still holds keys is only deleted, together with its
+    /// keys, if `force` is true.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the namespace has keys
+    /// and `force` is false, [`POPSignerError::NamespaceNotFound`] if there
+    /// is no such namespace, and [`POPSignerError::OrgNotFound`] if the
+    /// organization does not exist.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "client.delete_namespace",
+            skip_all,
+            fields(org_id = %org_id, name = %name, force)
+        )
+    )]
+    pub async fn delete_namespace(&self, org_id: &Uuid, name: &str, force: bool) -> Result<()> {
+        let mut path = format!(
+            "/v1/organizations/{}/namespaces/{}",
+            org_id,
+            encode_query_value(name)
+        );
+        if force {
+            path.push_str("?force=true");
+        }
+        self.delete(&path).await.map_err(|e| match e.status_code() {
+            Some(409) => POPSignerError::InvalidRequest(format!(
+                "namespace {:?} still has keys; delete them or pass force = true",
+                name
+            )),
+            Some(404) if is_namespace_not_found(&e) => {
+                POPSignerError::NamespaceNotFound(name.to_string())
+            }
+            Some(404) => POPSignerError::OrgNotFound(org_id.to_string()),
+            _ => e,
+        })
     }
 
-    /// Make an authenticated DELETE request.
-    pub(crate) async fn delete(&self, path: &str) -> Result<()> {
-        let url = format!("{}{}", self.base_url, path);
+    /// Estimate what signing `requests` would cost the current organization,
+    /// e.g. to ask for confirmation before a large batch.
+    ///
+    /// Only each request's key, options and data length are sent, not the
+    /// data itself. Nothing is signed and no quota is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::NoPricingPlan`] if the organization has no
+    /// pricing plan to estimate from.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, SignRequest};
+    ///
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let key_id: KeyId = "...".parse()?;
+    ///
+    /// let requests: Vec<_> = (0..1000)
+    ///     .map(|i| SignRequest::new(key_id, format!("tx-{}", i).into_bytes()))
+    ///     .collect();
+    /// let estimate = client.estimate_cost(&requests).await?;
+    /// println!("{} units, {} {}", estimate.quota_units, estimate.cost, estimate.currency);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "client.estimate_cost",
+            skip_all,
+            fields(requests = requests.len())
+        )
+    )]
+    pub async fn estimate_cost(&self, requests: &[SignRequest]) -> Result<CostEstimate> {
+        #[derive(Serialize)]
+        struct Operation<'a> {
+            key_id: &'a KeyId,
+            data_len: usize,
+            prehashed: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            key_version: Option<u32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            algorithm: Option<SigningAlgorithm>,
+        }
+
+        #[derive(Serialize)]
+        struct Request<'a> {
+            operations: Vec<Operation<'a>>,
+        }
+
+        let operations = requests
+            .iter()
+            .map(|request| Operation {
+                key_id: &request.key_id,
+                data_len: request.data.len(),
+                prehashed: request.prehashed,
+                key_version: request.key_version,
+                algorithm: request.algorithm,
+            })
+            .collect();
+        self.post("/v1/org/cost-estimate", &Request { operations })
+            .await
+            .map_err(|e| match e {
+                POPSignerError::Api {
+                    code: ApiErrorCode::NoPricingPlan,
+                    ..
+                } => POPSignerError::NoPricingPlan,
+                e => e,
+            })
+    }
 
+    /// Check that the server is reachable, warming up the connection pool.
+    ///
+    /// Call this at startup so the first signing request does not pay for
+    /// the TCP/TLS handshake. A 5xx response is reported as
+    /// [`HealthStatus::Degraded`] rather than an error, and no retries are
+    /// made.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on transport failures (timeout, connection refused),
+    /// authentication problems, or other 4xx responses, which usually mean
+    /// the base URL is wrong.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = popsigner::Client::new("psk_live_xxxxx");
+    /// let status = client.health_check().await?;
+    /// println!("healthy: {}, version: {:?}", status.is_healthy(), status.version());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "client.health_check", skip_all)
+    )]
+    pub async fn health_check(&self) -> Result<HealthStatus> {
+        #[derive(Deserialize)]
+        struct Health {
+            #[serde(default)]
+            version: Option<String>,
+        }
+
+        let _in_flight = self.start_call()?;
+        let options = RequestOptions::default();
+        let request = self.request(reqwest::Method::GET, "/health", None, &options)?;
         let response = self
-            .http
-            .delete(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .send()
+            .send_measured("/health", request, Priority::Normal)
             .await?;
 
-        if response.status().is_success() {
-            Ok(())
+        // The body is informational; a degraded server may not send JSON.
+        let version = serde_json::from_slice::<Health>(&response.body)
+            .ok()
+            .and_then(|health| health.version);
+        match response.status {
+            200..=299 => Ok(HealthStatus::Healthy { version }),
+            500..=599 => Ok(HealthStatus::Degraded { version }),
+            _ => Err(self.redact(self.parse_error(&response))),
+        }
+    }
+
+    /// Shut the client down gracefully, e.g. before the process exits on
+    /// scale-in.
+    ///
+    /// Requests waiting in a [`SignBatcher`](crate::batcher::SignBatcher)
+    /// built on this client are sent first. New calls are then refused,
+    /// calls already in flight get up to `grace` to finish, and the
+    /// connection pool is dropped once the last of them is done. Clones
+    /// share this state, so all of them are shut down.
+    ///
+    /// Calls made afterwards fail with [`POPSignerError::InvalidRequest`].
+    /// Returns the number of calls still in flight when `grace` ran out,
+    /// which is 0 if the client drained in time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let client = popsigner::Client::new("psk_live_xxxxx");
+    /// let abandoned = client.shutdown(Duration::from_secs(5)).await;
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, grace: Duration) -> usize {
+        let batchers = std::mem::take(&mut *self.inner.batchers.lock().unwrap());
+        batcher::flush_all(batchers).await;
+        self.inner.shut_down.store(true, Ordering::SeqCst);
+
+        let start = Instant::now();
+        while self.inner.in_flight.load(Ordering::SeqCst) > 0 && start.elapsed() < grace {
+            runtime::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+        // Calls still in flight hold their own handle to the transport.
+        self.inner.transport.lock().unwrap().take();
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Remember `batcher` so [`shutdown`](Self::shutdown) can flush it.
+    pub(crate) fn register_batcher(&self, batcher: &Arc<batcher::Inner>) {
+        let mut batchers = self.inner.batchers.lock().unwrap();
+        batchers.retain(|batcher| batcher.strong_count() > 0);
+        batchers.push(Arc::downgrade(batcher));
+    }
+
+    /// Count a call as in flight, or refuse it once the client is shut down.
+    fn start_call(&self) -> Result<InFlight<'_>> {
+        // Count first, so `shutdown` cannot miss a call that passes the check.
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        let in_flight = InFlight(&self.inner.in_flight);
+        self.check_open()?;
+        Ok(in_flight)
+    }
+
+    /// Fail if the client has been shut down.
+    pub(crate) fn check_open(&self) -> Result<()> {
+        if self.inner.shut_down.load(Ordering::SeqCst) {
+            return Err(shut_down());
+        }
+        Ok(())
+    }
+
+    /// Build an authenticated request to `path`.
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&[u8]>,
+        options: &RequestOptions,
+    ) -> Result<Request> {
+        let inner = &self.inner;
+        let url = format!("{}{}", inner.base_url, path);
+        let url = reqwest::Url::parse(&url).map_err(|e| {
+            POPSignerError::InvalidRequest(format!("invalid request URL '{}': {}", url, e))
+        })?;
+
+        // Later inserts win: defaults, then per-request headers, then auth.
+        let mut request_headers = inner.default_headers.clone();
+        for (name, value) in &options.headers {
+            request_headers.insert(name, value.clone());
+        }
+        if let Some(priority) = options.priority {
+            request_headers.insert(
+                PRIORITY_HEADER,
+                header::HeaderValue::from_static(priority.as_str()),
+            );
+        }
+        let (name, value) = inner.credentials.current().header()?;
+        request_headers.insert(name, value);
+        if body.is_some() {
+            request_headers.insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static("application/json"),
+            );
+        }
+
+        Ok(Request {
+            method,
+            url,
+            headers: request_headers,
+            body: body.map(<[u8]>::to_vec),
+            timeout: capped_timeout(options.timeout.or(inner.timeout), options.deadline),
+            max_response_bytes: Some(inner.max_response_bytes),
+        })
+    }
+
+    /// Send a request, waiting for a rate limit permit at `priority` first
+    /// if configured and running it through the interceptors.
+    async fn send(&self, mut request: Request, priority: Priority) -> Result<Response> {
+        if let Some(limiter) = &self.inner.rate_limiter {
+            limiter.acquire(priority).await;
+        }
+        for interceptor in &self.inner.interceptors {
+            interceptor.intercept(&mut request).await;
+        }
+        let limit = self.inner.max_response_bytes;
+        let transport = self.inner.transport.lock().unwrap().clone();
+        let mut response = transport.ok_or_else(shut_down)?.execute(request).await?;
+        // Custom transports may not enforce the limit themselves.
+        if response.body.len() > limit {
+            return Err(POPSignerError::ResponseTooLarge { limit });
+        }
+        if self.inner.compression_threshold.is_some() {
+            compression::decode(&mut response, limit)?;
+        }
+        Ok(response)
+    }
+
+    /// Send one attempt, reporting it to tracing and metrics.
+    async fn send_measured(
+        &self,
+        path: &str,
+        request: Request,
+        priority: Priority,
+    ) -> Result<Response> {
+        let metrics = self.inner.metrics.as_deref();
+        let path = path.split('?').next().unwrap_or(path);
+        if let Some(metrics) = metrics {
+            metrics.on_request_start(path);
+        }
+
+        let start = Instant::now();
+        let result = self.send(request, priority).await;
+        let elapsed = start.elapsed();
+        let status = result.as_ref().ok().map(|response| response.status);
+        if let Some(status) = status {
+            trace::on_response(status, elapsed);
+            self.observe_latency(elapsed);
+        }
+        if let Some(metrics) = metrics {
+            metrics.on_request_end(path, status, elapsed);
+        }
+        if let (Ok(response), Some(hook)) = (&result, &self.inner.raw_response_hook) {
+            if !response.is_success() || self.inner.raw_responses_include_success {
+                hook(&String::from_utf8_lossy(&response.body), response.status);
+            }
+        }
+        result
+    }
+
+    /// Fail with [`POPSignerError::InvalidRequest`] if `len` bytes of data
+    /// exceed the configured payload limit, naming the payload as `what`.
+    pub(crate) fn check_payload_size(&self, what: &str, len: usize) -> Result<()> {
+        match self.inner.max_payload_bytes {
+            Some(max) if len > max => Err(POPSignerError::InvalidRequest(format!(
+                "{} is {} bytes, over the {}-byte limit",
+                what, len, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn observe_latency(&self, elapsed: Duration) {
+        let alpha = self.inner.latency_smoothing;
+        let mut latency = self.inner.latency.lock().unwrap();
+        *latency = Some(match *latency {
+            Some(average) => average.mul_f64(1.0 - alpha) + elapsed.mul_f64(alpha),
+            None => elapsed,
+        });
+    }
+
+    /// Largest data to sign in one request; `None` when the check is off.
+    pub(crate) fn max_payload_bytes(&self) -> Option<usize> {
+        self.inner.max_payload_bytes
+    }
+
+    /// Largest batch the server has said it accepts, if it has.
+    pub(crate) fn max_batch_size(&self) -> Option<usize> {
+        match self.inner.max_batch_size.load(Ordering::Relaxed) {
+            0 => None,
+            max => Some(max),
+        }
+    }
+
+    fn observe_max_batch_size(&self, headers: &header::HeaderMap) {
+        let max = headers
+            .get(MAX_BATCH_SIZE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|max| *max > 0);
+        if let Some(max) = max {
+            self.inner.max_batch_size.store(max, Ordering::Relaxed);
+        }
+    }
+
+    /// Redact `error` if the client is configured to.
+    pub(crate) fn redact(&self, error: POPSignerError) -> POPSignerError {
+        if self.inner.redact {
+            error.redacted()
         } else {
-            Err(self.parse_error(response).await)
+            error
+        }
+    }
+
+    fn should_retry(&self, error: &POPSignerError) -> bool {
+        match &self.inner.retry_classifier {
+            Some(classifier) => classifier(error),
+            None => error.is_retryable(),
+        }
+    }
+
+    fn jitter_unit(&self) -> f64 {
+        match &self.inner.jitter_source {
+            Some(source) => source(),
+            None => retry::random_unit(),
         }
     }
 
-    async fn handle_response<T: serde::de::DeserializeOwned>(
+    /// Fail fast if the circuit breaker is open.
+    fn check_circuit(&self) -> Result<()> {
+        let Some(breaker) = &self.inner.circuit_breaker else {
+            return Ok(());
+        };
+        breaker.try_acquire().map_err(|retry_after| {
+            let e = POPSignerError::CircuitOpen { retry_after };
+            trace::record_error(&e);
+            e
+        })
+    }
+
+    /// Report the outcome of an attempt to the circuit breaker.
+    fn record_circuit(&self, result: &Result<Response>) {
+        let Some(breaker) = &self.inner.circuit_breaker else {
+            return;
+        };
+        match result {
+            Err(e) if self.should_retry(e) => breaker.on_failure(),
+            _ => breaker.on_success(),
+        }
+    }
+
+    fn on_retry(&self, path: &str, attempt: u32, error: &POPSignerError) {
+        if let Some(metrics) = &self.inner.metrics {
+            let path = path.split('?').next().unwrap_or(path);
+            metrics.on_retry(path, attempt, error);
+        }
+    }
+
+    /// Execute a request, retrying per the client's retry policy.
+    ///
+    /// The same body and `options` are sent on every attempt; the timeout
+    /// applies to each attempt separately. Returns the response only if it
+    /// has a success status.
+    async fn execute(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+        options: &RequestOptions,
+    ) -> Result<Response> {
+        self.execute_timed(method, path, body, options)
+            .await
+            .map(Timed::into_inner)
+    }
+
+    /// Like [`execute`](Self::execute), also reporting the time taken and
+    /// the number of attempts.
+    async fn execute_timed(
         &self,
-        response: reqwest::Response,
-    ) -> Result<T> {
-        if response.status().is_success() {
-            let wrapper: ApiResponse<T> = response.json().await?;
-            Ok(wrapper.data)
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+        options: &RequestOptions,
+    ) -> Result<Timed<Response>> {
+        let _in_flight = self.start_call()?;
+        #[cfg(feature = "tracing")]
+        let span = trace::request_span(&method, path);
+
+        let future = self.execute_with_retries(method, path, body, options);
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        future.await
+    }
+
+    async fn execute_with_retries(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+        options: &RequestOptions,
+    ) -> Result<Timed<Response>> {
+        let start = Instant::now();
+        let mut sent = 0;
+        let policy = options.retry.as_ref().unwrap_or(&self.inner.retry_policy);
+        let can_refresh_key = matches!(self.inner.credentials, Credentials::Provider(_));
+        let mut refreshed_key = false;
+        let mut attempt = 1;
+        let mut previous_delay = policy.base_delay;
+        let compressed = match (&body, self.inner.compression_threshold) {
+            (Some(body), Some(threshold)) if body.len() >= threshold => {
+                Some(compression::gzip(body)?)
+            }
+            _ => None,
+        };
+        let mut gzip = compressed.is_some();
+
+        loop {
+            let payload = if gzip {
+                compressed.as_deref()
+            } else {
+                body.as_deref()
+            };
+            if past(options.deadline, Duration::ZERO) {
+                trace::record_error(&POPSignerError::Timeout);
+                return Err(POPSignerError::Timeout);
+            }
+            self.check_circuit()?;
+            sent += 1;
+            let result = self
+                .execute_once(method.clone(), path, payload, gzip, options, attempt)
+                .await;
+            self.record_circuit(&result);
+            match result {
+                Ok(response) => {
+                    let meta = ResponseMeta {
+                        elapsed: start.elapsed(),
+                        attempts: sent,
+                        request_id: request_id(&response.headers),
+                        warnings: Vec::new(),
+                    };
+                    return Ok(Timed {
+                        value: response,
+                        meta,
+                    });
+                }
+                Err(e) if gzip && e.status_code() == Some(415) => {
+                    // The server does not accept gzip bodies; resend as is.
+                    gzip = false;
+                }
+                Err(e @ POPSignerError::Unauthorized { .. })
+                    if can_refresh_key && !refreshed_key =>
+                {
+                    // The key may have rotated mid-flight; the next attempt
+                    // asks the provider again.
+                    self.on_retry(path, attempt, &e);
+                    trace::on_retry(attempt, &e, Duration::ZERO, false);
+                    refreshed_key = true;
+                }
+                Err(e) if attempt < policy.max_attempts && self.should_retry(&e) => {
+                    let retry_after = e.retry_after();
+                    let delay = retry_after.unwrap_or_else(|| {
+                        policy.backoff_with(attempt, previous_delay, &mut || self.jitter_unit())
+                    });
+                    if past(options.deadline, delay) {
+                        // Waiting for the retry would overrun the deadline.
+                        trace::record_error(&POPSignerError::Timeout);
+                        return Err(POPSignerError::Timeout);
+                    }
+                    self.on_retry(path, attempt, &e);
+                    trace::on_retry(attempt, &e, delay, retry_after.is_some());
+                    previous_delay = delay;
+                    runtime::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    let exhausted = attempt > 1 && self.should_retry(&e);
+                    let e = if exhausted {
+                        POPSignerError::RetriesExhausted {
+                            attempts: attempt,
+                            last: Box::new(e),
+                        }
+                    } else {
+                        e
+                    };
+                    if let (Some(hook), Some(info)) = (&self.inner.quota_hook, e.quota_info()) {
+                        hook(&info);
+                    }
+                    let e = self.redact(e);
+                    if exhausted {
+                        trace::on_retries_exhausted(attempt, start.elapsed(), &e);
+                    }
+                    trace::record_error(&e);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Make a single attempt at a request.
+    async fn execute_once(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&[u8]>,
+        gzip: bool,
+        options: &RequestOptions,
+        attempt: u32,
+    ) -> Result<Response> {
+        let mut request = self.request(method, path, body, options)?;
+        if gzip {
+            request.headers.insert(
+                header::CONTENT_ENCODING,
+                header::HeaderValue::from_static("gzip"),
+            );
+        }
+
+        trace::on_request_start(attempt);
+        let priority = options.priority.unwrap_or_default();
+        let response = self.send_measured(path, request, priority).await?;
+        self.observe_max_batch_size(&response.headers);
+        if response.is_success() {
+            Ok(response)
         } else {
-            Err(self.parse_error(response).await)
+            Err(self.version_error(self.parse_error(&response)))
+        }
+    }
+
+    /// Report a rejection of the pinned API version as
+    /// [`POPSignerError::UnsupportedApiVersion`].
+    fn version_error(&self, error: POPSignerError) -> POPSignerError {
+        let Some(requested) = &self.inner.api_version else {
+            return error;
+        };
+        match error {
+            POPSignerError::Api {
+                code,
+                message,
+                status_code,
+                ..
+            } if status_code == 406 || code == ApiErrorCode::UnsupportedApiVersion => {
+                POPSignerError::UnsupportedApiVersion {
+                    requested: requested.clone(),
+                    message,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Make an authenticated GET request.
+    pub(crate) async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let options = RequestOptions::default();
+        let response = self
+            .execute(reqwest::Method::GET, path, None, &options)
+            .await?;
+        self.parse_data(&response)
+    }
+
+    /// Make an authenticated GET request to a cursor-paginated endpoint.
+    pub(crate) async fn get_page<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Page<T>> {
+        let options = RequestOptions::default();
+        let response = self
+            .execute(reqwest::Method::GET, path, None, &options)
+            .await?;
+        let wrapper: PageResponse<T> = self.parse_body(&response)?;
+        self.observe_warnings(&wrapper.warnings);
+        Ok(Page {
+            items: wrapper.data,
+            next_cursor: wrapper
+                .meta
+                .and_then(|meta| meta.next_cursor)
+                .filter(|cursor| !cursor.is_empty()),
+        })
+    }
+
+    /// Make an authenticated POST request.
+    pub(crate) async fn post<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        self.post_with_options(path, body, RequestOptions::default())
+            .await
+    }
+
+    /// Make an authenticated POST request with extra headers or a timeout.
+    pub(crate) async fn post_with_options<T, B>(
+        &self,
+        path: &str,
+        body: &B,
+        options: RequestOptions,
+    ) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        self.post_timed(path, body, options)
+            .await
+            .map(Timed::into_inner)
+    }
+
+    /// Make an authenticated POST request, reporting its [`ResponseMeta`].
+    pub(crate) async fn post_timed<T, B>(
+        &self,
+        path: &str,
+        body: &B,
+        options: RequestOptions,
+    ) -> Result<Timed<T>>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        let body = serde_json::to_vec(body).map_err(|e| {
+            POPSignerError::InvalidRequest(format!("failed to serialize request: {}", e))
+        })?;
+        let response = self
+            .execute_timed(reqwest::Method::POST, path, Some(body), &options)
+            .await?;
+        let (value, warnings) = self.parse_data_warned(&response.value)?;
+        let meta = ResponseMeta {
+            warnings,
+            ..response.meta
+        };
+        Ok(Timed { value, meta })
+    }
+
+    /// Make an authenticated DELETE request.
+    pub(crate) async fn delete(&self, path: &str) -> Result<()> {
+        let options = RequestOptions::default();
+        self.execute(reqwest::Method::DELETE, path, None, &options)
+            .await?;
+        Ok(())
+    }
+
+    /// Unwrap the `data` envelope of a successful response.
+    fn parse_data<T: serde::de::DeserializeOwned>(&self, response: &Response) -> Result<T> {
+        self.parse_data_warned(response).map(|(data, _)| data)
+    }
+
+    /// Like [`parse_data`](Self::parse_data), also returning the server's
+    /// warnings once they have been passed to the warning hook.
+    fn parse_data_warned<T: serde::de::DeserializeOwned>(
+        &self,
+        response: &Response,
+    ) -> Result<(T, Vec<ServerWarning>)> {
+        let wrapper: ApiResponse<T> = self.parse_body(response)?;
+        self.observe_warnings(&wrapper.warnings);
+        Ok((wrapper.data, wrapper.warnings))
+    }
+
+    fn observe_warnings(&self, warnings: &[ServerWarning]) {
+        if let Some(hook) = &self.inner.warning_hook {
+            for warning in warnings {
+                hook(warning);
+            }
         }
     }
 
-    async fn parse_error(&self, response: reqwest::Response) -> POPSignerError {
-        let status = response.status().as_u16();
+    /// Decode a JSON response body.
+    fn parse_body<T: serde::de::DeserializeOwned>(&self, response: &Response) -> Result<T> {
+        let result = if self.inner.strict_schema {
+            parse_strict(&response.body)
+        } else {
+            serde_json::from_slice(&response.body)
+        };
+        result.map_err(|e| {
+            let request_id = request_id(&response.headers);
+            self.redact(POPSignerError::deserialize(e, &response.body, request_id))
+        })
+    }
+
+    fn parse_error(&self, response: &Response) -> POPSignerError {
+        let status = response.status;
+
+        let request_id = request_id(&response.headers);
 
         // Handle specific status codes
         if status == 401 {
-            return POPSignerError::Unauthorized;
+            return POPSignerError::Unauthorized { request_id };
         }
         if status == 429 {
-            return POPSignerError::RateLimited;
+            return POPSignerError::RateLimited {
+                retry_after: parse_retry_after(&response.headers, self.inner.max_retry_after),
+                request_id,
+            };
         }
 
-        let error: std::result::Result<ApiErrorResponse, _> = response.json().await;
+        let error: std::result::Result<ApiErrorResponse, _> =
+            serde_json::from_slice(&response.body);
 
         match error {
             Ok(e) => {
                 // Check for quota exceeded
-                if e.error.code == "quota_exceeded" {
-                    return POPSignerError::QuotaExceeded(e.error.message);
+                if e.error.code == ApiErrorCode::QuotaExceeded {
+                    let resets_at = e
+                        .error
+                        .resets_at
+                        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                        .or_else(|| parse_quota_reset(&response.headers));
+                    return POPSignerError::QuotaExceeded {
+                        message: e.error.message,
+                        resets_at,
+                        org_id: e.error.org_id,
+                        namespace: e.error.namespace,
+                        used: e.error.used,
+                        limit: e.error.limit,
+                        request_id,
+                    };
                 }
                 POPSignerError::Api {
                     code: e.error.code,
                     message: e.error.message,
                     status_code: status,
+                    request_id,
                 }
             }
             Err(_) => POPSignerError::Api {
-                code: "unknown".to_string(),
+                code: ApiErrorCode::Unknown("unknown".to_string()),
                 message: "Unknown error".to_string(),
                 status_code: status,
+                request_id,
             },
         }
     }
 }
 
+/// Marks a call as in flight until dropped.
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn shut_down() -> POPSignerError {
+    POPSignerError::InvalidRequest("client shut down".to_string())
+}
+
+/// Decode `body`, failing on the first field `T` does not know.
+fn parse_strict<T: serde::de::DeserializeOwned>(body: &[u8]) -> serde_json::Result<T> {
+    let mut unknown = None;
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let value: T = serde_ignored::deserialize(&mut deserializer, |path| {
+        unknown.get_or_insert_with(|| path.to_string());
+    })?;
+    deserializer.end()?;
+    match unknown {
+        Some(path) => Err(serde::de::Error::custom(format!(
+            "unknown field `{}`",
+            path
+        ))),
+        None => Ok(value),
+    }
+}
+
+/// Cap an attempt's `timeout` to the time left until `deadline`.
+fn capped_timeout(timeout: Option<Duration>, deadline: Option<Instant>) -> Option<Duration> {
+    let Some(deadline) = deadline else {
+        return timeout;
+    };
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)))
+}
+
+/// Returns true if `deadline` passes within `wait` from now.
+fn past(deadline: Option<Instant>, wait: Duration) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() + wait >= deadline)
+}
+
+/// Read the server-assigned request ID from response headers.
+fn request_id(headers: &header::HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Parse a `Retry-After` header in either delta-seconds or HTTP-date form,
+/// capped at `max`. A date in the past gives zero.
+fn parse_retry_after(headers: &header::HeaderMap, max: Duration) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    let delay = match value.parse::<u64>() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            date.duration_since(runtime::now())
+                .unwrap_or(Duration::ZERO)
+        }
+    };
+    Some(delay.min(max))
+}
+
+/// Parse the quota reset header in either Unix-seconds or HTTP-date form.
+fn parse_quota_reset(headers: &header::HeaderMap) -> Option<SystemTime> {
+    let value = headers.get(QUOTA_RESET_HEADER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    }
+    httpdate::parse_http_date(value).ok()
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ApiResponse<T> {
     pub data: T,
+    #[serde(default)]
+    pub warnings: Vec<ServerWarning>,
+}
+
+#[derive(Deserialize)]
+struct PageResponse<T> {
+    data: Vec<T>,
+    #[serde(default)]
+    meta: Option<PageMeta>,
+    #[serde(default)]
+    warnings: Vec<ServerWarning>,
+}
+
+#[derive(Deserialize)]
+struct PageMeta {
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+/// Run `future`, returning [`POPSignerError::Cancelled`] as soon as `cancel`
+/// fires. In-flight requests are dropped, which aborts them.
+pub(crate) async fn cancellable<T>(
+    cancel: Option<&CancellationToken>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(cancel) = cancel else {
+        return future.await;
+    };
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(POPSignerError::Cancelled),
+        result = future => result,
+    }
+}
+
+/// Returns true if `err` means the server does not know the key.
+pub(crate) fn is_key_not_found(err: &POPSignerError) -> bool {
+    matches!(
+        err,
+        POPSignerError::KeyNotFound(_)
+            | POPSignerError::Api {
+                code: ApiErrorCode::KeyNotFound,
+                ..
+            }
+    )
+}
+
+/// Returns true if `err` is the server's `namespace_not_found`.
+fn is_namespace_not_found(err: &POPSignerError) -> bool {
+    matches!(
+        err,
+        POPSignerError::Api {
+            code: ApiErrorCode::NamespaceNotFound,
+            ..
+        }
+    )
+}
+
+/// Per-call settings layered on top of the client's defaults.
+#[derive(Debug, Default)]
+pub(crate) struct RequestOptions {
+    /// Extra headers, overriding client-wide defaults with the same name.
+    pub headers: header::HeaderMap,
+    /// Timeout for each attempt, overriding the client's timeout.
+    pub timeout: Option<Duration>,
+    /// When the whole call, retries included, must finish.
+    pub deadline: Option<Instant>,
+    /// Retry policy replacing the client's.
+    pub retry: Option<RetryPolicy>,
+    /// Scheduling hint, sent as a header and used by the rate limiter.
+    pub priority: Option<Priority>,
+}
+
+/// Headers the SDK manages itself, which callers may not set.
+const RESERVED_HEADERS: &[&str] = &[
+    "authorization",
+    API_KEY_HEADER,
+    "content-type",
+    "content-length",
+    "host",
+];
+
+/// Validate caller-supplied headers, rejecting invalid and reserved ones.
+pub(crate) fn custom_headers<I, K, V>(headers: I) -> Result<header::HeaderMap>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut map = header::HeaderMap::new();
+    for (name, value) in headers {
+        let name = header::HeaderName::from_bytes(name.as_ref().as_bytes()).map_err(|_| {
+            POPSignerError::InvalidRequest(format!("invalid header name: {:?}", name.as_ref()))
+        })?;
+        if RESERVED_HEADERS.contains(&name.as_str()) {
+            return Err(POPSignerError::InvalidRequest(format!(
+                "header {} is managed by the SDK and cannot be overridden",
+                name
+            )));
+        }
+        let value = header::HeaderValue::from_str(value.as_ref()).map_err(|_| {
+            POPSignerError::InvalidRequest(format!("invalid value for header {}", name))
+        })?;
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+/// Percent-encode a query parameter value.
+pub(crate) fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
 
 #[derive(Deserialize)]
@@ -301,8 +2272,21 @@ struct ApiErrorResponse {
 
 #[derive(Deserialize)]
 struct ApiError {
-    code: String,
+    code: ApiErrorCode,
     message: String,
+    /// Unix seconds when an exceeded quota resets.
+    #[serde(default)]
+    resets_at: Option<u64>,
+    /// Organization and namespace of an exceeded quota.
+    #[serde(default)]
+    org_id: Option<Uuid>,
+    #[serde(default)]
+    namespace: Option<String>,
+    /// Usage of an exceeded quota.
+    #[serde(default)]
+    used: Option<u64>,
+    #[serde(default)]
+    limit: Option<u64>,
 }
 
 #[cfg(test)]
@@ -328,6 +2312,500 @@ mod tests {
         assert_eq!(client.base_url(), "https://custom.api.com");
     }
 
+    #[test]
+    fn test_builder_defaults() {
+        let client = Client::builder().api_key("test_key").build().unwrap();
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+        assert_eq!(
+            client.timeout(),
+            Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+        );
+    }
+
+    #[test]
+    fn test_builder_custom() {
+        let client = Client::builder()
+            .api_key("test_key")
+            .base_url("http://10.0.0.5:8080")
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "http://10.0.0.5:8080");
+        assert_eq!(client.timeout(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_builder_with_http_client() {
+        let http = HttpClient::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .with_http_client(http)
+            .build()
+            .unwrap();
+        assert_eq!(client.timeout(), None);
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .with_http_client(HttpClient::new())
+            .timeout(Duration::from_secs(3))
+            .build()
+            .unwrap();
+        assert_eq!(client.timeout(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_rate_limiter_shared_across_clones() {
+        let client = Client::builder()
+            .api_key("test_key")
+            .rate_limit(10, 5)
+            .build()
+            .unwrap();
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(&client.inner, &clone.inner));
+        assert_eq!(clone.available_permits(), Some(5));
+
+        let result = Client::builder()
+            .api_key("test_key")
+            .rate_limit(0, 5)
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+
+        assert_eq!(Client::new("test_key").available_permits(), None);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_provisioning_errors() {
+        let mock = crate::testing::MockTransport::new();
+        let error = |status, code: &str| {
+            let body = serde_json::json!({ "error": { "code": code, "message": code } });
+            Response::json(status, &body)
+        };
+        mock.push_response(error(409, "conflict"));
+        mock.push_response(error(404, "org_not_found"));
+        mock.push_response(error(409, "conflict"));
+        mock.push_response(Response::new(204, ""));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let org_id = Uuid::nil();
+
+        let err = client
+            .create_namespace(&org_id, "acme", NamespaceSettings::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+        let err = client
+            .create_namespace(&org_id, "acme", NamespaceSettings::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::OrgNotFound(_)));
+
+        let err = client
+            .delete_namespace(&org_id, "acme", false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(msg) if msg.contains("force")));
+        client
+            .delete_namespace(&org_id, "acme", true)
+            .await
+            .unwrap();
+        let url = &mock.last_request().unwrap().url;
+        assert_eq!(url.query(), Some("force=true"));
+    }
+
+    #[tokio::test]
+    async fn test_interceptors_run_in_order_on_every_attempt() {
+        struct Append(&'static str);
+
+        #[async_trait::async_trait]
+        impl Interceptor for Append {
+            async fn intercept(&self, request: &mut Request) {
+                assert!(request.headers.contains_key(header::AUTHORIZATION));
+                request
+                    .headers
+                    .append("x-trail", header::HeaderValue::from_static(self.0));
+            }
+        }
+
+        let mock = crate::testing::MockTransport::new();
+        mock.push_error(POPSignerError::Timeout);
+        mock.push_data(serde_json::json!({ "status": "ok" }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .retry(RetryPolicy {
+                max_attempts: 2,
+                ..RetryPolicy::none()
+            })
+            .add_interceptor(Append("first"))
+            .add_interceptor(Append("second"))
+            .build()
+            .unwrap();
+
+        let _: serde_json::Value = client.get("/v1/status").await.unwrap();
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            let trail: Vec<_> = request.headers.get_all("x-trail").iter().collect();
+            assert_eq!(trail, ["first", "second"]);
+        }
+    }
+
+    #[test]
+    fn test_builder_invalid_base_url() {
+        let result = Client::builder()
+            .api_key("test_key")
+            .base_url("not a url")
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_cost() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "quota_units": 2,
+            "cost": "0.02",
+            "currency": "USD"
+        }));
+        let body = serde_json::json!({
+            "error": { "code": "no_pricing_plan", "message": "no plan" }
+        });
+        mock.push_response(Response::json(404, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(Uuid::nil());
+        let requests = [
+            SignRequest::new(key_id, b"tx-1".to_vec()),
+            SignRequest::new(key_id, vec![0; 32]).prehashed(true),
+        ];
+        let estimate = client.estimate_cost(&requests).await.unwrap();
+        assert_eq!(estimate.quota_units, 2);
+        assert_eq!(estimate.cost, "0.02");
+
+        let sent = mock.requests()[0].body.clone().unwrap();
+        let sent: serde_json::Value = serde_json::from_slice(&sent).unwrap();
+        assert_eq!(sent["operations"][1]["data_len"], 32);
+        assert_eq!(sent["operations"][1]["prehashed"], true);
+
+        let err = client.estimate_cost(&requests).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::NoPricingPlan));
+    }
+
+    #[tokio::test]
+    async fn test_strict_schema_rejects_unknown_fields() {
+        let mock = crate::testing::MockTransport::new();
+        let org = serde_json::json!({
+            "id": Uuid::nil(),
+            "name": "acme",
+            "slug": "acme",
+            "plan": "free",
+            "created_at": "2025-01-01T00:00:00Z",
+            "region": "eu-west-1"
+        });
+        mock.push_data(org.clone());
+        mock.push_data(org);
+        let lenient = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        assert!(lenient.orgs().get_current().await.is_ok());
+
+        let strict = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .strict_schema(true)
+            .build()
+            .unwrap();
+        let err = strict.orgs().get_current().await.unwrap_err();
+        assert!(err.to_string().contains("unknown field `data.region`"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_builder_rejects_malformed_tls_material() {
+        let result = Client::builder()
+            .api_key("test_key")
+            .client_identity(b"not a cert", b"not a key")
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+
+        let result = Client::builder()
+            .api_key("test_key")
+            .add_root_certificate(b"not a cert")
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_default_headers_reject_reserved() {
+        let result = Client::builder()
+            .api_key("test_key")
+            .default_header("Authorization", "Bearer other")
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .default_header("X-Tenant", "acme")
+            .build()
+            .unwrap();
+        assert_eq!(client.inner.default_headers["x-tenant"], "acme");
+    }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("abc-1_2.3~"), "abc-1_2.3~");
+        assert_eq!(encode_query_value("a+b/c="), "a%2Bb%2Fc%3D");
+    }
+
+    #[tokio::test]
+    async fn test_max_response_bytes() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_response(Response::new(500, "x".repeat(65)));
+        mock.push_data(serde_json::json!({ "status": "ok" }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .max_response_bytes(64)
+            .build()
+            .unwrap();
+
+        let err = client.orgs().get_current().await.unwrap_err();
+        assert!(matches!(
+            err,
+            POPSignerError::ResponseTooLarge { limit: 64 }
+        ));
+        assert_eq!(mock.requests()[0].max_response_bytes, Some(64));
+        let _: serde_json::Value = client.get("/v1/status").await.unwrap();
+
+        let result = Client::builder()
+            .api_key("test_key")
+            .max_response_bytes(0)
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_recent_latency_moving_average() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_response(Response::json(
+            400,
+            &serde_json::json!({ "error": { "code": "bad_request", "message": "no" } }),
+        ));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .latency_smoothing(0.5)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.recent_latency(), None);
+        assert!(client.orgs().get_current().await.is_err());
+        assert!(client.recent_latency().is_some());
+
+        client.inner.latency.lock().unwrap().take();
+        client.observe_latency(Duration::from_secs(1));
+        client.observe_latency(Duration::from_secs(3));
+        let latency = client.clone().recent_latency();
+        assert_eq!(latency, Some(Duration::from_secs(2)));
+
+        let result = Client::builder()
+            .api_key("test_key")
+            .latency_smoothing(0.0)
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_debug_redacts_credentials_and_clones_share_state() {
+        let client = Client::builder()
+            .api_key("psk_live_secret")
+            .base_url("https://popsigner.internal")
+            .build()
+            .unwrap();
+        let debug = format!("{:?}", client);
+        assert!(debug.contains("https://popsigner.internal"));
+        assert!(debug.contains("***"));
+        assert!(!debug.contains("psk_live_secret"));
+
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(&client.inner, &clone.inner));
+    }
+
+    #[tokio::test]
+    async fn test_api_version_pinning() {
+        let mock = crate::testing::MockTransport::new();
+        let body = serde_json::json!({
+            "error": { "code": "not_acceptable", "message": "version 2 is not served" }
+        });
+        mock.push_response(Response::json(406, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .api_version("2")
+            .build()
+            .unwrap();
+
+        let err = client.orgs().get_current().await.unwrap_err();
+        assert!(matches!(
+            err,
+            POPSignerError::UnsupportedApiVersion { ref requested, .. } if requested == "2"
+        ));
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.headers[API_VERSION_HEADER], "2");
+
+        let result = Client::builder()
+            .api_key("test_key")
+            .api_version("bad\nversion")
+            .build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted() {
+        let mock = crate::testing::MockTransport::new();
+        let body = serde_json::json!({
+            "error": { "code": "service_unavailable", "message": "down" }
+        });
+        mock.push_error(POPSignerError::Timeout);
+        mock.push_response(Response::json(503, &body).with_header("x-request-id", "req_9"));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .retry(RetryPolicy {
+                max_attempts: 2,
+                ..RetryPolicy::none()
+            })
+            .build()
+            .unwrap();
+
+        let err = client.orgs().get_current().await.unwrap_err();
+        let POPSignerError::RetriesExhausted { attempts, last } = &err else {
+            panic!("expected RetriesExhausted, got {:?}", err);
+        };
+        assert_eq!(*attempts, 2);
+        assert_eq!(last.status_code(), Some(503));
+        assert!(err.is_retryable());
+        assert_eq!(err.status_code(), Some(503));
+        assert_eq!(err.request_id(), Some("req_9"));
+
+        // Without retries there is nothing to exhaust.
+        mock.push_error(POPSignerError::Timeout);
+        let err = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .build()
+            .unwrap()
+            .orgs()
+            .get_current()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::Timeout));
+    }
+
+    #[tokio::test]
+    async fn test_auth_schemes() {
+        let mock = crate::testing::MockTransport::new();
+        let unauthorized = serde_json::json!({
+            "error": { "code": "unauthorized", "message": "expired" }
+        });
+        mock.push_response(Response::json(401, &unauthorized));
+        mock.push_data(serde_json::json!({ "status": "ok" }));
+        mock.push_data(serde_json::json!({ "status": "ok" }));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let client = Client::builder()
+            .auth_provider(move || {
+                Auth::ApiKey(format!("key_{}", counter.fetch_add(1, Ordering::SeqCst)))
+            })
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        // A 401 refreshes the credentials once, whatever the scheme.
+        let _: serde_json::Value = client.get("/v1/status").await.unwrap();
+        let requests = mock.requests();
+        assert_eq!(requests[0].headers[API_KEY_HEADER], "key_0");
+        assert_eq!(requests[1].headers[API_KEY_HEADER], "key_1");
+        assert!(!requests[1].headers.contains_key(header::AUTHORIZATION));
+
+        let client = Client::builder()
+            .auth(Auth::Bearer("oidc_token".to_string()))
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let _: serde_json::Value = client.get("/v1/status").await.unwrap();
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.headers[header::AUTHORIZATION], "Bearer oidc_token");
+        assert_eq!(
+            format!("{:?}", Auth::Bearer("secret".into())),
+            r#"Bearer("***")"#
+        );
+    }
+
+    #[test]
+    fn test_builder_requires_api_key() {
+        let result = Client::builder().build();
+        assert!(matches!(result, Err(POPSignerError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, "120".parse().unwrap());
+        let delay = parse_retry_after(&headers, DEFAULT_MAX_RETRY_AFTER);
+        assert_eq!(delay, Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let when = SystemTime::now() + Duration::from_secs(60);
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::RETRY_AFTER,
+            httpdate::fmt_http_date(when).parse().unwrap(),
+        );
+
+        let delay = parse_retry_after(&headers, DEFAULT_MAX_RETRY_AFTER).unwrap();
+        assert!(delay <= Duration::from_secs(60));
+        assert!(delay >= Duration::from_secs(58));
+    }
+
+    #[test]
+    fn test_parse_retry_after_clamps_skewed_dates() {
+        let max = Duration::from_secs(30);
+        let at = |when: SystemTime| {
+            let mut headers = header::HeaderMap::new();
+            let date = httpdate::fmt_http_date(when).parse().unwrap();
+            headers.insert(header::RETRY_AFTER, date);
+            parse_retry_after(&headers, max)
+        };
+        let hours = Duration::from_secs(3 * 3600);
+        assert_eq!(at(SystemTime::now() + hours), Some(max));
+        assert_eq!(at(SystemTime::now() - hours), Some(Duration::ZERO));
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, "86400".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers, max), Some(max));
+    }
+
+    #[test]
+    fn test_parse_retry_after_missing_or_invalid() {
+        let mut headers = header::HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers, DEFAULT_MAX_RETRY_AFTER), None);
+
+        headers.insert(header::RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers, DEFAULT_MAX_RETRY_AFTER), None);
+    }
+
     #[test]
     fn test_default_config() {
         let config = ClientConfig::default();
@@ -0,0 +1,103 @@
+//! Gzip request and response bodies.
+//!
+//! Enabled with [`ClientBuilder::compression`](crate::ClientBuilder::compression).
+
+use crate::error::{POPSignerError, Result};
+use crate::transport::Response;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use reqwest::header;
+use std::io::{Read, Write};
+
+/// Smallest request body that is compressed by default, in bytes.
+pub(crate) const DEFAULT_THRESHOLD: usize = 1024;
+
+/// Gzip `body`.
+pub(crate) fn gzip(body: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(body.len() / 2), Compression::default());
+    encoder.write_all(body)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decompress `response` in place if the server gzipped it, failing if the
+/// decompressed body is larger than `limit`.
+pub(crate) fn decode(response: &mut Response, limit: usize) -> Result<()> {
+    let gzipped = response
+        .headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("gzip"));
+    if !gzipped {
+        return Ok(());
+    }
+
+    let mut body = Vec::with_capacity(response.body.len() * 2);
+    // Read one byte past the limit to tell a body that fits exactly from
+    // one that does not.
+    GzDecoder::new(response.body.as_slice())
+        .take(limit as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| POPSignerError::Decode(format!("invalid gzip response body: {}", e)))?;
+    if body.len() > limit {
+        return Err(POPSignerError::ResponseTooLarge { limit });
+    }
+    response.body = body;
+    response.headers.remove(header::CONTENT_ENCODING);
+    response.headers.remove(header::CONTENT_LENGTH);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::testing::MockTransport;
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let body = br#"{"data":{"id":"abc"}}"#.repeat(100);
+        let mut response =
+            Response::new(200, gzip(&body).unwrap()).with_header("content-encoding", "gzip");
+        decode(&mut response, usize::MAX).unwrap();
+        assert_eq!(response.body, body);
+        assert!(response.headers.get(header::CONTENT_ENCODING).is_none());
+
+        let mut plain = Response::new(200, "not gzip");
+        decode(&mut plain, usize::MAX).unwrap();
+        assert_eq!(plain.body, b"not gzip");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_media_type_resends_uncompressed() {
+        let mock = MockTransport::new();
+        mock.push_response(Response::json(
+            415,
+            &serde_json::json!({ "error": { "code": "bad_request", "message": "gzip" } }),
+        ));
+        let name = "x".repeat(64);
+        mock.push_data(serde_json::json!({
+            "id": uuid::Uuid::nil(),
+            "name": name,
+            "org_id": uuid::Uuid::nil(),
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .compression(true)
+            .compression_threshold(16)
+            .build()
+            .unwrap();
+
+        let namespace = client.orgs().create_namespace(&name).await.unwrap();
+        assert_eq!(namespace.name, name);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].headers[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(requests[0].headers[header::ACCEPT_ENCODING], "gzip");
+        assert!(requests[1].headers.get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(requests[1].body_json().unwrap()["name"], name);
+    }
+}
@@ -0,0 +1,484 @@
+//! COSE_Sign1 messages (RFC 8152) signed with POPSigner keys.
+//!
+//! [`NamespaceClient::sign_cose_sign1`](crate::namespace::NamespaceClient::sign_cose_sign1)
+//! builds the `Sig_structure` for a payload and protected header, has the
+//! server sign it, and returns the tagged COSE_Sign1 encoding. The header's
+//! `alg` is set from the key's algorithm and the key ID is added as the
+//! unprotected `kid`. [`verify_cose_sign1`](crate::verify::verify_cose_sign1)
+//! checks such a message locally.
+//!
+//! Only the CBOR needed for COSE headers is supported: integers, byte and
+//! text strings, arrays, maps, booleans and null. Maps are encoded in
+//! insertion order with the shortest length forms; external AAD is always
+//! empty and detached payloads are not supported.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::cose::{CborMap, CoseSign1};
+//! use popsigner::{Client, KeyId, NamespaceId};
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let namespace_id: NamespaceId = "...".parse()?;
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! // Content type (label 3): application/cbor.
+//! let mut protected = CborMap::new();
+//! protected.insert(3, 60);
+//! let message = client
+//!     .namespace(&namespace_id)
+//!     .sign_cose_sign1(&key_id, protected, b"reading: 21.5C")
+//!     .await?;
+//! assert_eq!(CoseSign1::decode(&message)?.payload, b"reading: 21.5C");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{POPSignerError, Result};
+
+/// Header label of the algorithm.
+pub const HEADER_ALG: i64 = 1;
+/// Header label of the key ID.
+pub const HEADER_KID: i64 = 4;
+
+/// CBOR tag of a COSE_Sign1 message.
+const COSE_SIGN1_TAG: u64 = 18;
+/// Deepest nesting accepted when decoding, so hostile input cannot
+/// exhaust the stack.
+const MAX_DEPTH: usize = 16;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+/// A CBOR data item, as used in COSE headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CborValue {
+    /// An unsigned or negative integer.
+    Integer(i64),
+    /// A byte string.
+    Bytes(Vec<u8>),
+    /// A UTF-8 text string.
+    Text(String),
+    /// An array.
+    Array(Vec<CborValue>),
+    /// A map.
+    Map(CborMap),
+    /// `true` or `false`.
+    Bool(bool),
+    /// `null`.
+    Null,
+}
+
+impl From<i64> for CborValue {
+    fn from(value: i64) -> Self {
+        CborValue::Integer(value)
+    }
+}
+
+impl From<i32> for CborValue {
+    fn from(value: i32) -> Self {
+        CborValue::Integer(value.into())
+    }
+}
+
+impl From<&str> for CborValue {
+    fn from(value: &str) -> Self {
+        CborValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for CborValue {
+    fn from(value: String) -> Self {
+        CborValue::Text(value)
+    }
+}
+
+impl From<&[u8]> for CborValue {
+    fn from(value: &[u8]) -> Self {
+        CborValue::Bytes(value.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for CborValue {
+    fn from(value: Vec<u8>) -> Self {
+        CborValue::Bytes(value)
+    }
+}
+
+impl From<bool> for CborValue {
+    fn from(value: bool) -> Self {
+        CborValue::Bool(value)
+    }
+}
+
+impl From<CborMap> for CborValue {
+    fn from(value: CborMap) -> Self {
+        CborValue::Map(value)
+    }
+}
+
+/// A CBOR map that keeps its insertion order, e.g. a COSE header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CborMap {
+    entries: Vec<(CborValue, CborValue)>,
+}
+
+impl CborMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `key` to `value`, returning the previous value. A new key goes
+    /// last; an existing one keeps its place.
+    pub fn insert(
+        &mut self,
+        key: impl Into<CborValue>,
+        value: impl Into<CborValue>,
+    ) -> Option<CborValue> {
+        let key = key.into();
+        let value = value.into();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// The value of `key`, if set.
+    pub fn get(&self, key: impl Into<CborValue>) -> Option<&CborValue> {
+        let key = key.into();
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entries, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&CborValue, &CborValue)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+/// A decoded COSE_Sign1 message.
+#[derive(Debug, Clone)]
+pub struct CoseSign1 {
+    /// The protected header.
+    pub protected: CborMap,
+    /// The unprotected header.
+    pub unprotected: CborMap,
+    /// The payload.
+    pub payload: Vec<u8>,
+    /// The signature; raw `r || s` for ECDSA.
+    pub signature: Vec<u8>,
+    /// The protected header as encoded by the signer, which is what the
+    /// signature covers.
+    pub(crate) protected_bytes: Vec<u8>,
+}
+
+impl CoseSign1 {
+    /// Decode a COSE_Sign1 message, tagged or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if `bytes` is not a well-formed
+    /// COSE_Sign1 with an attached payload.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder { bytes, pos: 0 };
+        if bytes.first() == Some(&((MAJOR_TAG << 5) | COSE_SIGN1_TAG as u8)) {
+            decoder.pos = 1;
+        }
+        let value = decoder.value(0)?;
+        if decoder.pos != bytes.len() {
+            return Err(invalid("trailing bytes"));
+        }
+
+        let CborValue::Array(items) = value else {
+            return Err(invalid("not an array"));
+        };
+        let [protected, unprotected, payload, signature]: [CborValue; 4] = items
+            .try_into()
+            .map_err(|_| invalid("expected 4 elements"))?;
+        let CborValue::Bytes(protected_bytes) = protected else {
+            return Err(invalid("protected header is not a byte string"));
+        };
+        let CborValue::Map(unprotected) = unprotected else {
+            return Err(invalid("unprotected header is not a map"));
+        };
+        let payload = match payload {
+            CborValue::Bytes(payload) => payload,
+            CborValue::Null => return Err(invalid("detached payloads are not supported")),
+            _ => return Err(invalid("payload is not a byte string")),
+        };
+        let CborValue::Bytes(signature) = signature else {
+            return Err(invalid("signature is not a byte string"));
+        };
+
+        Ok(Self {
+            protected: decode_header(&protected_bytes)?,
+            unprotected,
+            payload,
+            signature,
+            protected_bytes,
+        })
+    }
+
+    /// The `alg` from the protected header, if it is an integer.
+    pub fn alg(&self) -> Option<i64> {
+        match self.protected.get(HEADER_ALG) {
+            Some(CborValue::Integer(alg)) => Some(*alg),
+            _ => None,
+        }
+    }
+
+    /// The `Sig_structure` the signature covers.
+    pub(crate) fn to_be_signed(&self) -> Vec<u8> {
+        sig_structure(&self.protected_bytes, &self.payload)
+    }
+}
+
+/// Encode `value` as CBOR.
+pub fn encode(value: &CborValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(&mut out, value);
+    out
+}
+
+/// Encode a protected header: an empty map becomes an empty byte string.
+pub(crate) fn encode_header(header: &CborMap) -> Vec<u8> {
+    if header.is_empty() {
+        Vec::new()
+    } else {
+        encode(&CborValue::Map(header.clone()))
+    }
+}
+
+/// The `Sig_structure` for a COSE_Sign1 with empty external AAD.
+pub(crate) fn sig_structure(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    encode(&CborValue::Array(vec![
+        "Signature1".into(),
+        protected.into(),
+        CborValue::Bytes(Vec::new()),
+        payload.into(),
+    ]))
+}
+
+/// Encode a tagged COSE_Sign1 message.
+pub(crate) fn encode_sign1(
+    protected: &[u8],
+    unprotected: CborMap,
+    payload: &[u8],
+    signature: Vec<u8>,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_head(&mut out, MAJOR_TAG, COSE_SIGN1_TAG);
+    write_value(
+        &mut out,
+        &CborValue::Array(vec![
+            protected.into(),
+            unprotected.into(),
+            payload.into(),
+            signature.into(),
+        ]),
+    );
+    out
+}
+
+fn decode_header(bytes: &[u8]) -> Result<CborMap> {
+    if bytes.is_empty() {
+        return Ok(CborMap::new());
+    }
+    let mut decoder = Decoder { bytes, pos: 0 };
+    match decoder.value(0)? {
+        CborValue::Map(map) if decoder.pos == bytes.len() => Ok(map),
+        _ => Err(invalid("protected header is not a map")),
+    }
+}
+
+fn invalid(reason: &str) -> POPSignerError {
+    POPSignerError::Decode(format!("invalid COSE_Sign1: {}", reason))
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        out.push(major | value as u8);
+    } else if let Ok(value) = u8::try_from(value) {
+        out.extend_from_slice(&[major | 24, value]);
+    } else if let Ok(value) = u16::try_from(value) {
+        out.push(major | 25);
+        out.extend_from_slice(&value.to_be_bytes());
+    } else if let Ok(value) = u32::try_from(value) {
+        out.push(major | 26);
+        out.extend_from_slice(&value.to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_value(out: &mut Vec<u8>, value: &CborValue) {
+    match value {
+        CborValue::Integer(n) if *n >= 0 => write_head(out, MAJOR_UNSIGNED, *n as u64),
+        // -1 - n cannot overflow for negative n.
+        CborValue::Integer(n) => write_head(out, MAJOR_NEGATIVE, (-1 - *n) as u64),
+        CborValue::Bytes(bytes) => {
+            write_head(out, MAJOR_BYTES, bytes.len() as u64);
+            out.extend_from_slice(bytes);
+        }
+        CborValue::Text(text) => {
+            write_head(out, MAJOR_TEXT, text.len() as u64);
+            out.extend_from_slice(text.as_bytes());
+        }
+        CborValue::Array(items) => {
+            write_head(out, MAJOR_ARRAY, items.len() as u64);
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        CborValue::Map(map) => {
+            write_head(out, MAJOR_MAP, map.len() as u64);
+            for (key, value) in map.iter() {
+                write_value(out, key);
+                write_value(out, value);
+            }
+        }
+        CborValue::Bool(false) => out.push(0xf4),
+        CborValue::Bool(true) => out.push(0xf5),
+        CborValue::Null => out.push(0xf6),
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        if self.bytes.len() - self.pos < len {
+            return Err(invalid("truncated"));
+        }
+        let taken = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(taken)
+    }
+
+    /// Read an item's major type and argument.
+    fn head(&mut self) -> Result<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let value = match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+            26 => u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Err(invalid("indefinite lengths are not supported")),
+        };
+        Ok((major, value))
+    }
+
+    /// Read a length that must fit in the remaining input.
+    fn length(&self, value: u64) -> Result<usize> {
+        usize::try_from(value)
+            .ok()
+            .filter(|len| *len <= self.bytes.len() - self.pos)
+            .ok_or_else(|| invalid("truncated"))
+    }
+
+    fn value(&mut self, depth: usize) -> Result<CborValue> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("nested too deeply"));
+        }
+        let (major, value) = self.head()?;
+        let integer = || i64::try_from(value).map_err(|_| invalid("integer out of range"));
+        Ok(match major {
+            MAJOR_UNSIGNED => CborValue::Integer(integer()?),
+            MAJOR_NEGATIVE => CborValue::Integer(-1 - integer()?),
+            MAJOR_BYTES => {
+                let len = self.length(value)?;
+                CborValue::Bytes(self.take(len)?.to_vec())
+            }
+            MAJOR_TEXT => {
+                let len = self.length(value)?;
+                let text = std::str::from_utf8(self.take(len)?)
+                    .map_err(|_| invalid("text is not UTF-8"))?;
+                CborValue::Text(text.to_string())
+            }
+            MAJOR_ARRAY => {
+                let len = self.length(value)?;
+                let items = (0..len)
+                    .map(|_| self.value(depth + 1))
+                    .collect::<Result<_>>()?;
+                CborValue::Array(items)
+            }
+            MAJOR_MAP => {
+                let len = self.length(value)?;
+                let mut map = CborMap::new();
+                for _ in 0..len {
+                    let key = self.value(depth + 1)?;
+                    let value = self.value(depth + 1)?;
+                    if map.insert(key, value).is_some() {
+                        return Err(invalid("duplicate map key"));
+                    }
+                }
+                CborValue::Map(map)
+            }
+            MAJOR_SIMPLE => match value {
+                20 => CborValue::Bool(false),
+                21 => CborValue::Bool(true),
+                22 => CborValue::Null,
+                _ => return Err(invalid("unsupported simple value or float")),
+            },
+            _ => return Err(invalid("unsupported CBOR tag")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode_round_trip() {
+        let mut header = CborMap::new();
+        header.insert(HEADER_ALG, -7);
+        assert_eq!(encode_header(&header), [0xa1, 0x01, 0x26]);
+        assert!(encode_header(&CborMap::new()).is_empty());
+
+        let mut unprotected = CborMap::new();
+        unprotected.insert(HEADER_KID, b"key-1".as_slice());
+        let tags = vec![CborValue::Null, CborValue::Integer(1000)];
+        unprotected.insert("tags", CborValue::Array(tags));
+        let protected = encode_header(&header);
+        let message = encode_sign1(&protected, unprotected.clone(), b"payload", vec![7; 64]);
+        assert_eq!(message[0], 0xd2);
+
+        let decoded = CoseSign1::decode(&message).unwrap();
+        assert_eq!(decoded.alg(), Some(-7));
+        assert_eq!(decoded.unprotected, unprotected);
+        assert_eq!(decoded.payload, b"payload");
+        let to_be_signed = sig_structure(&protected, b"payload");
+        assert_eq!(decoded.to_be_signed(), to_be_signed);
+
+        assert!(CoseSign1::decode(&message[..message.len() - 1]).is_err());
+        assert!(CoseSign1::decode(&[0x9f]).is_err());
+    }
+}
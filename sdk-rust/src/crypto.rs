@@ -0,0 +1,596 @@
+//! Public keys and signatures for local cryptographic operations.
+//!
+//! These types carry raw key and signature bytes together with the
+//! algorithm they belong to, so they can be checked offline with the
+//! [`verify`](crate::verify) module.
+
+use crate::error::{POPSignerError, Result};
+use crate::types::{SignatureEncoding, SigningAlgorithm};
+use base64::engine::general_purpose::STANDARD_NO_PAD as BASE64_NO_PAD;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::pkcs8::{DecodePublicKey, EncodePublicKey};
+use sha2::{Digest, Sha256};
+
+const PEM_BEGIN: &str = "-----BEGIN PUBLIC KEY-----";
+const PEM_END: &str = "-----END PUBLIC KEY-----";
+
+const DER_INTEGER: u8 = 0x02;
+const DER_BIT_STRING: u8 = 0x03;
+const DER_SEQUENCE: u8 = 0x30;
+
+/// DER of the `rsaEncryption` AlgorithmIdentifier (OID 1.2.840.113549.1.1.1
+/// with NULL parameters) that wraps RSA keys in an SPKI.
+const RSA_ALGORITHM_ID: [u8; 15] = [
+    0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01, 0x05, 0x00,
+];
+
+/// A public key and the algorithm it is used with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    algorithm: SigningAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Create a public key from raw bytes.
+    ///
+    /// Ed25519 keys are the 32-byte encoding; ECDSA keys are SEC1-encoded
+    /// (33 bytes compressed or 65 bytes uncompressed); RSA keys are a DER
+    /// PKCS#1 `RSAPublicKey` or SubjectPublicKeyInfo.
+    pub fn new(algorithm: SigningAlgorithm, bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            algorithm,
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Create a public key from base64-encoded bytes.
+    pub fn from_base64(algorithm: SigningAlgorithm, encoded: &str) -> Result<Self> {
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| POPSignerError::Decode(format!("invalid base64 public key: {}", e)))?;
+        Ok(Self::new(algorithm, bytes))
+    }
+
+    /// Create a public key from hex-encoded bytes.
+    pub fn from_hex(algorithm: SigningAlgorithm, encoded: &str) -> Result<Self> {
+        let bytes = hex::decode(encoded)
+            .map_err(|e| POPSignerError::Decode(format!("invalid hex public key: {}", e)))?;
+        Ok(Self::new(algorithm, bytes))
+    }
+
+    /// The algorithm this key is used with.
+    pub fn algorithm(&self) -> SigningAlgorithm {
+        self.algorithm
+    }
+
+    /// The raw key bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Parse a DER-encoded SubjectPublicKeyInfo.
+    ///
+    /// The algorithm is taken from the SPKI. ECDSA keys are stored
+    /// compressed, so an uncompressed key does not round-trip byte for byte.
+    /// RSA keys are stored as their PKCS#1 `RSAPublicKey` and tagged
+    /// [`SigningAlgorithm::RsaPkcs1Sha256`], since an `rsaEncryption` SPKI
+    /// does not name the padding; use [`new`](Self::new) to tag one for PSS.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if the input is malformed or not an
+    /// Ed25519, secp256k1, P-256 or RSA key.
+    pub fn from_der(der: &[u8]) -> Result<Self> {
+        if let Ok(key) = ed25519_dalek::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::new(SigningAlgorithm::Ed25519, key.to_bytes()));
+        }
+        if let Ok(key) = k256::PublicKey::from_public_key_der(der) {
+            let point = key.to_encoded_point(true);
+            return Ok(Self::new(
+                SigningAlgorithm::EcdsaSecp256k1,
+                point.as_bytes(),
+            ));
+        }
+        if let Ok(key) = p256::PublicKey::from_public_key_der(der) {
+            let point = key.to_encoded_point(true);
+            return Ok(Self::new(SigningAlgorithm::EcdsaP256, point.as_bytes()));
+        }
+        if let Some(pkcs1) = rsa_spki_key(der).filter(|key| parse_rsa_public_key(key).is_some()) {
+            return Ok(Self::new(SigningAlgorithm::RsaPkcs1Sha256, pkcs1));
+        }
+        Err(POPSignerError::Decode(
+            "invalid or unsupported SPKI public key".to_string(),
+        ))
+    }
+
+    /// Parse a PEM `PUBLIC KEY` block, as produced by
+    /// `openssl pkey -pubout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if the PEM framing or the key
+    /// inside it is malformed.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        let body = pem
+            .trim()
+            .strip_prefix(PEM_BEGIN)
+            .and_then(|rest| rest.strip_suffix(PEM_END))
+            .ok_or_else(|| POPSignerError::Decode("expected a PEM PUBLIC KEY block".to_string()))?;
+        let body: String = body.split_whitespace().collect();
+        let der = BASE64
+            .decode(body)
+            .map_err(|e| POPSignerError::Decode(format!("invalid base64 in PEM: {}", e)))?;
+        Self::from_der(&der)
+    }
+
+    /// Encode the key as a DER SubjectPublicKeyInfo.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if the key bytes are malformed.
+    pub fn to_der(&self) -> Result<Vec<u8>> {
+        let document = match self.algorithm {
+            SigningAlgorithm::Ed25519
+            | SigningAlgorithm::Ed25519ph
+            | SigningAlgorithm::Ed25519ctx => {
+                let bytes: &[u8; 32] = self.bytes.as_slice().try_into().map_err(|_| {
+                    POPSignerError::Decode(format!(
+                        "Ed25519 public key must be 32 bytes, got {}",
+                        self.bytes.len()
+                    ))
+                })?;
+                ed25519_dalek::VerifyingKey::from_bytes(bytes)
+                    .map_err(|e| {
+                        POPSignerError::Decode(format!("invalid Ed25519 public key: {}", e))
+                    })?
+                    .to_public_key_der()
+            }
+            SigningAlgorithm::EcdsaSecp256k1 => k256::PublicKey::from_sec1_bytes(&self.bytes)
+                .map_err(|e| {
+                    POPSignerError::Decode(format!("invalid secp256k1 public key: {}", e))
+                })?
+                .to_public_key_der(),
+            SigningAlgorithm::EcdsaP256 => p256::PublicKey::from_sec1_bytes(&self.bytes)
+                .map_err(|e| POPSignerError::Decode(format!("invalid P-256 public key: {}", e)))?
+                .to_public_key_der(),
+            SigningAlgorithm::RsaPkcs1Sha256 | SigningAlgorithm::RsaPssSha256 => {
+                let mut bits = vec![0];
+                bits.extend_from_slice(self.rsa_pkcs1()?);
+                let mut spki = RSA_ALGORITHM_ID.to_vec();
+                spki.extend(der_encode(DER_BIT_STRING, &bits));
+                return Ok(der_encode(DER_SEQUENCE, &spki));
+            }
+        };
+
+        document
+            .map(|document| document.into_vec())
+            .map_err(|e| POPSignerError::Decode(format!("cannot encode public key: {}", e)))
+    }
+
+    /// SHA-256 of the key's SPKI DER encoding ([`to_der`](Self::to_der)).
+    ///
+    /// This is what `openssl pkey -pubin -outform DER | sha256sum` prints,
+    /// and the same for every algorithm. ECDSA keys are hashed in their
+    /// uncompressed form, so compressed and uncompressed copies of a key
+    /// share a fingerprint.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`to_der`](Self::to_der).
+    pub fn fingerprint_bytes(&self) -> Result<[u8; 32]> {
+        Ok(Sha256::digest(self.to_der()?).into())
+    }
+
+    /// The [`fingerprint_bytes`](Self::fingerprint_bytes) in the
+    /// `SHA256:<unpadded base64>` notation used by OpenSSH.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`to_der`](Self::to_der).
+    pub fn fingerprint(&self) -> Result<String> {
+        Ok(format!(
+            "SHA256:{}",
+            BASE64_NO_PAD.encode(self.fingerprint_bytes()?)
+        ))
+    }
+
+    /// Encode the key as a PEM `PUBLIC KEY` block (SPKI), for OpenSSL and
+    /// other tooling.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`to_der`](Self::to_der).
+    pub fn to_pem(&self) -> Result<String> {
+        let encoded = BASE64.encode(self.to_der()?);
+        let mut pem = format!("{}\n", PEM_BEGIN);
+        for line in encoded.as_bytes().chunks(64) {
+            // Base64 output is ASCII, so every chunk is valid UTF-8.
+            pem.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+            pem.push('\n');
+        }
+        pem.push_str(PEM_END);
+        pem.push('\n');
+        Ok(pem)
+    }
+
+    /// The modulus `n` and public exponent `e` of an RSA key, as unsigned
+    /// big-endian bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if the key is not valid PKCS#1 or
+    /// SPKI DER.
+    pub fn rsa_components(&self) -> Result<(&[u8], &[u8])> {
+        let pkcs1 = self.rsa_pkcs1()?;
+        Ok(parse_rsa_public_key(pkcs1).expect("checked by rsa_pkcs1"))
+    }
+
+    /// The PKCS#1 `RSAPublicKey` of an RSA key stored as PKCS#1 or SPKI.
+    fn rsa_pkcs1(&self) -> Result<&[u8]> {
+        let pkcs1 = rsa_spki_key(&self.bytes).unwrap_or(&self.bytes);
+        match parse_rsa_public_key(pkcs1) {
+            Some(_) => Ok(pkcs1),
+            None => Err(POPSignerError::Decode(
+                "invalid RSA public key: expected PKCS#1 or SPKI DER".to_string(),
+            )),
+        }
+    }
+}
+
+/// Split one DER element off `input`: its tag, its contents and the rest.
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (usize::from(first), rest)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let (len, rest) = rest.split_at(count);
+        let len = len.iter().fold(0, |len, &b| (len << 8) | usize::from(b));
+        (len, rest)
+    };
+    if rest.len() < len {
+        return None;
+    }
+    let (contents, rest) = rest.split_at(len);
+    Some((tag, contents, rest))
+}
+
+/// Encode one DER element.
+fn der_encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = contents.len().to_be_bytes();
+    let skip = len.iter().take_while(|&&b| b == 0).count();
+    if contents.len() >= 0x80 {
+        out.push(0x80 | (len.len() - skip) as u8);
+        out.extend_from_slice(&len[skip..]);
+    } else {
+        out.push(contents.len() as u8);
+    }
+    out.extend_from_slice(contents);
+    out
+}
+
+/// The PKCS#1 `RSAPublicKey` inside an `rsaEncryption` SPKI, if `der` is
+/// one.
+fn rsa_spki_key(der: &[u8]) -> Option<&[u8]> {
+    let (DER_SEQUENCE, spki, []) = der_element(der)? else {
+        return None;
+    };
+    let rest = spki.strip_prefix(&RSA_ALGORITHM_ID[..])?;
+    let (DER_BIT_STRING, bits, []) = der_element(rest)? else {
+        return None;
+    };
+    bits.strip_prefix(&[0])
+}
+
+/// The modulus and public exponent of a PKCS#1 `RSAPublicKey`.
+fn parse_rsa_public_key(pkcs1: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (DER_SEQUENCE, key, []) = der_element(pkcs1)? else {
+        return None;
+    };
+    let (DER_INTEGER, n, rest) = der_element(key)? else {
+        return None;
+    };
+    let (DER_INTEGER, e, []) = der_element(rest)? else {
+        return None;
+    };
+    Some((der_unsigned(n)?, der_unsigned(e)?))
+}
+
+/// A positive DER INTEGER without its leading zero bytes.
+fn der_unsigned(int: &[u8]) -> Option<&[u8]> {
+    if int.first().is_none_or(|&b| b & 0x80 != 0) {
+        return None;
+    }
+    let start = int.iter().position(|&b| b != 0)?;
+    Some(&int[start..])
+}
+
+/// A signature, optionally tagged with the algorithm that produced it.
+///
+/// ECDSA signatures may be either 64-byte raw `r || s` or ASN.1 DER.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    algorithm: Option<SigningAlgorithm>,
+    bytes: Vec<u8>,
+}
+
+impl Signature {
+    /// Create a signature from raw bytes.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            algorithm: None,
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Create a signature tagged with the algorithm that produced it.
+    ///
+    /// Verification fails with [`POPSignerError::SigningError`] if the tag
+    /// does not match the public key's algorithm.
+    pub fn with_algorithm(algorithm: SigningAlgorithm, bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            algorithm: Some(algorithm),
+            bytes: bytes.into(),
+        }
+    }
+
+    /// The algorithm tag, if any.
+    pub fn algorithm(&self) -> Option<SigningAlgorithm> {
+        self.algorithm
+    }
+
+    /// The raw signature bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Convert an ECDSA signature between raw `r || s` and DER.
+    ///
+    /// Ed25519 signatures are already raw and are returned unchanged for
+    /// [`SignatureEncoding::Raw`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::SigningError`] if the signature is untagged
+    /// or the conversion is impossible for its algorithm, and
+    /// [`POPSignerError::Decode`] if the bytes are malformed.
+    pub fn to_encoding(&self, encoding: SignatureEncoding) -> Result<Vec<u8>> {
+        match (self.algorithm, encoding) {
+            (Some(SigningAlgorithm::EcdsaSecp256k1), _) => {
+                transcode_secp256k1(&self.bytes, encoding)
+            }
+            (Some(SigningAlgorithm::EcdsaP256), _) => transcode_p256(&self.bytes, encoding),
+            (Some(algorithm), SignatureEncoding::Raw) if algorithm.is_ed25519() => {
+                Ok(self.bytes.clone())
+            }
+            (Some(algorithm), _) => Err(POPSignerError::SigningError(format!(
+                "cannot encode a {} signature as {}",
+                algorithm, encoding
+            ))),
+            (None, _) => Err(POPSignerError::SigningError(
+                "cannot re-encode a signature of unknown algorithm".to_string(),
+            )),
+        }
+    }
+}
+
+fn transcode_secp256k1(bytes: &[u8], encoding: SignatureEncoding) -> Result<Vec<u8>> {
+    let signature = if bytes.len() == 64 {
+        k256::ecdsa::Signature::from_slice(bytes)
+    } else {
+        k256::ecdsa::Signature::from_der(bytes)
+    }
+    .map_err(|e| POPSignerError::Decode(format!("invalid secp256k1 signature: {}", e)))?;
+
+    Ok(match encoding {
+        SignatureEncoding::Raw => signature.to_bytes().to_vec(),
+        SignatureEncoding::Der => signature.to_der().as_bytes().to_vec(),
+    })
+}
+
+fn transcode_p256(bytes: &[u8], encoding: SignatureEncoding) -> Result<Vec<u8>> {
+    let signature = if bytes.len() == 64 {
+        p256::ecdsa::Signature::from_slice(bytes)
+    } else {
+        p256::ecdsa::Signature::from_der(bytes)
+    }
+    .map_err(|e| POPSignerError::Decode(format!("invalid P-256 signature: {}", e)))?;
+
+    Ok(match encoding {
+        SignatureEncoding::Raw => signature.to_bytes().to_vec(),
+        SignatureEncoding::Der => signature.to_der().as_bytes().to_vec(),
+    })
+}
+
+impl From<Vec<u8>> for Signature {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<&[u8]> for Signature {
+    fn from(bytes: &[u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_key_decoding() {
+        let key = PublicKey::from_hex(SigningAlgorithm::Ed25519, "0102ff").unwrap();
+        assert_eq!(key.as_bytes(), &[0x01, 0x02, 0xff]);
+
+        let key = PublicKey::from_base64(SigningAlgorithm::Ed25519, "AQL/").unwrap();
+        assert_eq!(key.as_bytes(), &[0x01, 0x02, 0xff]);
+
+        let err = PublicKey::from_hex(SigningAlgorithm::Ed25519, "zz").unwrap_err();
+        assert!(matches!(err, POPSignerError::Decode(_)));
+    }
+
+    #[test]
+    fn test_public_key_pem_round_trip() {
+        // RFC 8032, section 7.1, TEST 1.
+        let ed25519 = PublicKey::from_hex(
+            SigningAlgorithm::Ed25519,
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        )
+        .unwrap();
+        let der = ed25519.to_der().unwrap();
+        assert_eq!(hex::encode(&der[..12]), "302a300506032b6570032100");
+        let pem = ed25519.to_pem().unwrap();
+        assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----\n"));
+        assert_eq!(PublicKey::from_pem(&pem).unwrap(), ed25519);
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let point = signing_key.verifying_key().to_encoded_point(true);
+        let secp256k1 = PublicKey::new(SigningAlgorithm::EcdsaSecp256k1, point.as_bytes());
+        let pem = secp256k1.to_pem().unwrap();
+        assert_eq!(PublicKey::from_pem(&pem).unwrap(), secp256k1);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // RFC 8032, section 7.1, TEST 1.
+        let ed25519 = PublicKey::from_hex(
+            SigningAlgorithm::Ed25519,
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        )
+        .unwrap();
+        assert_eq!(
+            ed25519.fingerprint().unwrap(),
+            "SHA256:BuP9j9opu2CrWVV95h7bCuzbIxE0vjDnW0Vfjht5L6k"
+        );
+
+        // The secp256k1 generator point, compressed and uncompressed.
+        let compressed = PublicKey::from_hex(
+            SigningAlgorithm::EcdsaSecp256k1,
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        )
+        .unwrap();
+        let uncompressed = PublicKey::from_hex(
+            SigningAlgorithm::EcdsaSecp256k1,
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap();
+        assert_eq!(
+            compressed.fingerprint().unwrap(),
+            "SHA256:mJ13uvIHWyT4/O2WK8IFJnxlN34JxmMcwaY2Tx33fUw"
+        );
+        assert_eq!(
+            uncompressed.fingerprint_bytes().unwrap(),
+            compressed.fingerprint_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rsa_public_key_spki_round_trip() {
+        // A 512-bit test key from `openssl genpkey`.
+        let pkcs1 = hex::decode(
+            "3048024100c4f057e1a0c264d4378671058c862856058fa3ba4d5b9847cab313c1d683\
+             96bda7c892733a0f450f95e340f53714dd9feb88d346f4e143316fab51d37c1bf39d02\
+             03010001",
+        )
+        .unwrap();
+        let pem = "-----BEGIN PUBLIC KEY-----\n\
+            MFwwDQYJKoZIhvcNAQEBBQADSwAwSAJBAMTwV+GgwmTUN4ZxBYyGKFYFj6O6TVuY\n\
+            R8qzE8HWg5a9p8iSczoPRQ+V40D1NxTdn+uI00b04UMxb6tR03wb850CAwEAAQ==\n\
+            -----END PUBLIC KEY-----\n";
+
+        let rsa = PublicKey::new(SigningAlgorithm::RsaPkcs1Sha256, pkcs1.clone());
+        assert_eq!(rsa.to_pem().unwrap(), pem);
+        assert_eq!(PublicKey::from_pem(pem).unwrap(), rsa);
+        assert_eq!(
+            rsa.fingerprint().unwrap(),
+            "SHA256:Q8My96HiAbKvHMyHcASn6OSjlnYIuVc2jW0S+ufwSRE"
+        );
+
+        // Keys the server already returns as SPKI pass through unchanged.
+        let der = rsa.to_der().unwrap();
+        let spki = PublicKey::new(SigningAlgorithm::RsaPssSha256, der.clone());
+        assert_eq!(spki.to_der().unwrap(), der);
+        let components = rsa.rsa_components().unwrap();
+        assert_eq!(spki.rsa_components().unwrap(), components);
+
+        let (n, e) = components;
+        assert_eq!(n, &pkcs1[5..69]);
+        assert_eq!(e, [0x01, 0x00, 0x01]);
+
+        let truncated = PublicKey::new(SigningAlgorithm::RsaPkcs1Sha256, &pkcs1[..40]);
+        assert!(matches!(truncated.to_der(), Err(POPSignerError::Decode(_))));
+    }
+
+    #[test]
+    fn test_public_key_pem_malformed() {
+        let err = PublicKey::from_pem("not a pem").unwrap_err();
+        assert!(matches!(err, POPSignerError::Decode(_)));
+
+        let pem = "-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PUBLIC KEY-----\n";
+        let err = PublicKey::from_pem(pem).unwrap_err();
+        assert!(matches!(err, POPSignerError::Decode(_)));
+
+        let short = PublicKey::new(SigningAlgorithm::Ed25519, vec![1, 2, 3]);
+        assert!(matches!(short.to_der(), Err(POPSignerError::Decode(_))));
+    }
+
+    #[test]
+    fn test_secp256k1_encoding_round_trip() {
+        use k256::ecdsa::signature::Signer;
+
+        let key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let signed: k256::ecdsa::Signature = key.sign(b"round trip");
+        let raw = signed.to_bytes().to_vec();
+        let der = signed.to_der().as_bytes().to_vec();
+
+        let from_raw = Signature::with_algorithm(SigningAlgorithm::EcdsaSecp256k1, raw.clone());
+        assert_eq!(from_raw.to_encoding(SignatureEncoding::Der).unwrap(), der);
+
+        let from_der = Signature::with_algorithm(SigningAlgorithm::EcdsaSecp256k1, der.clone());
+        assert_eq!(from_der.to_encoding(SignatureEncoding::Raw).unwrap(), raw);
+        assert_eq!(from_der.to_encoding(SignatureEncoding::Der).unwrap(), der);
+    }
+
+    #[test]
+    fn test_p256_encoding_round_trip() {
+        use p256::ecdsa::signature::Signer;
+
+        let key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let signed: p256::ecdsa::Signature = key.sign(b"round trip");
+        let raw = signed.to_bytes().to_vec();
+        let der = signed.to_der().as_bytes().to_vec();
+
+        let from_raw = Signature::with_algorithm(SigningAlgorithm::EcdsaP256, raw.clone());
+        let to_der = from_raw.to_encoding(SignatureEncoding::Der).unwrap();
+        assert_eq!(to_der, der);
+
+        let back = Signature::with_algorithm(SigningAlgorithm::EcdsaP256, to_der);
+        assert_eq!(back.to_encoding(SignatureEncoding::Raw).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_impossible_encoding() {
+        let ed25519 = Signature::with_algorithm(SigningAlgorithm::Ed25519, vec![0u8; 64]);
+        let raw = ed25519.to_encoding(SignatureEncoding::Raw).unwrap();
+        assert_eq!(raw, vec![0u8; 64]);
+        assert!(matches!(
+            ed25519.to_encoding(SignatureEncoding::Der),
+            Err(POPSignerError::SigningError(_))
+        ));
+
+        let untagged = Signature::new(vec![0u8; 64]);
+        assert!(matches!(
+            untagged.to_encoding(SignatureEncoding::Raw),
+            Err(POPSignerError::SigningError(_))
+        ));
+    }
+}
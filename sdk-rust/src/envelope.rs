@@ -0,0 +1,187 @@
+//! Signed envelopes for replay protection.
+//!
+//! A [`SignedEnvelope`] wraps a message with an issue time, an expiry and a
+//! random nonce, and signs all of them together. Receivers check the
+//! signature and the expiry with [`verify_envelope`]; remembering nonces
+//! until they expire, to reject replays within the validity window, is left
+//! to the receiver.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::envelope::verify_envelope;
+//! use popsigner::{Client, KeyId};
+//! use std::time::Duration;
+//!
+//! # async fn example(public_key: popsigner::PublicKey) -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! let envelope = client
+//!     .sign()
+//!     .sign_envelope(&key_id, b"transfer 10", Duration::from_secs(60))
+//!     .await?;
+//! let json = serde_json::to_string(&envelope).unwrap();
+//!
+//! // On the receiving side:
+//! let envelope = serde_json::from_str(&json).unwrap();
+//! assert!(verify_envelope(&public_key, &envelope)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto::{PublicKey, Signature};
+use crate::error::{POPSignerError, Result};
+use crate::runtime;
+use crate::types::SigningAlgorithm;
+use crate::verify::verify_signature;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+/// Version tag mixed into every signed payload.
+const ENVELOPE_VERSION: u32 = 1;
+
+/// A message signed together with its validity window and a nonce.
+///
+/// Serializes to JSON with the message and signature in base64 and times in
+/// Unix seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedEnvelope {
+    /// The wrapped message.
+    #[serde(with = "crate::base64_bytes")]
+    pub message: Vec<u8>,
+    /// When the envelope was created, in Unix seconds.
+    pub issued_at: u64,
+    /// When the envelope stops being valid, in Unix seconds.
+    pub expires_at: u64,
+    /// Random nonce, unique per envelope.
+    pub nonce: String,
+    /// Signature over [`signing_payload`](Self::signing_payload).
+    #[serde(with = "crate::base64_bytes")]
+    pub signature: Vec<u8>,
+    /// Algorithm that produced the signature, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<SigningAlgorithm>,
+}
+
+impl SignedEnvelope {
+    /// Build an unsigned envelope for `message`, valid for `ttl` from now.
+    pub(crate) fn unsigned(message: &[u8], ttl: Duration) -> Self {
+        let issued_at = unix_seconds(runtime::now());
+        Self {
+            message: message.to_vec(),
+            issued_at,
+            expires_at: issued_at.saturating_add(ttl.as_secs()),
+            nonce: Uuid::new_v4().simple().to_string(),
+            signature: Vec::new(),
+            algorithm: None,
+        }
+    }
+
+    /// The canonical bytes that are signed: a JSON object with the version,
+    /// nonce, validity window and base64 message, in that order.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Payload<'a> {
+            v: u32,
+            nonce: &'a str,
+            issued_at: u64,
+            expires_at: u64,
+            message: String,
+        }
+
+        let payload = Payload {
+            v: ENVELOPE_VERSION,
+            nonce: &self.nonce,
+            issued_at: self.issued_at,
+            expires_at: self.expires_at,
+            message: BASE64.encode(&self.message),
+        };
+        serde_json::to_vec(&payload).expect("envelope payload is always serializable")
+    }
+
+    /// Returns the expiry as a [`SystemTime`].
+    pub fn expires_at_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.expires_at)
+    }
+
+    /// Returns true if the envelope has expired.
+    pub fn is_expired(&self) -> bool {
+        unix_seconds(runtime::now()) >= self.expires_at
+    }
+}
+
+/// Verify an envelope's signature with `public_key` and check that it has
+/// not expired.
+///
+/// Returns `Ok(false)` if the signature does not verify, whatever the
+/// expiry.
+///
+/// # Errors
+///
+/// * [`POPSignerError::Expired`] if the signature is valid but the envelope
+///   has expired.
+/// * Any error from [`verify_signature`] for malformed keys or signatures.
+pub fn verify_envelope(public_key: &PublicKey, envelope: &SignedEnvelope) -> Result<bool> {
+    let signature = match envelope.algorithm {
+        Some(algorithm) => Signature::with_algorithm(algorithm, envelope.signature.clone()),
+        None => Signature::new(envelope.signature.clone()),
+    };
+    if !verify_signature(public_key, &envelope.signing_payload(), &signature)? {
+        return Ok(false);
+    }
+    if envelope.is_expired() {
+        return Err(POPSignerError::Expired {
+            expires_at: envelope.expires_at_time(),
+        });
+    }
+    Ok(true)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Signer;
+
+    fn signed(ttl: Duration) -> (PublicKey, SignedEnvelope) {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let public_key = PublicKey::new(
+            SigningAlgorithm::Ed25519,
+            signing_key.verifying_key().to_bytes(),
+        );
+        let mut envelope = SignedEnvelope::unsigned(b"transfer 10", ttl);
+        envelope.signature = signing_key
+            .sign(&envelope.signing_payload())
+            .to_bytes()
+            .to_vec();
+        envelope.algorithm = Some(SigningAlgorithm::Ed25519);
+        (public_key, envelope)
+    }
+
+    #[test]
+    fn test_verify_envelope() {
+        let (public_key, envelope) = signed(Duration::from_secs(60));
+        let json = serde_json::to_string(&envelope).unwrap();
+        let decoded: SignedEnvelope = serde_json::from_str(&json).unwrap();
+        assert!(verify_envelope(&public_key, &decoded).unwrap());
+
+        let mut tampered = decoded.clone();
+        tampered.expires_at += 3600;
+        assert!(!verify_envelope(&public_key, &tampered).unwrap());
+    }
+
+    #[test]
+    fn test_expired_envelope() {
+        let (public_key, envelope) = signed(Duration::ZERO);
+        let err = verify_envelope(&public_key, &envelope).unwrap_err();
+        assert!(matches!(err, POPSignerError::Expired { .. }));
+    }
+}
@@ -3,57 +3,254 @@
 //! This module provides a unified error type for all SDK operations,
 //! with rich error information from the API.
 
+use serde::{Deserialize, Deserializer};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Result type for POPSigner operations.
 pub type Result<T> = std::result::Result<T, POPSignerError>;
 
+/// Error codes returned by the POPSigner API.
+///
+/// Codes the SDK does not know about are preserved in [`ApiErrorCode::Unknown`],
+/// so new server codes never fail to parse.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    /// `unauthorized`
+    Unauthorized,
+    /// `forbidden`
+    Forbidden,
+    /// `not_found`
+    NotFound,
+    /// `key_not_found`
+    KeyNotFound,
+    /// `key_disabled`
+    KeyDisabled,
+    /// `key_version_not_found`
+    KeyVersionNotFound,
+    /// `namespace_not_found`
+    NamespaceNotFound,
+    /// `org_not_found`
+    OrgNotFound,
+    /// `no_pricing_plan`
+    NoPricingPlan,
+    /// `bad_request`
+    BadRequest,
+    /// `validation_error`
+    ValidationError,
+    /// `conflict`
+    Conflict,
+    /// `rate_limited`
+    RateLimited,
+    /// `quota_exceeded`
+    QuotaExceeded,
+    /// `invalid_signature_algorithm`
+    InvalidSignatureAlgorithm,
+    /// `unsupported_api_version`
+    UnsupportedApiVersion,
+    /// `internal_error`
+    InternalError,
+    /// `service_unavailable`
+    ServiceUnavailable,
+    /// Any code not known to this version of the SDK.
+    Unknown(String),
+}
+
+impl ApiErrorCode {
+    /// Returns the code as sent by the server.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorCode::Unauthorized => "unauthorized",
+            ApiErrorCode::Forbidden => "forbidden",
+            ApiErrorCode::NotFound => "not_found",
+            ApiErrorCode::KeyNotFound => "key_not_found",
+            ApiErrorCode::KeyDisabled => "key_disabled",
+            ApiErrorCode::KeyVersionNotFound => "key_version_not_found",
+            ApiErrorCode::NamespaceNotFound => "namespace_not_found",
+            ApiErrorCode::OrgNotFound => "org_not_found",
+            ApiErrorCode::NoPricingPlan => "no_pricing_plan",
+            ApiErrorCode::BadRequest => "bad_request",
+            ApiErrorCode::ValidationError => "validation_error",
+            ApiErrorCode::Conflict => "conflict",
+            ApiErrorCode::RateLimited => "rate_limited",
+            ApiErrorCode::QuotaExceeded => "quota_exceeded",
+            ApiErrorCode::InvalidSignatureAlgorithm => "invalid_signature_algorithm",
+            ApiErrorCode::UnsupportedApiVersion => "unsupported_api_version",
+            ApiErrorCode::InternalError => "internal_error",
+            ApiErrorCode::ServiceUnavailable => "service_unavailable",
+            ApiErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl FromStr for ApiErrorCode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "unauthorized" => ApiErrorCode::Unauthorized,
+            "forbidden" => ApiErrorCode::Forbidden,
+            "not_found" => ApiErrorCode::NotFound,
+            "key_not_found" => ApiErrorCode::KeyNotFound,
+            "key_disabled" => ApiErrorCode::KeyDisabled,
+            "key_version_not_found" => ApiErrorCode::KeyVersionNotFound,
+            "namespace_not_found" => ApiErrorCode::NamespaceNotFound,
+            "org_not_found" => ApiErrorCode::OrgNotFound,
+            "no_pricing_plan" => ApiErrorCode::NoPricingPlan,
+            "bad_request" => ApiErrorCode::BadRequest,
+            "validation_error" => ApiErrorCode::ValidationError,
+            "conflict" => ApiErrorCode::Conflict,
+            "rate_limited" => ApiErrorCode::RateLimited,
+            "quota_exceeded" => ApiErrorCode::QuotaExceeded,
+            "invalid_signature_algorithm" => ApiErrorCode::InvalidSignatureAlgorithm,
+            "unsupported_api_version" => ApiErrorCode::UnsupportedApiVersion,
+            "internal_error" => ApiErrorCode::InternalError,
+            "service_unavailable" => ApiErrorCode::ServiceUnavailable,
+            other => ApiErrorCode::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(code.parse().unwrap_or_else(|e: Infallible| match e {}))
+    }
+}
+
 /// Errors that can occur when using the POPSigner SDK.
 #[derive(Error, Debug)]
 pub enum POPSignerError {
     /// API error from the POPSigner service.
-    #[error("API error ({status_code}): [{code}] {message}")]
+    #[error(
+        "API error ({status_code}): [{code}] {message}{}",
+        display_request_id(.request_id)
+    )]
     Api {
         /// Error code from the API.
-        code: String,
+        code: ApiErrorCode,
         /// Human-readable error message.
         message: String,
         /// HTTP status code.
         status_code: u16,
+        /// Value of the `X-Request-Id` response header, for support tickets.
+        request_id: Option<String>,
     },
 
     /// HTTP request error.
     #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
+
+    /// The request timed out before a response was received.
+    #[error("Request timed out")]
+    Timeout,
+
+    /// Failed to establish a connection (DNS, refused, TLS handshake).
+    #[error("Connection error: {0}")]
+    Connect(String),
 
-    /// Decoding error (base64, etc).
+    /// Reading local input failed, e.g. the stream passed to
+    /// [`SignClient::sign_stream_prehashed`](crate::sign::SignClient::sign_stream_prehashed).
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Decoding error (base64, hex).
     #[error("Decode error: {0}")]
     Decode(String),
 
+    /// The response body did not match the expected schema.
+    #[error(
+        "Failed to deserialize response: {source}{}{}",
+        display_body_snippet(.body_snippet),
+        display_request_id(.request_id)
+    )]
+    Deserialize {
+        /// The underlying JSON error.
+        source: serde_json::Error,
+        /// The first bytes of the raw response body, for debugging.
+        body_snippet: String,
+        /// Value of the `X-Request-Id` response header, for support tickets.
+        request_id: Option<String>,
+    },
+
     /// Authentication error.
-    #[error("Unauthorized: invalid API key")]
-    Unauthorized,
+    #[error("Unauthorized: invalid API key{}", display_request_id(.request_id))]
+    Unauthorized {
+        /// Value of the `X-Request-Id` response header, for support tickets.
+        request_id: Option<String>,
+    },
 
     /// Rate limit exceeded.
-    #[error("Rate limit exceeded")]
-    RateLimited,
+    #[error("Rate limit exceeded{}", display_request_id(.request_id))]
+    RateLimited {
+        /// How long the server asked us to wait, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+        /// Value of the `X-Request-Id` response header, for support tickets.
+        request_id: Option<String>,
+    },
 
     /// Quota exceeded.
-    #[error("Quota exceeded: {0}")]
-    QuotaExceeded(String),
+    #[error("Quota exceeded: {message}{}", display_request_id(.request_id))]
+    QuotaExceeded {
+        /// Human-readable error message.
+        message: String,
+        /// When the quota window resets, if the server said.
+        resets_at: Option<SystemTime>,
+        /// Organization whose quota is exhausted, if the server said.
+        org_id: Option<Uuid>,
+        /// Namespace the request was for, if the server said.
+        namespace: Option<String>,
+        /// Signing operations used in the current window, if the server said.
+        used: Option<u64>,
+        /// Signing operations allowed per window, if the server said.
+        limit: Option<u64>,
+        /// Value of the `X-Request-Id` response header, for support tickets.
+        request_id: Option<String>,
+    },
 
     /// Key not found.
     #[error("Key not found: {0}")]
     KeyNotFound(String),
 
+    /// The key is disabled and cannot sign until it is enabled again.
+    #[error("Key disabled: {0}")]
+    KeyDisabled(String),
+
+    /// The key exists but has no such version.
+    #[error("Key {key_id} has no version {version}")]
+    KeyVersionNotFound {
+        /// The key that was asked for.
+        key_id: String,
+        /// The version that does not exist.
+        version: u32,
+    },
+
     /// Namespace not found.
     #[error("Namespace not found: {0}")]
     NamespaceNotFound(String),
 
+    /// A signing job has expired or was never known to the server.
+    #[error("Sign job not found or expired: {0}")]
+    SignJobNotFound(String),
+
     /// Organization not found.
     #[error("Organization not found: {0}")]
     OrgNotFound(String),
 
+    /// The organization has no pricing plan, so costs cannot be estimated.
+    #[error("Organization has no pricing plan")]
+    NoPricingPlan,
+
     /// Invalid request.
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
@@ -62,23 +259,216 @@ pub enum POPSignerError {
     #[error("Signing error: {0}")]
     SigningError(String),
 
+    /// A signature returned by the server did not verify against the key's
+    /// public key.
+    #[error("Signature from key {0} failed local verification")]
+    VerificationFailed(String),
+
+    /// The operation was cancelled through its cancellation token.
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// A [`SignedEnvelope`](crate::envelope::SignedEnvelope) has a valid
+    /// signature but is past its expiry.
+    #[error("Envelope expired at {expires_at:?}")]
+    Expired {
+        /// When the envelope expired.
+        expires_at: SystemTime,
+    },
+
+    /// A response body was larger than
+    /// [`ClientBuilder::max_response_bytes`](crate::ClientBuilder::max_response_bytes)
+    /// allows.
+    #[error("Response body exceeds the {limit}-byte limit")]
+    ResponseTooLarge {
+        /// The configured limit, in bytes.
+        limit: usize,
+    },
+
+    /// The server cannot serve the API version pinned with
+    /// [`ClientBuilder::api_version`](crate::ClientBuilder::api_version).
+    #[error("Unsupported API version {requested}: {message}")]
+    UnsupportedApiVersion {
+        /// The pinned version.
+        requested: String,
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
+    /// The client's circuit breaker is open after repeated failures, so the
+    /// request was not sent.
+    #[error("Circuit breaker open; retry in {retry_after:?}")]
+    CircuitOpen {
+        /// Time until the breaker lets a probe request through.
+        retry_after: Duration,
+    },
+
+    /// The client's retry policy gave up on a request that kept failing
+    /// with retryable errors.
+    ///
+    /// [`is_retryable`](Self::is_retryable),
+    /// [`status_code`](Self::status_code), [`request_id`](Self::request_id)
+    /// and [`retry_after`](Self::retry_after) report on `last`.
+    #[error("Retries exhausted after {attempts} attempts: {last}")]
+    RetriesExhausted {
+        /// Number of attempts made.
+        attempts: u32,
+        /// The error from the final attempt.
+        last: Box<POPSignerError>,
+    },
+
     /// Batch operation partial failure.
-    #[error("Batch operation had {failed} failures out of {total} requests")]
+    #[error("Batch operation had {} failures out of {total} requests", .failures.len())]
     BatchPartialFailure {
-        /// Number of failed operations.
-        failed: usize,
+        /// Failed operations, keyed by their index in the original request.
+        failures: Vec<(usize, POPSignerError)>,
         /// Total number of operations.
         total: usize,
     },
 }
 
+/// Details of an exceeded quota, from [`POPSignerError::quota_info`] and
+/// passed to
+/// [`ClientBuilder::on_quota_exceeded`](crate::ClientBuilder::on_quota_exceeded).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaInfo {
+    /// Human-readable error message.
+    pub message: String,
+    /// When the quota window resets, if the server said.
+    pub resets_at: Option<SystemTime>,
+    /// Organization whose quota is exhausted, if the server said.
+    pub org_id: Option<Uuid>,
+    /// Namespace the request was for, if the server said.
+    pub namespace: Option<String>,
+    /// Signing operations used in the current window, if the server said.
+    pub used: Option<u64>,
+    /// Signing operations allowed per window, if the server said.
+    pub limit: Option<u64>,
+    /// Value of the `X-Request-Id` response header, for support tickets.
+    pub request_id: Option<String>,
+}
+
+/// Coarse grouping of errors, from [`POPSignerError::category`].
+///
+/// Stable across SDK versions: new error variants are assigned to one of
+/// these categories rather than adding new ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Missing, invalid or insufficient credentials.
+    Auth,
+    /// Too many requests in a short time.
+    RateLimit,
+    /// The organization's usage quota is used up.
+    Quota,
+    /// A key, namespace, organization, job or pricing plan does not exist.
+    NotFound,
+    /// The request was rejected as invalid.
+    Validation,
+    /// The server failed or is unavailable.
+    Server,
+    /// The request or response was lost or garbled on the way.
+    Transport,
+    /// The SDK gave up on the call itself, e.g. on cancellation, a local
+    /// I/O failure or an incompatible API version.
+    Client,
+}
+
+impl ErrorCategory {
+    /// Returns the category as a `snake_case` label, e.g. `"rate_limit"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::RateLimit => "rate_limit",
+            ErrorCategory::Quota => "quota",
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::Validation => "validation",
+            ErrorCategory::Server => "server",
+            ErrorCategory::Transport => "transport",
+            ErrorCategory::Client => "client",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn display_request_id(request_id: &Option<String>) -> String {
+    match request_id {
+        Some(id) => format!(" (request id: {})", id),
+        None => String::new(),
+    }
+}
+
+fn display_body_snippet(body_snippet: &str) -> String {
+    if body_snippet.is_empty() {
+        String::new()
+    } else {
+        format!(" (body: {})", body_snippet)
+    }
+}
+
+/// Replacement text used by [`POPSignerError::redacted`].
+const REDACTED: &str = "[redacted]";
+
+/// A quota error is only worth retrying if the window resets this soon.
+const QUOTA_RESET_RETRY_WINDOW: Duration = Duration::from_secs(60);
+
+/// Maximum number of body bytes kept in [`POPSignerError::Deserialize`].
+const BODY_SNIPPET_LEN: usize = 200;
+
+impl From<serde_json::Error> for POPSignerError {
+    fn from(source: serde_json::Error) -> Self {
+        POPSignerError::Deserialize {
+            source,
+            body_snippet: String::new(),
+            request_id: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for POPSignerError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return POPSignerError::Timeout;
+        }
+        // The fetch backend does not distinguish connection failures.
+        #[cfg(not(target_arch = "wasm32"))]
+        if err.is_connect() {
+            return POPSignerError::Connect(err.to_string());
+        }
+        POPSignerError::Http(err)
+    }
+}
+
 impl POPSignerError {
+    /// Build a [`POPSignerError::Deserialize`] keeping the start of `body`.
+    pub(crate) fn deserialize(
+        source: serde_json::Error,
+        body: &[u8],
+        request_id: Option<String>,
+    ) -> Self {
+        let snippet = &body[..body.len().min(BODY_SNIPPET_LEN)];
+        POPSignerError::Deserialize {
+            source,
+            body_snippet: String::from_utf8_lossy(snippet).into_owned(),
+            request_id,
+        }
+    }
+
     /// Returns true if this is a retryable error.
     pub fn is_retryable(&self) -> bool {
         match self {
-            POPSignerError::RateLimited => true,
-            POPSignerError::Http(_) => true,
+            POPSignerError::RateLimited { .. } => true,
+            POPSignerError::Timeout | POPSignerError::Connect(_) => true,
+            POPSignerError::Http(e) => !e.is_builder(),
             POPSignerError::Api { status_code, .. } => *status_code >= 500,
+            POPSignerError::QuotaExceeded { .. } => self
+                .retry_after()
+                .is_some_and(|delay| delay <= QUOTA_RESET_RETRY_WINDOW),
+            POPSignerError::RetriesExhausted { last, .. } => last.is_retryable(),
             _ => false,
         }
     }
@@ -87,33 +477,401 @@ impl POPSignerError {
     pub fn is_auth_error(&self) -> bool {
         matches!(
             self,
-            POPSignerError::Unauthorized
+            POPSignerError::Unauthorized { .. }
                 | POPSignerError::Api { status_code: 401, .. }
                 | POPSignerError::Api { status_code: 403, .. }
         )
     }
 
+    /// Returns the category of the error, for grouping on dashboards.
+    ///
+    /// API errors are categorized by status code, except that a 429 with
+    /// the `quota_exceeded` code counts as [`ErrorCategory::Quota`].
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            POPSignerError::Api {
+                code, status_code, ..
+            } => match status_code {
+                401 | 403 => ErrorCategory::Auth,
+                429 if *code == ApiErrorCode::QuotaExceeded => ErrorCategory::Quota,
+                429 => ErrorCategory::RateLimit,
+                404 | 410 => ErrorCategory::NotFound,
+                400 | 409 | 422 => ErrorCategory::Validation,
+                400..=499 => ErrorCategory::Client,
+                _ => ErrorCategory::Server,
+            },
+            POPSignerError::Unauthorized { .. } => ErrorCategory::Auth,
+            POPSignerError::RateLimited { .. } => ErrorCategory::RateLimit,
+            POPSignerError::QuotaExceeded { .. } => ErrorCategory::Quota,
+            POPSignerError::KeyNotFound(_)
+            | POPSignerError::KeyVersionNotFound { .. }
+            | POPSignerError::NamespaceNotFound(_)
+            | POPSignerError::SignJobNotFound(_)
+            | POPSignerError::OrgNotFound(_)
+            | POPSignerError::NoPricingPlan => ErrorCategory::NotFound,
+            POPSignerError::InvalidRequest(_)
+            | POPSignerError::KeyDisabled(_)
+            | POPSignerError::Expired { .. } => ErrorCategory::Validation,
+            POPSignerError::SigningError(_)
+            | POPSignerError::VerificationFailed(_)
+            | POPSignerError::CircuitOpen { .. }
+            | POPSignerError::BatchPartialFailure { .. } => ErrorCategory::Server,
+            POPSignerError::Http(_)
+            | POPSignerError::Timeout
+            | POPSignerError::Connect(_)
+            | POPSignerError::Decode(_)
+            | POPSignerError::Deserialize { .. }
+            | POPSignerError::ResponseTooLarge { .. } => ErrorCategory::Transport,
+            POPSignerError::Io(_)
+            | POPSignerError::Cancelled
+            | POPSignerError::UnsupportedApiVersion { .. } => ErrorCategory::Client,
+            POPSignerError::RetriesExhausted { last, .. } => last.category(),
+        }
+    }
+
     /// Returns the HTTP status code if available.
     pub fn status_code(&self) -> Option<u16> {
         match self {
             POPSignerError::Api { status_code, .. } => Some(*status_code),
-            POPSignerError::Unauthorized => Some(401),
-            POPSignerError::RateLimited => Some(429),
+            POPSignerError::Unauthorized { .. } => Some(401),
+            POPSignerError::RateLimited { .. } => Some(429),
+            POPSignerError::RetriesExhausted { last, .. } => last.status_code(),
+            _ => None,
+        }
+    }
+
+    /// Returns the server's `X-Request-Id` for the failed request, if any.
+    ///
+    /// Include this when filing a support ticket.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            POPSignerError::Api { request_id, .. }
+            | POPSignerError::Unauthorized { request_id }
+            | POPSignerError::RateLimited { request_id, .. }
+            | POPSignerError::QuotaExceeded { request_id, .. }
+            | POPSignerError::Deserialize { request_id, .. } => request_id.as_deref(),
+            POPSignerError::RetriesExhausted { last, .. } => last.request_id(),
+            _ => None,
+        }
+    }
+
+    /// Returns the server-recommended delay before retrying, if any.
+    ///
+    /// For [`POPSignerError::QuotaExceeded`] this is the time left until the
+    /// quota window resets.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            POPSignerError::RateLimited { retry_after, .. } => *retry_after,
+            POPSignerError::CircuitOpen { retry_after } => Some(*retry_after),
+            POPSignerError::QuotaExceeded { .. } => self.resets_at().map(|resets_at| {
+                resets_at
+                    .duration_since(crate::runtime::now())
+                    .unwrap_or(Duration::ZERO)
+            }),
+            POPSignerError::RetriesExhausted { last, .. } => last.retry_after(),
+            _ => None,
+        }
+    }
+
+    /// Returns when the exceeded quota window resets, if known.
+    pub fn resets_at(&self) -> Option<SystemTime> {
+        match self {
+            POPSignerError::QuotaExceeded { resets_at, .. } => *resets_at,
             _ => None,
         }
     }
+
+    /// Returns the details of an exceeded quota, looking through
+    /// [`POPSignerError::RetriesExhausted`].
+    pub fn quota_info(&self) -> Option<QuotaInfo> {
+        match self {
+            POPSignerError::QuotaExceeded {
+                message,
+                resets_at,
+                org_id,
+                namespace,
+                used,
+                limit,
+                request_id,
+            } => Some(QuotaInfo {
+                message: message.clone(),
+                resets_at: *resets_at,
+                org_id: *org_id,
+                namespace: namespace.clone(),
+                used: *used,
+                limit: *limit,
+                request_id: request_id.clone(),
+            }),
+            POPSignerError::RetriesExhausted { last, .. } => last.quota_info(),
+            _ => None,
+        }
+    }
+
+    /// Returns the error with server messages, response snippets and other
+    /// free-form text replaced by `[redacted]`, for logs that must not
+    /// persist payloads or key material.
+    ///
+    /// Variants, error codes, status codes, request IDs and resource IDs
+    /// are kept. [`ClientBuilder::redact`](crate::ClientBuilder::redact)
+    /// applies this to every error a client returns.
+    pub fn redacted(self) -> Self {
+        match self {
+            POPSignerError::Api {
+                code,
+                status_code,
+                request_id,
+                ..
+            } => POPSignerError::Api {
+                code,
+                message: REDACTED.to_string(),
+                status_code,
+                request_id,
+            },
+            POPSignerError::Deserialize {
+                source, request_id, ..
+            } => {
+                // serde_json messages can quote the offending value.
+                let message = format!(
+                    "{:?} error at line {} column {}: {}",
+                    source.classify(),
+                    source.line(),
+                    source.column(),
+                    REDACTED
+                );
+                POPSignerError::Deserialize {
+                    source: serde::de::Error::custom(message),
+                    body_snippet: REDACTED.to_string(),
+                    request_id,
+                }
+            }
+            POPSignerError::QuotaExceeded {
+                resets_at,
+                org_id,
+                namespace,
+                used,
+                limit,
+                request_id,
+                ..
+            } => POPSignerError::QuotaExceeded {
+                message: REDACTED.to_string(),
+                resets_at,
+                org_id,
+                namespace,
+                used,
+                limit,
+                request_id,
+            },
+            POPSignerError::UnsupportedApiVersion { requested, .. } => {
+                POPSignerError::UnsupportedApiVersion {
+                    requested,
+                    message: REDACTED.to_string(),
+                }
+            }
+            POPSignerError::Decode(_) => POPSignerError::Decode(REDACTED.to_string()),
+            POPSignerError::InvalidRequest(_) => {
+                POPSignerError::InvalidRequest(REDACTED.to_string())
+            }
+            POPSignerError::SigningError(_) => POPSignerError::SigningError(REDACTED.to_string()),
+            POPSignerError::BatchPartialFailure { failures, total } => {
+                POPSignerError::BatchPartialFailure {
+                    failures: failures
+                        .into_iter()
+                        .map(|(index, e)| (index, e.redacted()))
+                        .collect(),
+                    total,
+                }
+            }
+            POPSignerError::RetriesExhausted { attempts, last } => {
+                POPSignerError::RetriesExhausted {
+                    attempts,
+                    last: Box::new(last.redacted()),
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Map the error to an HTTP status and JSON body, for services that
+    /// proxy signing and return SDK errors from their own handlers.
+    ///
+    /// Auth failures become 401, rate limits and exceeded quotas 429,
+    /// invalid requests 400, missing resources 404, and API errors keep the
+    /// server's status. Transport and decoding failures are reported as
+    /// upstream errors (502, or 504 for timeouts).
+    #[cfg(feature = "http")]
+    pub fn to_http_parts(&self) -> (http::StatusCode, ErrorBody) {
+        use http::StatusCode;
+
+        let (status, code) = match self {
+            POPSignerError::Api {
+                code, status_code, ..
+            } => (
+                StatusCode::from_u16(*status_code).unwrap_or(StatusCode::BAD_GATEWAY),
+                code.as_str(),
+            ),
+            POPSignerError::Unauthorized { .. } => (StatusCode::UNAUTHORIZED, "unauthorized"),
+            POPSignerError::RateLimited { .. } => (StatusCode::TOO_MANY_REQUESTS, "rate_limited"),
+            POPSignerError::QuotaExceeded { .. } => {
+                (StatusCode::TOO_MANY_REQUESTS, "quota_exceeded")
+            }
+            POPSignerError::KeyNotFound(_) => (StatusCode::NOT_FOUND, "key_not_found"),
+            POPSignerError::KeyDisabled(_) => (StatusCode::CONFLICT, "key_disabled"),
+            POPSignerError::KeyVersionNotFound { .. } => {
+                (StatusCode::NOT_FOUND, "key_version_not_found")
+            }
+            POPSignerError::NamespaceNotFound(_) => (StatusCode::NOT_FOUND, "namespace_not_found"),
+            POPSignerError::OrgNotFound(_) => (StatusCode::NOT_FOUND, "org_not_found"),
+            POPSignerError::NoPricingPlan => (StatusCode::NOT_FOUND, "no_pricing_plan"),
+            POPSignerError::SignJobNotFound(_) => (StatusCode::NOT_FOUND, "sign_job_not_found"),
+            POPSignerError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, "bad_request"),
+            POPSignerError::SigningError(_) => (StatusCode::UNPROCESSABLE_ENTITY, "signing_error"),
+            POPSignerError::VerificationFailed(_) => {
+                (StatusCode::BAD_GATEWAY, "verification_failed")
+            }
+            POPSignerError::Expired { .. } => (StatusCode::UNAUTHORIZED, "expired"),
+            POPSignerError::Timeout => (StatusCode::GATEWAY_TIMEOUT, "timeout"),
+            POPSignerError::CircuitOpen { .. } => {
+                (StatusCode::SERVICE_UNAVAILABLE, "service_unavailable")
+            }
+            POPSignerError::Http(_)
+            | POPSignerError::Connect(_)
+            | POPSignerError::Decode(_)
+            | POPSignerError::Deserialize { .. } => (StatusCode::BAD_GATEWAY, "bad_gateway"),
+            POPSignerError::BatchPartialFailure { .. } => {
+                (StatusCode::BAD_GATEWAY, "batch_partial_failure")
+            }
+            POPSignerError::UnsupportedApiVersion { .. } => {
+                (StatusCode::BAD_GATEWAY, "unsupported_api_version")
+            }
+            POPSignerError::ResponseTooLarge { .. } => {
+                (StatusCode::BAD_GATEWAY, "response_too_large")
+            }
+            POPSignerError::RetriesExhausted { last, .. } => {
+                let (status, body) = last.to_http_parts();
+                return (
+                    status,
+                    ErrorBody {
+                        message: self.to_string(),
+                        ..body
+                    },
+                );
+            }
+            POPSignerError::Io(_) | POPSignerError::Cancelled => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal_error")
+            }
+        };
+
+        let body = ErrorBody {
+            code: code.to_string(),
+            message: self.to_string(),
+            request_id: self.request_id().map(str::to_string),
+            retry_after_secs: self.retry_after().map(|delay| delay.as_secs()),
+        };
+        (status, body)
+    }
+}
+
+/// JSON error body built by [`POPSignerError::to_http_parts`].
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ErrorBody {
+    /// Machine-readable error code, e.g. `rate_limited`.
+    pub code: String,
+    /// Human-readable message.
+    pub message: String,
+    /// Upstream POPSigner request ID, for support tickets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Seconds to wait before retrying, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Lets handlers returning `Result<_, (StatusCode, ErrorBody)>` use `?` on
+/// SDK calls; see [`POPSignerError::to_http_parts`].
+#[cfg(feature = "http")]
+impl From<POPSignerError> for (http::StatusCode, ErrorBody) {
+    fn from(err: POPSignerError) -> Self {
+        err.to_http_parts()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_to_http_parts() {
+        let (status, body) = POPSignerError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+            request_id: None,
+        }
+        .to_http_parts();
+        assert_eq!(status, http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body.code, "rate_limited");
+        assert_eq!(body.retry_after_secs, Some(3));
+
+        let (status, body) = POPSignerError::Api {
+            code: ApiErrorCode::Forbidden,
+            message: "no".to_string(),
+            status_code: 403,
+            request_id: Some("req_1".to_string()),
+        }
+        .to_http_parts();
+        assert_eq!(status, http::StatusCode::FORBIDDEN);
+        assert_eq!(
+            serde_json::to_value(body).unwrap()["request_id"],
+            serde_json::json!("req_1")
+        );
+
+        let (status, _) = POPSignerError::InvalidRequest("bad".to_string()).to_http_parts();
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_into_http_parts_with_question_mark() {
+        fn handler(key: &str) -> std::result::Result<String, (http::StatusCode, ErrorBody)> {
+            let key = key
+                .parse::<crate::KeyId>()
+                .map_err(|_| POPSignerError::KeyNotFound(key.to_string()))?;
+            Ok(key.to_string())
+        }
+
+        let (status, body) = handler("not-a-key").unwrap_err();
+        assert_eq!(status, http::StatusCode::NOT_FOUND);
+        assert_eq!(body.code, "key_not_found");
+        assert_eq!(body.message, "Key not found: not-a-key");
+    }
+
+    #[test]
+    fn test_redacted() {
+        let err = POPSignerError::Api {
+            code: ApiErrorCode::BadRequest,
+            message: "invalid payload deadbeef".to_string(),
+            status_code: 400,
+            request_id: Some("req_1".to_string()),
+        }
+        .redacted();
+        assert_eq!(
+            err.to_string(),
+            "API error (400): [bad_request] [redacted] (request id: req_1)"
+        );
+
+        let source = serde_json::from_str::<u32>("\"secret\"").unwrap_err();
+        let err = POPSignerError::deserialize(source, b"\"secret\"", None).redacted();
+        assert!(!err.to_string().contains("secret"));
+        assert!(matches!(err, POPSignerError::Deserialize { .. }));
+    }
+
     #[test]
     fn test_error_display() {
         let err = POPSignerError::Api {
-            code: "key_not_found".to_string(),
+            code: ApiErrorCode::KeyNotFound,
             message: "Key does not exist".to_string(),
             status_code: 404,
+            request_id: None,
         };
         assert_eq!(
             err.to_string(),
@@ -121,35 +879,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_request_id() {
+        let err = POPSignerError::Api {
+            code: ApiErrorCode::InternalError,
+            message: "boom".to_string(),
+            status_code: 500,
+            request_id: Some("req_123".to_string()),
+        };
+        assert_eq!(err.request_id(), Some("req_123"));
+        assert_eq!(
+            err.to_string(),
+            "API error (500): [internal_error] boom (request id: req_123)"
+        );
+        assert_eq!(POPSignerError::Timeout.request_id(), None);
+    }
+
     #[test]
     fn test_is_retryable() {
-        let rate_limited = POPSignerError::RateLimited;
+        let rate_limited = POPSignerError::RateLimited {
+            retry_after: None,
+            request_id: None,
+        };
         assert!(rate_limited.is_retryable());
 
         let server_error = POPSignerError::Api {
-            code: "internal".to_string(),
+            code: ApiErrorCode::InternalError,
             message: "Internal server error".to_string(),
             status_code: 500,
+            request_id: None,
         };
         assert!(server_error.is_retryable());
 
         let not_found = POPSignerError::Api {
-            code: "not_found".to_string(),
+            code: ApiErrorCode::NotFound,
             message: "Not found".to_string(),
             status_code: 404,
+            request_id: None,
         };
         assert!(!not_found.is_retryable());
+
+        assert!(POPSignerError::Timeout.is_retryable());
+        assert!(POPSignerError::Connect("connection refused".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_category() {
+        let api = |code: ApiErrorCode, status_code: u16| POPSignerError::Api {
+            code,
+            message: "boom".to_string(),
+            status_code,
+            request_id: None,
+        };
+        let id = || "id".to_string();
+        let source = serde_json::from_str::<u32>("x").unwrap_err();
+        let http = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let cases = [
+            (api(ApiErrorCode::Forbidden, 403), ErrorCategory::Auth),
+            (
+                api(ApiErrorCode::RateLimited, 429),
+                ErrorCategory::RateLimit,
+            ),
+            (api(ApiErrorCode::QuotaExceeded, 429), ErrorCategory::Quota),
+            (api(ApiErrorCode::NotFound, 404), ErrorCategory::NotFound),
+            (
+                api(ApiErrorCode::ValidationError, 422),
+                ErrorCategory::Validation,
+            ),
+            (
+                api(ApiErrorCode::UnsupportedApiVersion, 406),
+                ErrorCategory::Client,
+            ),
+            (
+                api(ApiErrorCode::ServiceUnavailable, 503),
+                ErrorCategory::Server,
+            ),
+            (
+                POPSignerError::Unauthorized { request_id: None },
+                ErrorCategory::Auth,
+            ),
+            (
+                POPSignerError::RateLimited {
+                    retry_after: None,
+                    request_id: None,
+                },
+                ErrorCategory::RateLimit,
+            ),
+            (
+                POPSignerError::QuotaExceeded {
+                    message: "used up".to_string(),
+                    resets_at: None,
+                    org_id: None,
+                    namespace: None,
+                    used: None,
+                    limit: None,
+                    request_id: None,
+                },
+                ErrorCategory::Quota,
+            ),
+            (POPSignerError::KeyNotFound(id()), ErrorCategory::NotFound),
+            (
+                POPSignerError::KeyVersionNotFound {
+                    key_id: id(),
+                    version: 2,
+                },
+                ErrorCategory::NotFound,
+            ),
+            (
+                POPSignerError::NamespaceNotFound(id()),
+                ErrorCategory::NotFound,
+            ),
+            (
+                POPSignerError::SignJobNotFound(id()),
+                ErrorCategory::NotFound,
+            ),
+            (POPSignerError::OrgNotFound(id()), ErrorCategory::NotFound),
+            (POPSignerError::NoPricingPlan, ErrorCategory::NotFound),
+            (
+                POPSignerError::InvalidRequest(id()),
+                ErrorCategory::Validation,
+            ),
+            (POPSignerError::KeyDisabled(id()), ErrorCategory::Validation),
+            (
+                POPSignerError::Expired {
+                    expires_at: SystemTime::UNIX_EPOCH,
+                },
+                ErrorCategory::Validation,
+            ),
+            (POPSignerError::SigningError(id()), ErrorCategory::Server),
+            (
+                POPSignerError::VerificationFailed(id()),
+                ErrorCategory::Server,
+            ),
+            (
+                POPSignerError::CircuitOpen {
+                    retry_after: Duration::ZERO,
+                },
+                ErrorCategory::Server,
+            ),
+            (
+                POPSignerError::BatchPartialFailure {
+                    failures: Vec::new(),
+                    total: 1,
+                },
+                ErrorCategory::Server,
+            ),
+            (POPSignerError::Http(http), ErrorCategory::Transport),
+            (POPSignerError::Timeout, ErrorCategory::Transport),
+            (POPSignerError::Connect(id()), ErrorCategory::Transport),
+            (POPSignerError::Decode(id()), ErrorCategory::Transport),
+            (
+                POPSignerError::deserialize(source, b"x", None),
+                ErrorCategory::Transport,
+            ),
+            (
+                POPSignerError::ResponseTooLarge { limit: 1 },
+                ErrorCategory::Transport,
+            ),
+            (
+                POPSignerError::Io(std::io::Error::other("disk")),
+                ErrorCategory::Client,
+            ),
+            (POPSignerError::Cancelled, ErrorCategory::Client),
+            (
+                POPSignerError::UnsupportedApiVersion {
+                    requested: "1".to_string(),
+                    message: id(),
+                },
+                ErrorCategory::Client,
+            ),
+            (
+                POPSignerError::RetriesExhausted {
+                    attempts: 3,
+                    last: Box::new(POPSignerError::Timeout),
+                },
+                ErrorCategory::Transport,
+            ),
+        ];
+        for (err, category) in cases {
+            assert_eq!(err.category(), category, "{:?}", err);
+        }
+        assert_eq!(ErrorCategory::RateLimit.to_string(), "rate_limit");
+    }
+
+    #[test]
+    fn test_builder_error_not_retryable() {
+        let err = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let err = POPSignerError::from(err);
+        assert!(matches!(err, POPSignerError::Http(_)));
+        assert!(!err.is_retryable());
     }
 
     #[test]
     fn test_is_auth_error() {
-        let unauthorized = POPSignerError::Unauthorized;
+        let unauthorized = POPSignerError::Unauthorized { request_id: None };
         assert!(unauthorized.is_auth_error());
 
         let api_401 = POPSignerError::Api {
-            code: "unauthorized".to_string(),
+            code: ApiErrorCode::Unauthorized,
             message: "Invalid API key".to_string(),
             status_code: 401,
+            request_id: None,
         };
         assert!(api_401.is_auth_error());
     }
@@ -157,13 +1087,92 @@ mod tests {
     #[test]
     fn test_status_code() {
         let err = POPSignerError::Api {
-            code: "test".to_string(),
+            code: ApiErrorCode::Unknown("test".to_string()),
             message: "Test".to_string(),
             status_code: 500,
+            request_id: None,
         };
         assert_eq!(err.status_code(), Some(500));
 
         let decode_err = POPSignerError::Decode("bad base64".to_string());
         assert_eq!(decode_err.status_code(), None);
     }
+
+    #[test]
+    fn test_deserialize_error_snippet() {
+        let body = format!(r#"{{"data": "{}"}}"#, "x".repeat(500));
+        let source = serde_json::from_slice::<u32>(body.as_bytes()).unwrap_err();
+        let err = POPSignerError::deserialize(source, body.as_bytes(), None);
+        match &err {
+            POPSignerError::Deserialize { body_snippet, .. } => {
+                assert_eq!(body_snippet.len(), BODY_SNIPPET_LEN);
+                assert!(body_snippet.starts_with(r#"{"data": "xxx"#));
+            }
+            other => panic!("expected Deserialize, got {:?}", other),
+        }
+        assert!(!err.is_retryable());
+
+        let err: POPSignerError = serde_json::from_str::<u32>("nope").unwrap_err().into();
+        let message = err.to_string();
+        assert!(message.starts_with("Failed to deserialize response:"));
+    }
+
+    #[test]
+    fn test_batch_partial_failure_display() {
+        let err = POPSignerError::BatchPartialFailure {
+            failures: vec![(2, POPSignerError::SigningError("quota".to_string()))],
+            total: 4,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Batch operation had 1 failures out of 4 requests"
+        );
+    }
+
+    #[test]
+    fn test_api_error_code_parsing() {
+        let code: ApiErrorCode = "quota_exceeded".parse().unwrap();
+        assert_eq!(code, ApiErrorCode::QuotaExceeded);
+        assert_eq!(code.as_str(), "quota_exceeded");
+
+        let code: ApiErrorCode = serde_json::from_str(r#""brand_new_code""#).unwrap();
+        assert_eq!(code, ApiErrorCode::Unknown("brand_new_code".to_string()));
+        assert_eq!(code.as_str(), "brand_new_code");
+    }
+
+    #[test]
+    fn test_quota_exceeded_reset() {
+        let quota = |resets_at| POPSignerError::QuotaExceeded {
+            message: "monthly quota exceeded".to_string(),
+            resets_at,
+            org_id: None,
+            namespace: None,
+            used: None,
+            limit: None,
+            request_id: None,
+        };
+        let soon = quota(Some(SystemTime::now() + Duration::from_secs(10)));
+        assert_eq!(soon.to_string(), "Quota exceeded: monthly quota exceeded");
+        assert!(soon.resets_at().is_some());
+        assert!(soon.retry_after().unwrap() <= Duration::from_secs(10));
+        assert!(soon.is_retryable());
+
+        let later = quota(Some(SystemTime::now() + Duration::from_secs(86_400)));
+        assert!(!later.is_retryable());
+
+        let unknown = quota(None);
+        assert!(!unknown.is_retryable());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let err = POPSignerError::RateLimited {
+            retry_after: Some(Duration::from_secs(5)),
+            request_id: None,
+        };
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(5)));
+
+        let err = POPSignerError::Unauthorized { request_id: None };
+        assert_eq!(err.retry_after(), None);
+    }
 }
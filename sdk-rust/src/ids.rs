@@ -0,0 +1,185 @@
+//! Typed key and namespace IDs.
+//!
+//! Both IDs are UUIDs on the wire. Wrapping them in [`KeyId`] and
+//! [`NamespaceId`] turns passing one where the other is expected into a
+//! compile error instead of a `key_not_found` at runtime.
+//!
+//! # Example
+//!
+//! ```rust
+//! use popsigner::{KeyId, NamespaceId};
+//! use uuid::Uuid;
+//!
+//! # fn main() -> popsigner::Result<()> {
+//! let key_id: KeyId = "6c2f3a4e-8b1d-4f6a-9c3e-2d5b7a9e1f08".parse()?;
+//! let namespace_id = NamespaceId::from(Uuid::nil());
+//!
+//! assert_eq!(key_id.to_string(), "6c2f3a4e-8b1d-4f6a-9c3e-2d5b7a9e1f08");
+//! assert!(namespace_id.is_nil()); // derefs to `Uuid`
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{POPSignerError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use uuid::Uuid;
+
+macro_rules! uuid_id {
+    ($(#[$meta:meta])* $name:ident, $what:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            Default,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            Serialize,
+            Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            /// Wrap a UUID.
+            pub const fn new(id: Uuid) -> Self {
+                Self(id)
+            }
+
+            /// Returns the underlying UUID.
+            pub const fn as_uuid(&self) -> &Uuid {
+                &self.0
+            }
+
+            /// Unwrap the underlying UUID.
+            pub const fn into_uuid(self) -> Uuid {
+                self.0
+            }
+        }
+
+        impl From<Uuid> for $name {
+            fn from(id: Uuid) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&Uuid> for $name {
+            fn from(id: &Uuid) -> Self {
+                Self(*id)
+            }
+        }
+
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<Uuid> for $name {
+            fn eq(&self, other: &Uuid) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<$name> for Uuid {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Uuid;
+
+            fn deref(&self) -> &Uuid {
+                &self.0
+            }
+        }
+
+        impl AsRef<Uuid> for $name {
+            fn as_ref(&self) -> &Uuid {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = POPSignerError;
+
+            /// Parse a UUID string, returning
+            /// [`POPSignerError::InvalidRequest`] if it is malformed.
+            fn from_str(s: &str) -> Result<Self> {
+                Uuid::parse_str(s).map(Self).map_err(|e| {
+                    POPSignerError::InvalidRequest(format!(
+                        concat!("invalid ", $what, " {:?}: {}"),
+                        s,
+                        e
+                    ))
+                })
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = POPSignerError;
+
+            fn try_from(s: &str) -> Result<Self> {
+                s.parse()
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = POPSignerError;
+
+            fn try_from(s: String) -> Result<Self> {
+                s.parse()
+            }
+        }
+    };
+}
+
+uuid_id!(
+    /// ID of a key.
+    ///
+    /// Construct one from a [`Uuid`] with `From`, or parse a string with
+    /// [`str::parse`] or `TryFrom`. Strings are not converted with `From`
+    /// because parsing can fail.
+    KeyId,
+    "key ID"
+);
+
+uuid_id!(
+    /// ID of a namespace. See [`KeyId`] for conversions.
+    NamespaceId,
+    "namespace ID"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_conversions() {
+        let uuid = Uuid::from_u128(7);
+        let key_id = KeyId::from(uuid);
+        assert_eq!(key_id, uuid);
+        assert_eq!(Uuid::from(key_id), uuid);
+        assert_eq!(key_id.to_string().parse::<KeyId>().unwrap(), key_id);
+        assert_eq!(
+            serde_json::to_value(key_id).unwrap(),
+            serde_json::json!(uuid.to_string())
+        );
+
+        let err = NamespaceId::try_from("not-a-uuid").unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+}
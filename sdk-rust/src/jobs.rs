@@ -0,0 +1,107 @@
+//! Asynchronous signing for slow keys.
+//!
+//! Keys backed by an HSM can take seconds to sign. Instead of holding a
+//! connection open for that long,
+//! [`SignClient::sign_async`](crate::sign::SignClient::sign_async) submits
+//! the request as a job and returns a [`SignJobHandle`] straight away. Poll
+//! it with [`SignClient::poll_sign_job`](crate::sign::SignClient::poll_sign_job),
+//! or wait for the signature with [`SignJobHandle::await_completion`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::{Client, KeyId, SignRequest};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! let job = client
+//!     .sign()
+//!     .sign_async(&SignRequest::new(key_id, b"tx".to_vec()))
+//!     .await?;
+//! let response = job.await_completion(Duration::from_millis(500)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::runtime;
+use crate::types::{SignRequest, SignResponse, SigningAlgorithm};
+use std::time::Duration;
+
+/// A signing job submitted with
+/// [`SignClient::sign_async`](crate::sign::SignClient::sign_async).
+///
+/// Holds a handle to the client that submitted it, so it can be awaited on
+/// its own.
+#[derive(Debug, Clone)]
+pub struct SignJobHandle {
+    client: Client,
+    job_id: String,
+    key_id: KeyId,
+    pub(crate) algorithm: Option<SigningAlgorithm>,
+    pub(crate) context: Option<Vec<u8>>,
+}
+
+impl SignJobHandle {
+    pub(crate) fn new(client: Client, job_id: String, request: &SignRequest) -> Self {
+        Self {
+            client,
+            job_id,
+            key_id: request.key_id,
+            algorithm: request.algorithm,
+            context: request.context.clone(),
+        }
+    }
+
+    /// The server-assigned job ID.
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    /// The key the job signs with.
+    pub fn key_id(&self) -> &KeyId {
+        &self.key_id
+    }
+
+    /// Poll the job every `poll_interval` until it finishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::SigningError`] if the job failed,
+    /// [`POPSignerError::SignJobNotFound`] if it expired or is unknown, and
+    /// any error from polling.
+    pub async fn await_completion(&self, poll_interval: Duration) -> Result<SignResponse> {
+        loop {
+            match self.client.sign().poll_sign_job(self).await? {
+                SignJobStatus::Pending => runtime::sleep(poll_interval).await,
+                SignJobStatus::Completed(response) => return Ok(response),
+                SignJobStatus::Failed(message) => {
+                    return Err(POPSignerError::SigningError(message));
+                }
+            }
+        }
+    }
+}
+
+/// State of a signing job.
+#[derive(Debug, Clone)]
+pub enum SignJobStatus {
+    /// The job is queued or running.
+    Pending,
+    /// The job finished and produced a signature.
+    Completed(SignResponse),
+    /// The job failed, with the server's reason.
+    Failed(String),
+}
+
+impl SignJobStatus {
+    /// Returns true once the job has completed or failed.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, SignJobStatus::Pending)
+    }
+}
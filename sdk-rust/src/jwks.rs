@@ -0,0 +1,198 @@
+//! JSON Web Key Set export.
+//!
+//! [`NamespaceClient::export_jwks`](crate::namespace::NamespaceClient::export_jwks)
+//! turns a namespace's keys into a [`Jwks`] that serializes to the standard
+//! `{"keys": [...]}` document (RFC 7517), ready to publish to partners.
+
+use crate::crypto::PublicKey;
+use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::types::SigningAlgorithm;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use serde::{Deserialize, Serialize};
+
+/// A JSON Web Key Set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Jwks {
+    /// The exported keys.
+    pub keys: Vec<Jwk>,
+    /// IDs of keys left out because their public key could not be decoded.
+    /// Not serialized.
+    #[serde(skip)]
+    pub skipped: Vec<KeyId>,
+}
+
+/// A single public signing key in JWK form.
+///
+/// Ed25519 keys are `OKP` keys with `x`; ECDSA keys are `EC` keys with `x`
+/// and `y`; RSA keys are `RSA` keys with `n` and `e`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    /// Key ID; the POPSigner key ID.
+    pub kid: String,
+    /// Key type: `OKP`, `EC` or `RSA`.
+    pub kty: String,
+    /// Curve name, e.g. `Ed25519`, `secp256k1` or `P-256` (not RSA keys).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// JWS algorithm, e.g. `EdDSA`, `ES256K`, `ES256`, `RS256` or `PS256`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// Public key use; always `sig`.
+    #[serde(rename = "use")]
+    pub use_: String,
+    /// Base64url x coordinate, or the Ed25519 public key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// Base64url y coordinate (EC keys only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// Base64url modulus (RSA keys only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// Base64url public exponent (RSA keys only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+impl Jwk {
+    /// Build a JWK for `public_key` with the given key ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if the key bytes are malformed.
+    pub fn from_public_key(kid: impl Into<String>, public_key: &PublicKey) -> Result<Self> {
+        let bytes = public_key.as_bytes();
+        let (kty, crv, x, y) = match public_key.algorithm() {
+            SigningAlgorithm::Ed25519
+            | SigningAlgorithm::Ed25519ph
+            | SigningAlgorithm::Ed25519ctx => {
+                if bytes.len() != 32 {
+                    return Err(POPSignerError::Decode(format!(
+                        "Ed25519 public key must be 32 bytes, got {}",
+                        bytes.len()
+                    )));
+                }
+                ("OKP", "Ed25519", BASE64URL.encode(bytes), None)
+            }
+            SigningAlgorithm::EcdsaSecp256k1 => {
+                let key = k256::PublicKey::from_sec1_bytes(bytes).map_err(|e| {
+                    POPSignerError::Decode(format!("invalid secp256k1 public key: {}", e))
+                })?;
+                let point = key.to_encoded_point(false);
+                let (x, y) = ec_coordinates(point.x(), point.y());
+                ("EC", "secp256k1", x, Some(y))
+            }
+            SigningAlgorithm::EcdsaP256 => {
+                let key = p256::PublicKey::from_sec1_bytes(bytes).map_err(|e| {
+                    POPSignerError::Decode(format!("invalid P-256 public key: {}", e))
+                })?;
+                let point = key.to_encoded_point(false);
+                let (x, y) = ec_coordinates(point.x(), point.y());
+                ("EC", "P-256", x, Some(y))
+            }
+            SigningAlgorithm::RsaPkcs1Sha256 | SigningAlgorithm::RsaPssSha256 => {
+                let (n, e) = public_key.rsa_components()?;
+                return Ok(Self {
+                    kid: kid.into(),
+                    kty: "RSA".to_string(),
+                    crv: None,
+                    alg: public_key.algorithm().jws_alg().map(str::to_string),
+                    use_: "sig".to_string(),
+                    x: None,
+                    y: None,
+                    n: Some(BASE64URL.encode(n)),
+                    e: Some(BASE64URL.encode(e)),
+                });
+            }
+        };
+
+        Ok(Self {
+            kid: kid.into(),
+            kty: kty.to_string(),
+            crv: Some(crv.to_string()),
+            alg: public_key.algorithm().jws_alg().map(str::to_string),
+            use_: "sig".to_string(),
+            x: Some(x),
+            y,
+            n: None,
+            e: None,
+        })
+    }
+}
+
+/// Base64url-encode uncompressed point coordinates.
+fn ec_coordinates<T: AsRef<[u8]>>(x: Option<T>, y: Option<T>) -> (String, String) {
+    // Uncompressed, non-identity points always carry both coordinates.
+    let encode = |c: Option<T>| c.map(|c| BASE64URL.encode(c)).unwrap_or_default();
+    (encode(x), encode(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_jwk() {
+        let key = PublicKey::from_hex(
+            SigningAlgorithm::Ed25519,
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        )
+        .unwrap();
+        let jwk = Jwk::from_public_key("key-1", &key).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&jwk).unwrap(),
+            serde_json::json!({
+                "kid": "key-1",
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "alg": "EdDSA",
+                "use": "sig",
+                "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+            })
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_jwk() {
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let point = signing_key.verifying_key().to_encoded_point(true);
+        let key = PublicKey::new(SigningAlgorithm::EcdsaSecp256k1, point.as_bytes());
+        let jwk = Jwk::from_public_key("key-2", &key).unwrap();
+
+        assert_eq!(jwk.kty, "EC");
+        assert_eq!(jwk.crv.as_deref(), Some("secp256k1"));
+        assert_eq!(BASE64URL.decode(jwk.x.unwrap()).unwrap().len(), 32);
+        assert_eq!(BASE64URL.decode(jwk.y.unwrap()).unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_rsa_jwk() {
+        // A 512-bit test key from `openssl genpkey`, as SPKI DER.
+        let key = PublicKey::from_base64(
+            SigningAlgorithm::RsaPssSha256,
+            "MFwwDQYJKoZIhvcNAQEBBQADSwAwSAJBAMTwV+GgwmTUN4ZxBYyGKFYFj6O6TVuY\
+             R8qzE8HWg5a9p8iSczoPRQ+V40D1NxTdn+uI00b04UMxb6tR03wb850CAwEAAQ==",
+        )
+        .unwrap();
+        let jwk = Jwk::from_public_key("key-3", &key).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&jwk).unwrap(),
+            serde_json::json!({
+                "kid": "key-3",
+                "kty": "RSA",
+                "alg": "PS256",
+                "use": "sig",
+                "n": "xPBX4aDCZNQ3hnEFjIYoVgWPo7pNW5hHyrMTwdaDlr2nyJJzOg9FD5XjQPU3FN2f64jTRvThQzFvq1HTfBvznQ",
+                "e": "AQAB"
+            })
+        );
+
+        let malformed = PublicKey::new(SigningAlgorithm::RsaPkcs1Sha256, vec![0u8; 64]);
+        let err = Jwk::from_public_key("key-4", &malformed).unwrap_err();
+        assert!(matches!(err, POPSignerError::Decode(_)));
+    }
+}
@@ -0,0 +1,171 @@
+//! JWT signing with POPSigner keys.
+//!
+//! [`SignClient::sign_jwt`](crate::sign::SignClient::sign_jwt) builds the
+//! JWS signing input, has the server sign it, and returns the compact
+//! serialization (`header.claims.signature`). The private key never leaves
+//! POPSigner; only the assembly happens locally.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::jwt::JwtHeader;
+//! use popsigner::{Client, KeyId};
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "00000000-0000-0000-0000-000000000001".parse()?;
+//!
+//! let claims = serde_json::json!({ "sub": "service-a", "exp": 1767225600 });
+//! let token = client.sign().sign_jwt(&key_id, JwtHeader::new(), claims).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{POPSignerError, Result};
+use crate::types::SigningAlgorithm;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A JOSE header for [`SignClient::sign_jwt`](crate::sign::SignClient::sign_jwt).
+///
+/// `alg` and `kid` are filled in from the signing key; set `alg` only to
+/// assert which algorithm you expect.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JwtHeader {
+    /// JWS algorithm. Must match the key's algorithm if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// Token type, `JWT` by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Key ID. Defaults to the POPSigner key ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// Additional header parameters, e.g. `cty`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl JwtHeader {
+    /// Create a header with `typ: "JWT"`.
+    pub fn new() -> Self {
+        Self {
+            typ: Some("JWT".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Set `alg` and a default `kid` for `key_id`, rejecting an `alg` that
+    /// does not match `algorithm`.
+    pub(crate) fn bind(mut self, key_id: &Uuid, algorithm: SigningAlgorithm) -> Result<Self> {
+        let alg = algorithm.jws_alg().ok_or_else(|| {
+            POPSignerError::InvalidRequest(format!("JWTs cannot be signed with {}", algorithm))
+        })?;
+        if let Some(requested) = self.alg.as_deref().filter(|requested| *requested != alg) {
+            return Err(POPSignerError::InvalidRequest(format!(
+                "JWT header alg {} does not match key algorithm {}",
+                requested, alg
+            )));
+        }
+
+        self.alg = Some(alg.to_string());
+        self.kid.get_or_insert_with(|| key_id.to_string());
+        Ok(self)
+    }
+}
+
+/// Build the JWS signing input `base64url(header).base64url(claims)`.
+pub(crate) fn signing_input(header: &JwtHeader, claims: &serde_json::Value) -> Result<String> {
+    Ok(format!(
+        "{}.{}",
+        encode_segment(header)?,
+        encode_segment(claims)?
+    ))
+}
+
+/// Append the base64url signature to a signing input.
+pub(crate) fn compact(signing_input: &str, signature: &[u8]) -> String {
+    format!("{}.{}", signing_input, BASE64URL.encode(signature))
+}
+
+fn encode_segment<T: Serialize>(value: &T) -> Result<String> {
+    let json = serde_json::to_vec(value).map_err(|e| {
+        POPSignerError::InvalidRequest(format!("cannot serialize JWT segment: {}", e))
+    })?;
+    Ok(BASE64URL.encode(json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::testing::MockTransport;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+
+    fn client_with_key(algorithm: &str) -> (Client, MockTransport) {
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": Uuid::from_u128(1),
+            "name": "issuer",
+            "namespace_id": Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": algorithm,
+            "exportable": false,
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        (client, mock)
+    }
+
+    #[test]
+    fn test_header_bind() {
+        let key_id = Uuid::from_u128(1);
+        let header = JwtHeader::new()
+            .bind(&key_id, SigningAlgorithm::EcdsaP256)
+            .unwrap();
+        assert_eq!(header.alg.as_deref(), Some("ES256"));
+        assert_eq!(header.kid, Some(key_id.to_string()));
+
+        let header = JwtHeader {
+            alg: Some("RS256".to_string()),
+            ..JwtHeader::new()
+        };
+        let err = header.bind(&key_id, SigningAlgorithm::Ed25519).unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_jwt() {
+        let (client, mock) = client_with_key("ed25519");
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk"
+        }));
+
+        let claims = serde_json::json!({ "sub": "alice" });
+        let token = client
+            .sign()
+            .sign_jwt(&Uuid::from_u128(1).into(), JwtHeader::new(), claims)
+            .await
+            .unwrap();
+
+        let parts: Vec<&str> = token.split('.').collect();
+        assert_eq!(parts.len(), 3);
+        let header: serde_json::Value =
+            serde_json::from_slice(&BASE64URL.decode(parts[0]).unwrap()).unwrap();
+        assert_eq!(header["alg"], "EdDSA");
+        assert_eq!(header["kid"], Uuid::from_u128(1).to_string());
+        assert_eq!(parts[2], "AQID");
+
+        // The server signed exactly the signing input.
+        let body = mock.last_request().unwrap().body_json().unwrap();
+        let signed = BASE64.decode(body["data"].as_str().unwrap()).unwrap();
+        assert_eq!(signed, format!("{}.{}", parts[0], parts[1]).into_bytes());
+    }
+}
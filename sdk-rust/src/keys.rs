@@ -1,13 +1,22 @@
 //! Key management operations.
 //!
 //! This module provides the KeysClient for creating, retrieving, listing,
-//! and deleting cryptographic keys.
+//! disabling and deleting cryptographic keys.
 
-use crate::client::Client;
-use crate::error::Result;
-use crate::types::{CreateBatchRequest, CreateKeyRequest, Key};
+use crate::client::{cancellable, encode_query_value, Client};
+use crate::error::{POPSignerError, Result};
+use crate::ids::{KeyId, NamespaceId};
+use crate::types::{
+    BatchResult, CreateBatchRequest, CreateKeyRequest, Key, KeySpec, KeyVersion, LabelSelector,
+    Page,
+};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use serde::Deserialize;
-use uuid::Uuid;
+use std::collections::BTreeMap;
+use tokio_util::sync::CancellationToken;
+
+/// Number of keys [`KeysClient::create_keys`] creates at a time.
+const CREATE_KEYS_CONCURRENCY: usize = 8;
 
 /// Client for key management operations.
 ///
@@ -30,13 +39,12 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::{Client, types::CreateKeyRequest};
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId, types::CreateKeyRequest};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     
     ///     let key = client.keys().create(CreateKeyRequest {
     ///         name: "my-key".to_string(),
@@ -44,12 +52,21 @@ impl KeysClient {
     ///         algorithm: Some("secp256k1".to_string()),
     ///         exportable: Some(false),
     ///         metadata: None,
+    ///         ..Default::default()
     ///     }).await?;
     ///     
     ///     println!("Created key: {} ({})", key.name, key.address);
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "keys.create",
+            skip_all,
+            fields(namespace_id = %request.namespace_id)
+        )
+    )]
     pub async fn create(&self, request: CreateKeyRequest) -> Result<Key> {
         self.client.post("/v1/keys", &request).await
     }
@@ -66,13 +83,12 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::{Client, types::CreateBatchRequest};
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId, types::CreateBatchRequest};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     
     ///     let keys = client.keys().create_batch(CreateBatchRequest {
     ///         prefix: "blob-worker".to_string(),
@@ -88,6 +104,14 @@ impl KeysClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "keys.create_batch",
+            skip_all,
+            fields(namespace_id = %request.namespace_id, count = request.count)
+        )
+    )]
     pub async fn create_batch(&self, request: CreateBatchRequest) -> Result<Vec<Key>> {
         #[derive(Deserialize)]
         struct Response {
@@ -98,6 +122,83 @@ impl KeysClient {
         Ok(response.keys)
     }
 
+    /// Create keys from individual specs, e.g. when provisioning a tenant.
+    ///
+    /// Unlike [`create_batch`](Self::create_batch), each key gets its own
+    /// name and settings. Keys are created a few at a time and the returned
+    /// [`BatchResult`] holds one result per spec, in input order, so a name
+    /// collision fails only that entry. Use [`BatchResult::into_result`] to
+    /// get a [`POPSignerError::BatchPartialFailure`] mapping indices to
+    /// errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeySpec, NamespaceId};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let namespace_id: NamespaceId = "...".parse()?;
+    ///
+    ///     let specs = vec![KeySpec::new("sequencer"), KeySpec::new("batcher")];
+    ///     let results = client.keys().create_keys(&namespace_id, specs).await;
+    ///     for (index, err) in results.failures() {
+    ///         println!("key {} failed: {}", index, err);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "keys.create_keys",
+            skip_all,
+            fields(namespace_id = %namespace_id, count = specs.len())
+        )
+    )]
+    pub async fn create_keys(
+        &self,
+        namespace_id: &NamespaceId,
+        specs: Vec<KeySpec>,
+    ) -> BatchResult<Key> {
+        self.create_each(namespace_id, specs, None).await
+    }
+
+    /// Like [`create_keys`](Self::create_keys), but stops when `cancel`
+    /// fires.
+    ///
+    /// Keys created before cancellation keep their results; the rest fail
+    /// with [`POPSignerError::Cancelled`].
+    pub async fn create_keys_with_cancel(
+        &self,
+        namespace_id: &NamespaceId,
+        specs: Vec<KeySpec>,
+        cancel: CancellationToken,
+    ) -> BatchResult<Key> {
+        self.create_each(namespace_id, specs, Some(&cancel)).await
+    }
+
+    async fn create_each(
+        &self,
+        namespace_id: &NamespaceId,
+        specs: Vec<KeySpec>,
+        cancel: Option<&CancellationToken>,
+    ) -> BatchResult<Key> {
+        let namespace_id = namespace_id.into_uuid();
+        let results = stream::iter(specs)
+            .map(|spec| {
+                cancellable(cancel, async move {
+                    self.create(spec.into_request(namespace_id)?).await
+                })
+            })
+            .buffered(CREATE_KEYS_CONCURRENCY)
+            .collect()
+            .await;
+
+        BatchResult { results }
+    }
+
     /// Get a key by ID.
     ///
     /// # Arguments
@@ -107,21 +208,29 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let key_id = Uuid::parse_str("...")?;
+    ///     let key_id: KeyId = "...".parse()?;
     ///     
     ///     let key = client.keys().get(&key_id).await?;
     ///     println!("Key: {} ({})", key.name, key.algorithm);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get(&self, key_id: &Uuid) -> Result<Key> {
-        self.client.get(&format!("/v1/keys/{}", key_id)).await
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.get", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn get(&self, key_id: &KeyId) -> Result<Key> {
+        if let Some(key) = self.client.cached_key(key_id) {
+            return Ok(key);
+        }
+        let result = self.client.get(&format!("/v1/keys/{}", key_id)).await;
+        self.client.observe_key(key_id, &result);
+        result
     }
 
     /// List all keys, optionally filtered by namespace.
@@ -133,8 +242,7 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -145,12 +253,16 @@ impl KeysClient {
     ///     println!("Total keys: {}", all_keys.len());
     ///     
     ///     // List keys in a specific namespace
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     let namespace_keys = client.keys().list(Some(&namespace_id)).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn list(&self, namespace_id: Option<&Uuid>) -> Result<Vec<Key>> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.list", skip_all, fields(namespace_id = ?namespace_id))
+    )]
+    pub async fn list(&self, namespace_id: Option<&NamespaceId>) -> Result<Vec<Key>> {
         let path = match namespace_id {
             Some(id) => format!("/v1/keys?namespace_id={}", id),
             None => "/v1/keys".to_string(),
@@ -158,6 +270,159 @@ impl KeysClient {
         self.client.get(&path).await
     }
 
+    /// Fetch a single page of keys, optionally filtered by namespace.
+    ///
+    /// Pass the `next_cursor` of the previous page to fetch the next one.
+    /// Use [`KeysClient::list_stream`] to iterate over every page instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace_id` - Optional namespace ID to filter by
+    /// * `cursor` - Cursor returned by the previous page, or `None` for the first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.list_page", skip_all, fields(namespace_id = ?namespace_id))
+    )]
+    pub async fn list_page(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        cursor: Option<&str>,
+    ) -> Result<Page<Key>> {
+        self.fetch_page(namespace_id, None, cursor).await
+    }
+
+    async fn fetch_page(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: Option<&LabelSelector>,
+        cursor: Option<&str>,
+    ) -> Result<Page<Key>> {
+        let mut params = Vec::new();
+        if let Some(id) = namespace_id {
+            params.push(format!("namespace_id={}", id));
+        }
+        if let Some(selector) = selector.filter(|selector| !selector.is_empty()) {
+            params.push(format!(
+                "label_selector={}",
+                encode_query_value(&selector.to_query())
+            ));
+        }
+        if let Some(cursor) = cursor {
+            params.push(format!("cursor={}", encode_query_value(cursor)));
+        }
+
+        let path = if params.is_empty() {
+            "/v1/keys".to_string()
+        } else {
+            format!("/v1/keys?{}", params.join("&"))
+        };
+        self.client.get_page(&path).await
+    }
+
+    /// Stream all keys, optionally filtered by namespace.
+    ///
+    /// Pages are fetched lazily as the stream is polled, until the server
+    /// returns no cursor. Each page request goes through the client's retry
+    /// policy; an error that survives it is yielded as an `Err` item and ends
+    /// the stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use popsigner::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///
+    ///     let mut keys = Box::pin(client.keys().list_stream(None));
+    ///     while let Some(key) = keys.try_next().await? {
+    ///         println!("{}", key.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_stream(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+    ) -> impl Stream<Item = Result<Key>> + Send + 'static {
+        self.stream(namespace_id, None)
+    }
+
+    /// List every key carrying all labels of `selector`, optionally
+    /// filtered by namespace.
+    ///
+    /// The selector is applied by the server. A selector that matches no
+    /// keys returns an empty list.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, LabelSelector};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///
+    ///     let selector = LabelSelector::new().label("env", "prod").label("team", "infra");
+    ///     let keys = client.keys().list_matching(None, &selector).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "keys.list_matching",
+            skip_all,
+            fields(namespace_id = ?namespace_id)
+        )
+    )]
+    pub async fn list_matching(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: &LabelSelector,
+    ) -> Result<Vec<Key>> {
+        self.list_stream_matching(namespace_id, selector)
+            .try_collect()
+            .await
+    }
+
+    /// Like [`list_stream`](Self::list_stream), but only yields keys
+    /// carrying all labels of `selector`.
+    pub fn list_stream_matching(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: &LabelSelector,
+    ) -> impl Stream<Item = Result<Key>> + Send + 'static {
+        self.stream(namespace_id, Some(selector.clone()))
+    }
+
+    fn stream(
+        &self,
+        namespace_id: Option<&NamespaceId>,
+        selector: Option<LabelSelector>,
+    ) -> impl Stream<Item = Result<Key>> + Send + 'static {
+        let client = self.client.clone();
+        let namespace_id = namespace_id.copied();
+
+        // State is the cursor of the next page to fetch; `None` once exhausted.
+        stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+            let keys = KeysClient::new(client.clone());
+            let selector = selector.clone();
+            async move {
+                let Some(cursor) = cursor else {
+                    return Ok(None);
+                };
+                keys.fetch_page(namespace_id.as_ref(), selector.as_ref(), cursor.as_deref())
+                    .await
+                    .map(|page| Some((page.items, page.next_cursor.map(Some))))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok::<_, POPSignerError>)))
+        .try_flatten()
+    }
+
     /// Delete a key.
     ///
     /// **Warning:** This operation is irreversible. The key and all associated
@@ -170,21 +435,113 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let key_id = Uuid::parse_str("...")?;
+    ///     let key_id: KeyId = "...".parse()?;
     ///     
     ///     client.keys().delete(&key_id).await?;
     ///     println!("Key deleted");
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete(&self, key_id: &Uuid) -> Result<()> {
-        self.client.delete(&format!("/v1/keys/{}", key_id)).await
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.delete", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn delete(&self, key_id: &KeyId) -> Result<()> {
+        self.client.delete(&format!("/v1/keys/{}", key_id)).await?;
+        self.client.evict_key(key_id);
+        Ok(())
+    }
+
+    /// Disable a key. Signing with it fails with
+    /// [`POPSignerError::KeyDisabled`] until it is enabled again.
+    ///
+    /// Returns the key in its new state.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, KeyState};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///
+    ///     let key = client.keys().disable(&key_id).await?;
+    ///     assert_eq!(key.state, KeyState::Disabled);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.disable", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn disable(&self, key_id: &KeyId) -> Result<Key> {
+        self.set_state(key_id, "disable").await
+    }
+
+    /// Enable a disabled key, returning the key in its new state.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.enable", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn enable(&self, key_id: &KeyId) -> Result<Key> {
+        self.set_state(key_id, "enable").await
+    }
+
+    /// List the versions of a key, oldest first.
+    ///
+    /// Sign or verify with an older version by setting
+    /// [`SignRequest::key_version`](crate::SignRequest::key_version).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.list_versions", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn list_versions(&self, key_id: &KeyId) -> Result<Vec<KeyVersion>> {
+        self.client
+            .get(&format!("/v1/keys/{}/versions", key_id))
+            .await
+    }
+
+    /// Replace the labels of a key.
+    ///
+    /// Labels not in `labels` are removed; pass an empty map to clear them.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "keys.set_labels", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn set_labels(
+        &self,
+        key_id: &KeyId,
+        labels: BTreeMap<String, String>,
+    ) -> Result<Key> {
+        let result = self
+            .client
+            .post(
+                &format!("/v1/keys/{}/labels", key_id),
+                &serde_json::json!({ "labels": labels }),
+            )
+            .await;
+        self.client.observe_key(key_id, &result);
+        result
+    }
+
+    async fn set_state(&self, key_id: &KeyId, action: &str) -> Result<Key> {
+        let result = self
+            .client
+            .post(
+                &format!("/v1/keys/{}/{}", key_id, action),
+                &serde_json::json!({}),
+            )
+            .await;
+        self.client.evict_verifications(key_id);
+        self.client.observe_key(key_id, &result);
+        result
     }
 
     /// Get a key by name within a namespace.
@@ -197,23 +554,35 @@ impl KeysClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     
     ///     let key = client.keys().get_by_name(&namespace_id, "my-key").await?;
     ///     println!("Key ID: {}", key.id);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_by_name(&self, namespace_id: &Uuid, name: &str) -> Result<Key> {
-        self.client
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "keys.get_by_name",
+            skip_all,
+            fields(namespace_id = %namespace_id, name = %name)
+        )
+    )]
+    pub async fn get_by_name(&self, namespace_id: &NamespaceId, name: &str) -> Result<Key> {
+        let result: Result<Key> = self
+            .client
             .get(&format!("/v1/keys/by-name/{}/{}", namespace_id, name))
-            .await
+            .await;
+        if let Ok(key) = &result {
+            self.client.observe_key(&key.id, &result);
+        }
+        result
     }
 }
 
@@ -227,4 +596,141 @@ mod tests {
         let _keys = client.keys();
         // Just verify it compiles and doesn't panic
     }
+
+    #[tokio::test]
+    async fn test_disable_key_then_sign() {
+        use crate::types::KeyState;
+
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": uuid::Uuid::nil(),
+            "name": "k",
+            "namespace_id": uuid::Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": "secp256k1",
+            "exportable": false,
+            "state": "disabled",
+            "created_at": "2025-01-01T00:00:00Z",
+            "rotated_at": "2025-06-01T00:00:00Z"
+        }));
+        let body = serde_json::json!({
+            "error": { "code": "key_disabled", "message": "key is disabled" }
+        });
+        mock.push_response(crate::transport::Response::json(409, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(uuid::Uuid::nil());
+        let key = client.keys().disable(&key_id).await.unwrap();
+        assert_eq!(key.state, KeyState::Disabled);
+        assert!(!key.state.can_sign());
+        assert_eq!(key.rotated_at.as_deref(), Some("2025-06-01T00:00:00Z"));
+        assert_eq!(
+            mock.requests()[0].url.path(),
+            format!("/v1/keys/{}/disable", key_id)
+        );
+
+        let err = client.sign().sign(&key_id, b"tx", false).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::KeyDisabled(id) if id == key_id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_create_keys_forwards_spec() {
+        use crate::types::{KeyUsage, SigningAlgorithm};
+
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": uuid::Uuid::nil(),
+            "name": "webauthn",
+            "namespace_id": uuid::Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": "secp256r1",
+            "exportable": false,
+            "usage": "authentication",
+            "description": "login key",
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let specs = vec![
+            KeySpec::new("webauthn")
+                .algorithm(SigningAlgorithm::EcdsaP256)
+                .usage(KeyUsage::Authentication)
+                .description("login key")
+                .label("env", "prod"),
+            KeySpec::new("bad").usage(KeyUsage::Authentication),
+        ];
+        let namespace_id = NamespaceId::from(uuid::Uuid::nil());
+        let results = client.keys().create_keys(&namespace_id, specs).await;
+
+        let key = results.results[0].as_ref().unwrap();
+        assert_eq!(key.usage, KeyUsage::Authentication);
+        assert_eq!(key.public_key, "pk");
+        assert!(matches!(
+            results.results[1],
+            Err(POPSignerError::InvalidRequest(_))
+        ));
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["algorithm"], "secp256r1");
+        assert_eq!(body["usage"], "authentication");
+        assert_eq!(body["description"], "login key");
+        assert_eq!(body["labels"]["env"], "prod");
+    }
+
+    #[tokio::test]
+    async fn test_label_selector_and_set_labels() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!([]));
+        mock.push_data(serde_json::json!({
+            "id": uuid::Uuid::nil(),
+            "name": "k",
+            "namespace_id": uuid::Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": "secp256k1",
+            "exportable": false,
+            "labels": { "env": "prod", "team": "infra" },
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let selector = LabelSelector::new()
+            .label("team", "infra")
+            .label("env", "prod");
+        let keys = client.keys().list_matching(None, &selector).await.unwrap();
+        assert!(keys.is_empty());
+        assert_eq!(
+            mock.requests()[0].url.query(),
+            Some("label_selector=env%3Dprod%2Cteam%3Dinfra")
+        );
+
+        let key_id = KeyId::from(uuid::Uuid::nil());
+        let labels = BTreeMap::from([
+            ("env".to_string(), "prod".to_string()),
+            ("team".to_string(), "infra".to_string()),
+        ]);
+        let key = client.keys().set_labels(&key_id, labels).await.unwrap();
+        assert!(selector.matches(&key));
+        assert_eq!(
+            mock.requests()[1].url.path(),
+            format!("/v1/keys/{}/labels", key_id)
+        );
+    }
 }
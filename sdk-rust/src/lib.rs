@@ -8,8 +8,7 @@
 //! ## Quick Start
 //!
 //! ```rust,no_run
-//! use popsigner::{Client, types::CreateKeyRequest};
-//! use uuid::Uuid;
+//! use popsigner::{Client, NamespaceId, types::CreateKeyRequest};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -17,7 +16,7 @@
 //!     let client = Client::new("psk_live_xxxxx");
 //!     
 //!     // Create a key
-//!     let namespace_id = Uuid::parse_str("...")?;
+//!     let namespace_id: NamespaceId = "...".parse()?;
 //!     let key = client.keys().create(CreateKeyRequest {
 //!         name: "my-sequencer".to_string(),
 //!         namespace_id,
@@ -40,13 +39,12 @@
 //! For high-throughput signing, use batch operations:
 //!
 //! ```rust,no_run
-//! use popsigner::{Client, types::{CreateBatchRequest, BatchSignRequest, BatchSignItem}};
-//! use uuid::Uuid;
+//! use popsigner::{Client, NamespaceId, types::{CreateBatchRequest, BatchSignRequest, BatchSignItem}};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let client = Client::new("psk_live_xxxxx");
-//!     let namespace_id = Uuid::parse_str("...")?;
+//!     let namespace_id: NamespaceId = "...".parse()?;
 //!     
 //!     // Create 4 worker keys
 //!     let keys = client.keys().create_batch(CreateBatchRequest {
@@ -67,7 +65,10 @@
 //!         }).collect(),
 //!     }).await?;
 //!     
-//!     // All 4 signed in parallel!
+//!     // All 4 signed in parallel! Failed items can be retried by index.
+//!     for (index, err) in results.failures() {
+//!         println!("item {} failed: {}", index, err);
+//!     }
 //!     Ok(())
 //! }
 //! ```
@@ -78,8 +79,13 @@
 //! - **Key Management**: Create, get, list, delete keys
 //! - **Batch Operations**: Create and sign in batches for parallel workers
 //! - **Signing**: Sign data with keys (single or batch)
+//! - **Local Verification**: Verify Ed25519 and ECDSA signatures offline
 //! - **Organizations**: Manage organizations and namespaces
 //! - **Audit Logs**: Access audit logs for compliance
+//! - **Retries**: Optional exponential backoff that honors `Retry-After`
+//! - **Tracing**: Request spans and events via `tracing` (`tracing` feature)
+//! - **Metrics**: Per-attempt hooks for your own exporter
+//! - **Blocking Client**: Synchronous facade for non-async programs (`blocking` feature)
 //!
 //! ## Error Handling
 //!
@@ -94,28 +100,79 @@
 //!     
 //!     match client.keys().list(None).await {
 //!         Ok(keys) => println!("Found {} keys", keys.len()),
-//!         Err(POPSignerError::Unauthorized) => println!("Invalid API key"),
-//!         Err(POPSignerError::RateLimited) => println!("Rate limited, retry later"),
+//!         Err(POPSignerError::Unauthorized { .. }) => println!("Invalid API key"),
+//!         Err(POPSignerError::RateLimited { retry_after, .. }) => {
+//!             println!("Rate limited, retry in {:?}", retry_after)
+//!         }
 //!         Err(e) => println!("Error: {}", e),
 //!     }
 //! }
 //! ```
 
+#[cfg(all(feature = "blocking", target_arch = "wasm32"))]
+compile_error!("the `blocking` feature needs a tokio runtime and is not supported on wasm32");
+
 pub mod audit;
+mod base64_bytes;
+pub mod batcher;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 pub mod celestia;
+mod circuit_breaker;
 pub mod client;
+mod compression;
+pub mod cose;
+pub mod crypto;
+pub mod envelope;
 pub mod error;
+pub mod ids;
+pub mod jobs;
+pub mod jwks;
+pub mod jwt;
 pub mod keys;
+pub mod merkle;
+pub mod metrics;
+pub mod namespace;
 pub mod orgs;
+pub mod pool;
+mod rate_limit;
+pub mod retry;
+mod runtime;
+pub mod sequence;
 pub mod sign;
+pub mod signer;
+pub mod testing;
+mod trace;
+pub mod transport;
 pub mod types;
+pub mod verify;
 
 // Re-export main types at the crate root
-pub use client::{Client, ClientConfig};
-pub use error::{POPSignerError, Result};
+pub use client::{API_VERSION, Auth, Client, ClientBuilder, ClientConfig};
+pub use crypto::{PublicKey, Signature};
+pub use envelope::SignedEnvelope;
+#[cfg(feature = "http")]
+pub use error::ErrorBody;
+pub use error::{ApiErrorCode, ErrorCategory, POPSignerError, QuotaInfo, Result};
+pub use ids::{KeyId, NamespaceId};
+pub use jwks::{Jwk, Jwks};
+pub use jwt::JwtHeader;
+pub use metrics::Metrics;
+pub use retry::{Jitter, RetryPolicy};
+/// The clock used for deadlines such as [`SignRequest::deadline`]:
+/// `std::time::Instant`, or `web_time::Instant` on `wasm32`.
+pub use runtime::Instant;
+pub use signer::Signer;
+pub use tokio_util::sync::CancellationToken;
 
 // Re-export types module for easy access
 pub use types::{
-    AuditLog, BatchSignItem, BatchSignRequest, CreateBatchRequest, CreateKeyRequest, Key,
-    ListAuditLogsQuery, Namespace, Organization, PaginatedResponse, SignResponse,
+    AuditLog, BatchOptions, BatchResult, BatchSignItem, BatchSignRequest, CostEstimate,
+    CreateBatchRequest, CreateKeyRequest, DigestAlgorithm, HealthStatus, Key, KeyEvent, KeySpec,
+    KeyState, KeyUsage, KeyVersion, LabelSelector, ListAuditLogsQuery, Namespace,
+    NamespaceSettings, Organization, Page, PaginatedResponse, Priority, PublicKeys, QuotaStatus,
+    ResponseMeta, SelfTestReport, SelfTestStage, ServerWarning, SignRequest, SignResponse,
+    SignatureEncoding, SignatureOptions, SigningAlgorithm, StageOutcome, Timed, ValidationIssue,
+    ValidationReport, VerifyRequest, VerifyRequestBuilder, VerifyResult,
 };
@@ -0,0 +1,256 @@
+//! Merkle trees over many leaves, committed to with one signature.
+//!
+//! [`NamespaceClient::sign_merkle_root`](crate::namespace::NamespaceClient::sign_merkle_root)
+//! builds a [`MerkleTree`] locally, signs its root and returns a
+//! [`MerkleProof`] per leaf, so each leaf can later be shown to be covered
+//! by the signature without revealing the others.
+//!
+//! # Hashing scheme
+//!
+//! The scheme follows RFC 6962 domain separation, so a leaf can never be
+//! passed off as an inner node:
+//!
+//! * a leaf hashes to `H(0x00 || leaf)`;
+//! * an inner node hashes to `H(0x01 || left || right)`;
+//! * when a level has an odd number of nodes, the last one moves up to the
+//!   next level unchanged instead of being paired with itself.
+//!
+//! Leaves keep the order they were given in, so the same leaves and hash
+//! always give the same root and proofs. SHA-256, SHA-384 and SHA-512 are
+//! supported; Keccak-256 is not.
+//!
+//! The root is signed as a message, not as a prehashed digest, so the
+//! signature verifies over the root bytes like any other signature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::{Client, DigestAlgorithm, KeyId, NamespaceId};
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let namespace_id: NamespaceId = "...".parse()?;
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! let leaves = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+//! let signed = client
+//!     .namespace(&namespace_id)
+//!     .sign_merkle_root(&key_id, leaves, DigestAlgorithm::Sha256)
+//!     .await?;
+//! assert!(signed.proofs[1].verify(b"b", &signed.root, signed.hash)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{POPSignerError, Result};
+use crate::types::{DigestAlgorithm, SignResponse};
+use serde::{Deserialize, Serialize};
+use sha2::digest::DynDigest;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Prefix of a leaf hash.
+const LEAF_PREFIX: u8 = 0x00;
+/// Prefix of an inner node hash.
+const NODE_PREFIX: u8 = 0x01;
+
+/// A Merkle tree built with the scheme described in the [module
+/// docs](self).
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    hash: DigestAlgorithm,
+    /// Node hashes, from the leaves (`levels[0]`) up to the root.
+    levels: Vec<Vec<Vec<u8>>>,
+}
+
+impl MerkleTree {
+    /// Build the tree over `leaves`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `leaves` is empty or
+    /// `hash` is [`DigestAlgorithm::Keccak256`].
+    pub fn new<T: AsRef<[u8]>>(leaves: &[T], hash: DigestAlgorithm) -> Result<Self> {
+        if leaves.is_empty() {
+            return Err(POPSignerError::InvalidRequest(
+                "a Merkle tree needs at least one leaf".to_string(),
+            ));
+        }
+        let mut level = leaves
+            .iter()
+            .map(|leaf| digest(hash, LEAF_PREFIX, &[leaf.as_ref()]))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut levels = Vec::new();
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => digest(hash, NODE_PREFIX, &[left, right]),
+                    [last] => Ok(last.clone()),
+                    _ => unreachable!(),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+        Ok(Self { hash, levels })
+    }
+
+    /// The hash the tree was built with.
+    pub fn hash(&self) -> DigestAlgorithm {
+        self.hash
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Always false: a tree has at least one leaf.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The root hash.
+    pub fn root(&self) -> &[u8] {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// The inclusion proof for the leaf at `index`, or `None` if there is
+    /// no such leaf.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if position % 2 == 1 {
+                path.push(Sibling::Left(level[position - 1].clone()));
+            } else if let Some(right) = level.get(position + 1) {
+                path.push(Sibling::Right(right.clone()));
+            }
+            position /= 2;
+        }
+        Some(MerkleProof { index, path })
+    }
+}
+
+/// The nodes needed to recompute the root from one leaf.
+///
+/// Serializes with serde, with hashes in base64, so proofs can be handed
+/// to whoever holds the leaf.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the leaf in the tree.
+    pub index: usize,
+    /// Sibling hashes from the leaf's level up to just below the root.
+    /// Levels where the node moved up unpaired have no entry.
+    pub path: Vec<Sibling>,
+}
+
+/// A sibling hash in a [`MerkleProof`], and which side it is on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sibling {
+    /// The sibling is the left input of the parent node.
+    Left(#[serde(with = "crate::base64_bytes")] Vec<u8>),
+    /// The sibling is the right input of the parent node.
+    Right(#[serde(with = "crate::base64_bytes")] Vec<u8>),
+}
+
+impl MerkleProof {
+    /// Recompute the root from `leaf` and this proof with `hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] for
+    /// [`DigestAlgorithm::Keccak256`].
+    pub fn root(&self, leaf: &[u8], hash: DigestAlgorithm) -> Result<Vec<u8>> {
+        let mut node = digest(hash, LEAF_PREFIX, &[leaf])?;
+        for sibling in &self.path {
+            node = match sibling {
+                Sibling::Left(left) => digest(hash, NODE_PREFIX, &[left, &node])?,
+                Sibling::Right(right) => digest(hash, NODE_PREFIX, &[&node, right])?,
+            };
+        }
+        Ok(node)
+    }
+
+    /// Returns true if `leaf` is included in the tree with root `root`.
+    ///
+    /// This checks the proof only; verify the root's signature separately,
+    /// e.g. with [`verify_signature`](crate::verify::verify_signature).
+    pub fn verify(&self, leaf: &[u8], root: &[u8], hash: DigestAlgorithm) -> Result<bool> {
+        Ok(self.root(leaf, hash)? == root)
+    }
+}
+
+/// A signed Merkle root with an inclusion proof for every leaf.
+#[derive(Debug, Clone)]
+pub struct MerkleSignature {
+    /// The root hash that was signed.
+    pub root: Vec<u8>,
+    /// The hash the tree was built with.
+    pub hash: DigestAlgorithm,
+    /// The signature over [`root`](Self::root).
+    pub signature: SignResponse,
+    /// Inclusion proofs, in leaf order.
+    pub proofs: Vec<MerkleProof>,
+}
+
+/// `hash(prefix || parts...)`.
+fn digest(hash: DigestAlgorithm, prefix: u8, parts: &[&[u8]]) -> Result<Vec<u8>> {
+    let mut hasher: Box<dyn DynDigest> = match hash {
+        DigestAlgorithm::Sha256 => Box::new(Sha256::new()),
+        DigestAlgorithm::Sha384 => Box::new(Sha384::new()),
+        DigestAlgorithm::Sha512 => Box::new(Sha512::new()),
+        DigestAlgorithm::Keccak256 => {
+            return Err(POPSignerError::InvalidRequest(format!(
+                "{} Merkle trees are not supported",
+                hash
+            )));
+        }
+    };
+    hasher.update(&[prefix]);
+    for part in parts {
+        hasher.update(part);
+    }
+    Ok(hasher.finalize().into_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proofs_recompute_root() {
+        let leaves: Vec<Vec<u8>> = (0u8..5).map(|i| vec![i]).collect();
+        let sha256 = DigestAlgorithm::Sha256;
+        let tree = MerkleTree::new(&leaves, sha256).unwrap();
+
+        // Five leaves: ((0 1) (2 3)) 4, with leaf 4 moving up unpaired.
+        let leaf = |i: u8| digest(sha256, LEAF_PREFIX, &[&[i]]).unwrap();
+        let node = |l: &[u8], r: &[u8]| digest(sha256, NODE_PREFIX, &[l, r]);
+        let left = node(&leaf(0), &leaf(1)).unwrap();
+        let right = node(&leaf(2), &leaf(3)).unwrap();
+        let root = node(&node(&left, &right).unwrap(), &leaf(4)).unwrap();
+        assert_eq!(tree.root(), root);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i).unwrap();
+            assert!(proof.verify(leaf, &root, sha256).unwrap());
+            assert!(!proof.verify(b"other", &root, sha256).unwrap());
+        }
+        assert_eq!(tree.proof(4).unwrap().path.len(), 1);
+        assert!(tree.proof(5).is_none());
+    }
+
+    #[test]
+    fn test_rejects_empty_and_keccak() {
+        let empty: [&[u8]; 0] = [];
+        assert!(MerkleTree::new(&empty, DigestAlgorithm::Sha256).is_err());
+        assert!(MerkleTree::new(&[b"a"], DigestAlgorithm::Keccak256).is_err());
+    }
+}
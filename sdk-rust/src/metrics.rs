@@ -0,0 +1,156 @@
+//! Metrics hooks.
+//!
+//! Implement [`Metrics`] and install it with
+//! [`ClientBuilder::metrics`](crate::ClientBuilder::metrics) to feed request
+//! counts, latencies and error rates into your own exporter, for example the
+//! `metrics` or `prometheus` crates. The SDK ships no exporter itself.
+//!
+//! Callbacks fire once per HTTP attempt, so retried requests produce several
+//! `on_request_start`/`on_request_end` pairs with an [`on_retry`] in between.
+//! Logical operations are the number of starts minus the number of retries.
+//!
+//! [`on_retry`]: Metrics::on_retry
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::metrics::Metrics;
+//! use popsigner::{Client, POPSignerError};
+//! use std::time::Duration;
+//!
+//! struct Printer;
+//!
+//! impl Metrics for Printer {
+//!     fn on_request_end(&self, path: &str, status: Option<u16>, elapsed: Duration) {
+//!         println!("{} -> {:?} in {:?}", path, status, elapsed);
+//!     }
+//!
+//!     fn on_retry(&self, path: &str, attempt: u32, error: &POPSignerError) {
+//!         println!("{} retry #{} after {}", path, attempt, error);
+//!     }
+//! }
+//!
+//! # fn main() -> popsigner::Result<()> {
+//! let client = Client::builder()
+//!     .api_key("psk_live_xxxxx")
+//!     .metrics(Printer)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::POPSignerError;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Callbacks invoked around every HTTP attempt.
+///
+/// All methods default to doing nothing. `path` is the request path without
+/// the base URL or query string, e.g. `/v1/keys/{id}/sign`. Callbacks run on
+/// the request's task, so keep them cheap.
+pub trait Metrics: Send + Sync {
+    /// An attempt is about to be sent.
+    fn on_request_start(&self, path: &str) {
+        let _ = path;
+    }
+
+    /// An attempt finished. `status` is `None` if no response was received,
+    /// e.g. on a timeout or connection error.
+    fn on_request_end(&self, path: &str, status: Option<u16>, elapsed: Duration) {
+        let _ = (path, status, elapsed);
+    }
+
+    /// Attempt number `attempt` failed with `error` and will be retried.
+    fn on_retry(&self, path: &str, attempt: u32, error: &POPSignerError) {
+        let _ = (path, attempt, error);
+    }
+}
+
+/// Lets callers keep a handle to the recorder they install.
+impl<M: Metrics + ?Sized> Metrics for Arc<M> {
+    fn on_request_start(&self, path: &str) {
+        (**self).on_request_start(path);
+    }
+
+    fn on_request_end(&self, path: &str, status: Option<u16>, elapsed: Duration) {
+        (**self).on_request_end(path, status, elapsed);
+    }
+
+    fn on_retry(&self, path: &str, attempt: u32, error: &POPSignerError) {
+        (**self).on_retry(path, attempt, error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::retry::{Jitter, RetryPolicy};
+    use crate::testing::MockTransport;
+    use crate::transport::Response;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Metrics for Recorder {
+        fn on_request_start(&self, path: &str) {
+            self.events.lock().unwrap().push(format!("start {}", path));
+        }
+
+        fn on_request_end(&self, _path: &str, status: Option<u16>, _elapsed: Duration) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("end {:?}", status));
+        }
+
+        fn on_retry(&self, _path: &str, attempt: u32, _error: &POPSignerError) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("retry {}", attempt));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_attempts() {
+        let mock = MockTransport::new();
+        mock.push_error(POPSignerError::Timeout);
+        mock.push_response(Response::json(503, &serde_json::json!({})));
+        mock.push_data(serde_json::json!([]));
+
+        let recorder = Arc::new(Recorder::default());
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .retry(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+                jitter: Jitter::None,
+            })
+            .metrics(recorder.clone())
+            .build()
+            .unwrap();
+
+        client.keys().list(None).await.unwrap();
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                "start /v1/keys",
+                "end None",
+                "retry 1",
+                "start /v1/keys",
+                "end Some(503)",
+                "retry 2",
+                "start /v1/keys",
+                "end Some(200)",
+            ]
+        );
+    }
+}
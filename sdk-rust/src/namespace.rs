@@ -0,0 +1,796 @@
+//! Namespace-scoped operations.
+//!
+//! This module provides the NamespaceClient, a handle bound to a single
+//! namespace so the namespace ID does not have to be passed to every call.
+
+use crate::client::{is_key_not_found, Client};
+use crate::cose::{self, CborMap, CborValue, HEADER_ALG, HEADER_KID};
+use crate::crypto::{PublicKey, Signature};
+use crate::error::{POPSignerError, Result};
+use crate::ids::{KeyId, NamespaceId};
+use crate::jwks::{Jwk, Jwks};
+use crate::merkle::{MerkleSignature, MerkleTree};
+use crate::runtime::Instant;
+use crate::trace;
+use crate::types::{
+    DigestAlgorithm, Key, KeyVersion, LabelSelector, PublicKeys, SelfTestReport, SignRequest,
+    SignResponse, SignatureEncoding, SigningAlgorithm,
+};
+use crate::verify::verify_signature;
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Payload signed by [`NamespaceClient::self_test`], marked so it cannot be
+/// mistaken for real data.
+pub const SELF_TEST_PAYLOAD: &[u8] = b"POPSIGNER SDK SELF-TEST - NOT A TRANSACTION";
+
+/// Request header marking [`NamespaceClient::self_test`] calls.
+const SELF_TEST_HEADER: &str = "x-self-test";
+
+/// Client for operations within a single namespace.
+///
+/// Access via `client.namespace(&namespace_id)`. The handle shares the
+/// underlying client's connection pool and state, so it is cheap to create
+/// and clone. Errors are the same as for the base client; an unknown
+/// namespace surfaces as an [`ApiErrorCode::NamespaceNotFound`] API error.
+///
+/// [`ApiErrorCode::NamespaceNotFound`]: crate::ApiErrorCode::NamespaceNotFound
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use popsigner::{Client, NamespaceId};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::new("psk_live_xxxxx");
+///     let namespace_id: NamespaceId = "...".parse()?;
+///     let production = client.namespace(&namespace_id);
+///
+///     let keys = production.list_keys().await?;
+///     let result = production.sign("sequencer", b"tx data", false).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct NamespaceClient {
+    client: Client,
+    namespace_id: NamespaceId,
+}
+
+impl NamespaceClient {
+    pub(crate) fn new(client: Client, namespace_id: NamespaceId) -> Self {
+        Self {
+            client,
+            namespace_id,
+        }
+    }
+
+    /// The namespace this handle is bound to.
+    pub fn namespace_id(&self) -> &NamespaceId {
+        &self.namespace_id
+    }
+
+    /// List all keys in the namespace.
+    pub async fn list_keys(&self) -> Result<Vec<Key>> {
+        self.client.keys().list(Some(&self.namespace_id)).await
+    }
+
+    /// Stream all keys in the namespace, fetching pages lazily.
+    ///
+    /// See [`KeysClient::list_stream`](crate::keys::KeysClient::list_stream).
+    pub fn list_keys_stream(&self) -> impl Stream<Item = Result<Key>> + Send + 'static {
+        self.client.keys().list_stream(Some(&self.namespace_id))
+    }
+
+    /// List the keys in the namespace carrying all labels of `selector`.
+    ///
+    /// See [`KeysClient::list_matching`](crate::keys::KeysClient::list_matching).
+    pub async fn list_keys_matching(&self, selector: &LabelSelector) -> Result<Vec<Key>> {
+        self.client
+            .keys()
+            .list_matching(Some(&self.namespace_id), selector)
+            .await
+    }
+
+    /// Stream the keys in the namespace carrying all labels of `selector`.
+    pub fn list_keys_stream_matching(
+        &self,
+        selector: &LabelSelector,
+    ) -> impl Stream<Item = Result<Key>> + Send + 'static {
+        self.client
+            .keys()
+            .list_stream_matching(Some(&self.namespace_id), selector)
+    }
+
+    /// Get a key in the namespace by name.
+    pub async fn get_key(&self, name: &str) -> Result<Key> {
+        self.client
+            .keys()
+            .get_by_name(&self.namespace_id, name)
+            .await
+    }
+
+    /// Sign data with the named key in the namespace.
+    ///
+    /// The key is looked up by name first, which costs an extra request. If
+    /// you already have the key ID, use [`Client::sign`] directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_name` - Name of the key within the namespace
+    /// * `data` - The data to sign
+    /// * `prehashed` - If true, data is already hashed (SHA-256)
+    pub async fn sign(&self, key_name: &str, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+        let key = self.get_key(key_name).await?;
+        self.client.sign().sign(&key.id, data, prehashed).await
+    }
+
+    /// Sign `message` with `key_id` and verify the signature locally
+    /// before returning it.
+    ///
+    /// An opt-in integrity check over [`Client::sign`] for flows that
+    /// persist signatures. The public key comes from
+    /// [`KeysClient::get`](crate::keys::KeysClient::get), so it is served
+    /// from the key cache when one is configured; if the signature does not
+    /// verify against a cached key, the key is fetched again once in case it
+    /// was rotated.
+    ///
+    /// # Errors
+    ///
+    /// * [`POPSignerError::VerificationFailed`] if the signature does not
+    ///   verify.
+    /// * [`POPSignerError::KeyNotFound`] if the key does not exist or lives
+    ///   in another namespace.
+    /// * [`POPSignerError::SigningError`] for algorithms that cannot be
+    ///   verified locally (RSA, Ed25519ph, Ed25519ctx).
+    pub async fn sign_verified(&self, key_id: &KeyId, message: &[u8]) -> Result<SignResponse> {
+        let cached = self.client.cached_key(key_id).is_some();
+        let key = self.member(key_id).await?;
+        let response = self
+            .client
+            .sign()
+            .sign(key_id, message, false)
+            .await
+            .map_err(|e| not_found(key_id, e))?;
+
+        if verifies(&key, message, &response)? {
+            return Ok(response);
+        }
+        if cached {
+            self.client.evict_key(key_id);
+            let key = self.member(key_id).await?;
+            if verifies(&key, message, &response)? {
+                return Ok(response);
+            }
+        }
+        Err(POPSignerError::VerificationFailed(key_id.to_string()))
+    }
+
+    /// Sign `message` with both the outgoing and the incoming key during a
+    /// rotation, returning the signatures in that order.
+    ///
+    /// Both keys must belong to this namespace. The API has no dual-sign
+    /// endpoint, and a batch reports per-item failures only as text, so the
+    /// two keys are signed with concurrently in separate calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::KeyNotFound`] with the ID of the key that
+    /// does not exist or lives in another namespace.
+    pub async fn sign_dual(
+        &self,
+        old_key: &KeyId,
+        new_key: &KeyId,
+        message: &[u8],
+    ) -> Result<(SignResponse, SignResponse)> {
+        futures::try_join!(
+            self.sign_with_member(old_key, message),
+            self.sign_with_member(new_key, message),
+        )
+    }
+
+    /// Build a Merkle tree over `leaves` with `hash`, sign its root with
+    /// `key_id` and return the root, the signature and an inclusion proof
+    /// for each leaf.
+    ///
+    /// The tree is built locally, so only the root is sent. See the
+    /// [`merkle`](crate::merkle) module for the hashing scheme.
+    ///
+    /// # Errors
+    ///
+    /// * [`POPSignerError::InvalidRequest`] if `leaves` is empty or `hash`
+    ///   is [`DigestAlgorithm::Keccak256`].
+    /// * [`POPSignerError::KeyNotFound`] if the key does not exist or lives
+    ///   in another namespace.
+    pub async fn sign_merkle_root(
+        &self,
+        key_id: &KeyId,
+        leaves: Vec<Vec<u8>>,
+        hash: DigestAlgorithm,
+    ) -> Result<MerkleSignature> {
+        let tree = MerkleTree::new(&leaves, hash)?;
+        let signature = self.sign_with_member(key_id, tree.root()).await?;
+        Ok(MerkleSignature {
+            root: tree.root().to_vec(),
+            hash,
+            signature,
+            proofs: (0..tree.len()).filter_map(|i| tree.proof(i)).collect(),
+        })
+    }
+
+    /// Sign `payload` as a COSE_Sign1 message (RFC 8152) and return its
+    /// tagged CBOR encoding.
+    ///
+    /// The `alg` header is set in `protected` from the key's algorithm and
+    /// the key ID is sent as the unprotected `kid`. See the
+    /// [`cose`](crate::cose) module for decoding the result.
+    ///
+    /// # Errors
+    ///
+    /// * [`POPSignerError::InvalidRequest`] if `protected` already has an
+    ///   `alg` that does not match the key, or COSE defines no `alg` for
+    ///   the key's algorithm.
+    /// * [`POPSignerError::KeyNotFound`] if the key does not exist or lives
+    ///   in another namespace.
+    pub async fn sign_cose_sign1(
+        &self,
+        key_id: &KeyId,
+        mut protected: CborMap,
+        payload: &[u8],
+    ) -> Result<Vec<u8>> {
+        let key = self.member(key_id).await?;
+        let algorithm: SigningAlgorithm = key.algorithm.parse()?;
+        let alg = algorithm.cose_alg().ok_or_else(|| {
+            POPSignerError::InvalidRequest(format!(
+                "COSE_Sign1 messages cannot be signed with {}",
+                algorithm
+            ))
+        })?;
+        match protected.insert(HEADER_ALG, alg) {
+            Some(CborValue::Integer(existing)) if existing == alg => {}
+            Some(existing) => {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "COSE alg {:?} does not match key algorithm {}",
+                    existing, algorithm
+                )));
+            }
+            None => {}
+        }
+
+        let protected = cose::encode_header(&protected);
+        let to_be_signed = cose::sig_structure(&protected, payload);
+        let request = SignRequest::new(*key_id, to_be_signed).algorithm(algorithm);
+        let response = self
+            .client
+            .sign()
+            .sign_request(&request)
+            .await
+            .map_err(|e| not_found(key_id, e))?;
+        let signature = if algorithm.is_ecdsa() {
+            response.to_encoding(SignatureEncoding::Raw)?
+        } else {
+            response.signature
+        };
+
+        let mut header = CborMap::new();
+        header.insert(HEADER_KID, key_id.to_string().into_bytes());
+        Ok(cose::encode_sign1(&protected, header, payload, signature))
+    }
+
+    /// Check end-to-end health with `key_id`, e.g. from a troubleshooting
+    /// runbook.
+    ///
+    /// Runs four stages, each timed in the report: `auth` fetches the key,
+    /// `dry_run` validates a sign request without signing, `sign` signs
+    /// [`SELF_TEST_PAYLOAD`] and `verify` checks the signature and that it
+    /// came from the expected key, locally where the algorithm allows and
+    /// with the server otherwise. Once a stage fails the rest are skipped.
+    ///
+    /// The sign stage makes one real signature, counted against your quota.
+    /// Its payload is clearly marked and the request carries an
+    /// `X-Self-Test` header; whether the server keeps such calls out of
+    /// audit logs depends on the deployment.
+    ///
+    /// # Errors
+    ///
+    /// Stage failures are reported in the [`SelfTestReport`], not as
+    /// errors; check [`SelfTestReport::passed`].
+    pub async fn self_test(&self, key_id: &KeyId) -> Result<SelfTestReport> {
+        let mut report = SelfTestReport {
+            key_id: *key_id,
+            stages: Vec::new(),
+        };
+        let request = SignRequest::new(*key_id, SELF_TEST_PAYLOAD).header(SELF_TEST_HEADER, "true");
+
+        let start = Instant::now();
+        let Some(key) = report.record(start, self.member(key_id).await) else {
+            return Ok(report.finish());
+        };
+
+        let start = Instant::now();
+        let validation = self.client.sign().validate(&request).await.and_then(|v| {
+            if v.is_valid() {
+                return Ok(());
+            }
+            let codes: Vec<_> = v.issues.iter().map(|i| i.code.as_str()).collect();
+            Err(POPSignerError::InvalidRequest(format!(
+                "dry run found issues: {}",
+                codes.join(", ")
+            )))
+        });
+        if report.record(start, validation).is_none() {
+            return Ok(report.finish());
+        }
+
+        let start = Instant::now();
+        let signed = self.client.sign().sign_request(&request).await;
+        let Some(response) = report.record(start, signed) else {
+            return Ok(report.finish());
+        };
+
+        let start = Instant::now();
+        let verified = self.verify_self_test(&key, &response).await;
+        report.record(start, verified);
+        Ok(report.finish())
+    }
+
+    /// The round-trip checks of [`self_test`](Self::self_test).
+    async fn verify_self_test(&self, key: &Key, response: &SignResponse) -> Result<()> {
+        if response.key_id != key.id {
+            return Err(POPSignerError::SigningError(format!(
+                "signed with key {}, expected {}",
+                response.key_id, key.id
+            )));
+        }
+        let valid = match verifies(key, SELF_TEST_PAYLOAD, response) {
+            // Not verifiable locally, e.g. RSA.
+            Err(POPSignerError::SigningError(_)) => {
+                self.client
+                    .sign()
+                    .verify(&key.id, SELF_TEST_PAYLOAD, &response.signature, false)
+                    .await?
+            }
+            result => result?,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(POPSignerError::VerificationFailed(key.id.to_string()))
+        }
+    }
+
+    /// Fetch the public keys of `key_ids` in one request, e.g. to warm a
+    /// local verification cache at startup instead of fetching each key.
+    ///
+    /// IDs that do not exist or live in another namespace are listed in
+    /// [`PublicKeys::missing`] rather than failing the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if a returned public key is
+    /// malformed.
+    pub async fn get_public_keys(&self, key_ids: &[KeyId]) -> Result<PublicKeys> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            key_ids: &'a [KeyId],
+        }
+        #[derive(Deserialize)]
+        struct Entry {
+            key_id: KeyId,
+            algorithm: String,
+            public_key: String,
+        }
+        #[derive(Deserialize)]
+        struct Reply {
+            keys: Vec<Entry>,
+        }
+
+        if key_ids.is_empty() {
+            return Ok(PublicKeys::default());
+        }
+        let path = format!("/v1/namespaces/{}/public-keys", self.namespace_id);
+        let reply: Reply = self.client.post(&path, &Request { key_ids }).await?;
+
+        let mut keys = HashMap::new();
+        for entry in reply.keys {
+            let public_key = PublicKey::from_base64(entry.algorithm.parse()?, &entry.public_key)?;
+            keys.insert(entry.key_id, public_key);
+        }
+        let mut seen = HashSet::new();
+        let missing = key_ids
+            .iter()
+            .filter(|id| !keys.contains_key(*id) && seen.insert(**id))
+            .copied()
+            .collect();
+        Ok(PublicKeys { keys, missing })
+    }
+
+    /// List the versions of a key in the namespace, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::KeyNotFound`] if the key does not exist or
+    /// lives in another namespace.
+    pub async fn list_key_versions(&self, key_id: &KeyId) -> Result<Vec<KeyVersion>> {
+        self.member(key_id).await?;
+        self.client
+            .keys()
+            .list_versions(key_id)
+            .await
+            .map_err(|e| not_found(key_id, e))
+    }
+
+    /// Replace the labels of a key in the namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::KeyNotFound`] if the key does not exist or
+    /// lives in another namespace.
+    pub async fn set_key_labels(
+        &self,
+        key_id: &KeyId,
+        labels: BTreeMap<String, String>,
+    ) -> Result<Key> {
+        self.member(key_id).await?;
+        self.client
+            .keys()
+            .set_labels(key_id, labels)
+            .await
+            .map_err(|e| not_found(key_id, e))
+    }
+
+    /// Sign with `key_id` after checking that it belongs to the namespace.
+    async fn sign_with_member(&self, key_id: &KeyId, message: &[u8]) -> Result<SignResponse> {
+        self.member(key_id).await?;
+        self.client
+            .sign()
+            .sign(key_id, message, false)
+            .await
+            .map_err(|e| not_found(key_id, e))
+    }
+
+    /// Fetch `key_id`, failing with [`POPSignerError::KeyNotFound`] unless
+    /// it exists and belongs to the namespace.
+    async fn member(&self, key_id: &KeyId) -> Result<Key> {
+        let key = self
+            .client
+            .keys()
+            .get(key_id)
+            .await
+            .map_err(|e| not_found(key_id, e))?;
+        if key.namespace_id != self.namespace_id {
+            return Err(POPSignerError::KeyNotFound(key_id.to_string()));
+        }
+        Ok(key)
+    }
+
+    /// Export the namespace's public keys as a JSON Web Key Set.
+    ///
+    /// Each key's ID becomes its `kid`. Keys whose public key cannot be
+    /// decoded are left out with a warning and listed in [`Jwks::skipped`]
+    /// instead of failing the export.
+    pub async fn export_jwks(&self) -> Result<Jwks> {
+        let mut jwks = Jwks::default();
+        for key in self.list_keys().await? {
+            let jwk = key
+                .to_public_key()
+                .and_then(|public_key| Jwk::from_public_key(key.id.to_string(), &public_key));
+            match jwk {
+                Ok(jwk) => jwks.keys.push(jwk),
+                Err(e) => {
+                    trace::skipped_jwk(&key.id, &e);
+                    jwks.skipped.push(key.id);
+                }
+            }
+        }
+        Ok(jwks)
+    }
+}
+
+/// Map a key-not-found error to [`POPSignerError::KeyNotFound`] for `key_id`.
+fn not_found(key_id: &KeyId, e: POPSignerError) -> POPSignerError {
+    if is_key_not_found(&e) {
+        POPSignerError::KeyNotFound(key_id.to_string())
+    } else {
+        e
+    }
+}
+
+/// Returns true if `response` holds a valid signature of `message` by `key`.
+fn verifies(key: &Key, message: &[u8], response: &SignResponse) -> Result<bool> {
+    let signature = Signature::new(response.signature.clone());
+    verify_signature(&key.to_public_key()?, message, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_namespace_client_creation() {
+        let client = Client::new("test_key");
+        let namespace_id = NamespaceId::from(Uuid::new_v4());
+        let namespace = client.namespace(&namespace_id);
+        assert_eq!(namespace.namespace_id(), &namespace_id);
+    }
+
+    #[tokio::test]
+    async fn test_sign_dual_rejects_foreign_key() {
+        let mock = MockTransport::new();
+        let key = |id: u128, namespace_id: Uuid| {
+            serde_json::json!({
+                "id": Uuid::from_u128(id),
+                "name": format!("key-{}", id),
+                "namespace_id": namespace_id,
+                "public_key": "pk",
+                "address": "addr",
+                "algorithm": "ed25519",
+                "exportable": false,
+                "created_at": "2025-01-01T00:00:00Z"
+            })
+        };
+        // The mock answers immediately, so the old key finishes first.
+        mock.push_data(key(1, Uuid::nil()));
+        mock.push_data(serde_json::json!({ "signature": "AQ==", "public_key": "pk" }));
+        mock.push_data(key(2, Uuid::from_u128(9)));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let old_key = KeyId::from(Uuid::from_u128(1));
+        let new_key = KeyId::from(Uuid::from_u128(2));
+        let err = client
+            .namespace(&Uuid::nil().into())
+            .sign_dual(&old_key, &new_key, b"tx")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, POPSignerError::KeyNotFound(id) if id == new_key.to_string()));
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_key_versions() {
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": Uuid::from_u128(1),
+            "name": "key-1",
+            "namespace_id": Uuid::nil(),
+            "public_key": "pk2",
+            "address": "addr",
+            "algorithm": "ed25519",
+            "exportable": false,
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        mock.push_data(serde_json::json!([
+            {
+                "version": 1,
+                "state": "disabled",
+                "public_key": "pk1",
+                "created_at": "2025-01-01T00:00:00Z"
+            },
+            { "version": 2, "public_key": "pk2", "created_at": "2025-06-01T00:00:00Z" }
+        ]));
+        let body = serde_json::json!({
+            "error": { "code": "key_version_not_found", "message": "no such version" }
+        });
+        mock.push_response(crate::transport::Response::json(404, &body));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let key_id = KeyId::from(Uuid::from_u128(1));
+        let versions = client
+            .namespace(&Uuid::nil().into())
+            .list_key_versions(&key_id)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].state, crate::types::KeyState::Disabled);
+        assert_eq!(versions[1].state, crate::types::KeyState::Active);
+
+        let request = crate::types::SignRequest::new(key_id, b"tx".to_vec()).key_version(7);
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(
+            err,
+            POPSignerError::KeyVersionNotFound { version: 7, .. }
+        ));
+        assert!(err.to_string().contains(&key_id.to_string()));
+        let sent = mock.requests()[2].body.clone().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&sent).unwrap();
+        assert_eq!(body["key_version"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_export_jwks_skips_malformed_keys() {
+        let mock = MockTransport::new();
+        let key = |id: u128, algorithm: &str, public_key: &str| {
+            serde_json::json!({
+                "id": Uuid::from_u128(id),
+                "name": format!("key-{}", id),
+                "namespace_id": Uuid::nil(),
+                "public_key": public_key,
+                "address": "addr",
+                "algorithm": algorithm,
+                "exportable": false,
+                "created_at": "2025-01-01T00:00:00Z"
+            })
+        };
+        mock.push_data(serde_json::json!([
+            key(1, "ed25519", "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo="),
+            key(2, "rsa-pss-sha256", "AAAA"),
+            key(
+                3,
+                "rsa-pkcs1-sha256",
+                "MFwwDQYJKoZIhvcNAQEBBQADSwAwSAJBAMTwV+GgwmTUN4ZxBYyGKFYFj6O6TVuY\
+                 R8qzE8HWg5a9p8iSczoPRQ+V40D1NxTdn+uI00b04UMxb6tR03wb850CAwEAAQ==",
+            ),
+        ]));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .build()
+            .unwrap();
+        let jwks = client
+            .namespace(&Uuid::nil().into())
+            .export_jwks()
+            .await
+            .unwrap();
+
+        assert_eq!(jwks.keys.len(), 2);
+        assert_eq!(jwks.keys[0].kid, Uuid::from_u128(1).to_string());
+        assert_eq!(jwks.keys[1].kty, "RSA");
+        assert_eq!(jwks.keys[1].alg.as_deref(), Some("RS256"));
+        assert_eq!(jwks.skipped, vec![Uuid::from_u128(2)]);
+        let json = serde_json::to_value(&jwks).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_public_keys_reports_missing() {
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "keys": [{
+                "key_id": Uuid::from_u128(1),
+                "algorithm": "ed25519",
+                "public_key": "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo="
+            }]
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let ids: Vec<KeyId> = [1, 2, 2].map(|i| Uuid::from_u128(i).into()).to_vec();
+        let public_keys = client
+            .namespace(&Uuid::nil().into())
+            .get_public_keys(&ids)
+            .await
+            .unwrap();
+        assert_eq!(public_keys.keys[&ids[0]].as_bytes()[0], 0xd7);
+        assert_eq!(public_keys.missing, vec![ids[1]]);
+
+        let sent = mock.requests()[0].body.clone().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&sent).unwrap();
+        assert_eq!(body["key_ids"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_sign_verified() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+        // RFC 8032 test 1: the empty message.
+        let public_key =
+            hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap();
+        let signature = hex::decode("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b").unwrap();
+        let key = serde_json::json!({
+            "id": Uuid::nil(),
+            "name": "key",
+            "namespace_id": Uuid::nil(),
+            "public_key": BASE64.encode(&public_key),
+            "address": "addr",
+            "algorithm": "ed25519",
+            "exportable": false,
+            "created_at": "2025-01-01T00:00:00Z"
+        });
+        let mut corrupted = signature.clone();
+        corrupted[0] ^= 1;
+
+        let mock = MockTransport::new();
+        for signature in [&signature, &corrupted] {
+            mock.push_data(key.clone());
+            mock.push_data(serde_json::json!({
+                "signature": BASE64.encode(signature),
+                "public_key": "pk"
+            }));
+        }
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let namespace = client.namespace(&Uuid::nil().into());
+        let key_id = KeyId::from(Uuid::nil());
+
+        let response = namespace.sign_verified(&key_id, b"").await.unwrap();
+        assert_eq!(response.signature, signature);
+
+        let err = namespace.sign_verified(&key_id, b"").await.unwrap_err();
+        assert!(matches!(err, POPSignerError::VerificationFailed(id) if id == key_id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_self_test() {
+        use crate::types::StageOutcome;
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+        use ed25519_dalek::Signer;
+
+        // RFC 8032 test 1 secret key.
+        let secret = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+        let secret = hex::decode(secret).unwrap();
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret.try_into().unwrap());
+        let signature = signing_key.sign(SELF_TEST_PAYLOAD).to_bytes();
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": Uuid::nil(),
+            "name": "key",
+            "namespace_id": Uuid::nil(),
+            "public_key": BASE64.encode(signing_key.verifying_key().as_bytes()),
+            "address": "addr",
+            "algorithm": "ed25519",
+            "exportable": false,
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        mock.push_data(serde_json::json!({ "issues": [] }));
+        mock.push_data(serde_json::json!({
+            "key_id": Uuid::nil(),
+            "signature": BASE64.encode(signature),
+            "public_key": "pk"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(Uuid::nil());
+        let report = client
+            .namespace(&Uuid::nil().into())
+            .self_test(&key_id)
+            .await
+            .unwrap();
+        assert!(report.passed(), "{:?}", report.failure());
+        let names: Vec<_> = report.stages.iter().map(|stage| stage.name).collect();
+        assert_eq!(names, SelfTestReport::STAGES);
+
+        let requests = mock.requests();
+        let dry_run: serde_json::Value =
+            serde_json::from_slice(requests[1].body.as_ref().unwrap()).unwrap();
+        assert_eq!(dry_run["dry_run"], true);
+        assert_eq!(requests[2].headers[SELF_TEST_HEADER], "true");
+
+        // A missing key fails the first stage and skips the rest.
+        let body = serde_json::json!({
+            "error": { "code": "key_not_found", "message": "no such key" }
+        });
+        mock.push_response(crate::transport::Response::json(404, &body));
+        let report = client
+            .namespace(&Uuid::nil().into())
+            .self_test(&key_id)
+            .await
+            .unwrap();
+        assert_eq!(report.failure().unwrap().name, "auth");
+        assert_eq!(report.stages[3].outcome, StageOutcome::Skipped);
+    }
+}
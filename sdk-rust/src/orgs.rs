@@ -3,8 +3,9 @@
 //! This module provides the OrgsClient for managing organizations and namespaces.
 
 use crate::client::Client;
-use crate::error::Result;
-use crate::types::{Namespace, Organization};
+use crate::error::{POPSignerError, Result};
+use crate::ids::NamespaceId;
+use crate::types::{Namespace, Organization, QuotaStatus};
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -36,10 +37,54 @@ impl OrgsClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "orgs.get_current", skip_all)
+    )]
     pub async fn get_current(&self) -> Result<Organization> {
         self.client.get("/v1/org").await
     }
 
+    /// Get the organization's signing quota usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `org_id` - The organization's unique identifier
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::OrgNotFound`] if the organization does not
+    /// exist or is not visible to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let org = client.orgs().get_current().await?;
+    ///
+    ///     let quota = client.orgs().get_quota(&org.id).await?;
+    ///     println!("{} left ({:.0}% used)", quota.remaining(), quota.fraction_used() * 100.0);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "orgs.get_quota", skip_all, fields(org_id = %org_id))
+    )]
+    pub async fn get_quota(&self, org_id: &Uuid) -> Result<QuotaStatus> {
+        self.client
+            .get(&format!("/v1/organizations/{}/quota", org_id))
+            .await
+            .map_err(|e| match e.status_code() {
+                Some(404) => POPSignerError::OrgNotFound(org_id.to_string()),
+                _ => e,
+            })
+    }
+
     /// List all namespaces in the organization.
     ///
     /// # Example
@@ -58,6 +103,10 @@ impl OrgsClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "orgs.list_namespaces", skip_all)
+    )]
     pub async fn list_namespaces(&self) -> Result<Vec<Namespace>> {
         self.client.get("/v1/namespaces").await
     }
@@ -71,20 +120,27 @@ impl OrgsClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     
     ///     let ns = client.orgs().get_namespace(&namespace_id).await?;
     ///     println!("Namespace: {}", ns.name);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_namespace(&self, namespace_id: &Uuid) -> Result<Namespace> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "orgs.get_namespace",
+            skip_all,
+            fields(namespace_id = %namespace_id)
+        )
+    )]
+    pub async fn get_namespace(&self, namespace_id: &NamespaceId) -> Result<Namespace> {
         self.client
             .get(&format!("/v1/namespaces/{}", namespace_id))
             .await
@@ -110,6 +166,10 @@ impl OrgsClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "orgs.create_namespace", skip_all, fields(name = %name))
+    )]
     pub async fn create_namespace(&self, name: &str) -> Result<Namespace> {
         #[derive(Serialize)]
         struct Request<'a> {
@@ -132,20 +192,27 @@ impl OrgsClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, NamespaceId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let namespace_id = Uuid::parse_str("...")?;
+    ///     let namespace_id: NamespaceId = "...".parse()?;
     ///     
     ///     client.orgs().delete_namespace(&namespace_id).await?;
     ///     println!("Namespace deleted");
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_namespace(&self, namespace_id: &Uuid) -> Result<()> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "orgs.delete_namespace",
+            skip_all,
+            fields(namespace_id = %namespace_id)
+        )
+    )]
+    pub async fn delete_namespace(&self, namespace_id: &NamespaceId) -> Result<()> {
         self.client
             .delete(&format!("/v1/namespaces/{}", namespace_id))
             .await
@@ -162,4 +229,23 @@ mod tests {
         let _orgs = client.orgs();
         // Just verify it compiles and doesn't panic
     }
+
+    #[tokio::test]
+    async fn test_get_quota_org_not_found() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_response(crate::transport::Response::json(
+            404,
+            &serde_json::json!({
+                "error": { "code": "org_not_found", "message": "organization not found" }
+            }),
+        ));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock)
+            .build()
+            .unwrap();
+
+        let err = client.orgs().get_quota(&Uuid::nil()).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::OrgNotFound(id) if id == Uuid::nil().to_string()));
+    }
 }
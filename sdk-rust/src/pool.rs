@@ -0,0 +1,190 @@
+//! Deterministic assignment of shards to a pool of equivalent keys.
+//!
+//! A [`KeyPool`] places its keys on a consistent-hashing ring, so the same
+//! shard key (e.g. a tenant ID) always maps to the same signing key, and
+//! adding or removing a key only moves the shards that key gains or loses.
+//!
+//! Each key is placed on the ring at a fixed number of points, all derived
+//! from SHA-256, so every process with the same keys agrees on the
+//! assignment.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::pool::KeyPool;
+//! use popsigner::{Client, KeyId};
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_ids: Vec<KeyId> = vec!["...".parse()?, "...".parse()?];
+//!
+//! let pool = KeyPool::new(client, key_ids)?;
+//! let response = pool.sign_for(b"tenant-42", b"tx").await?;
+//! assert_eq!(response.key_id, pool.key_for(b"tenant-42"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::types::SignResponse;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Points each key takes on the ring; more points spread shards more
+/// evenly.
+const POINTS_PER_KEY: u32 = 128;
+
+/// A pool of interchangeable keys with consistent-hash shard assignment.
+#[derive(Debug, Clone)]
+pub struct KeyPool {
+    client: Client,
+    /// Ring position to the key owning it.
+    ring: BTreeMap<u64, KeyId>,
+    keys: Vec<KeyId>,
+}
+
+impl KeyPool {
+    /// Create a pool over `key_ids`. Duplicates are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `key_ids` is empty.
+    pub fn new(client: Client, key_ids: impl IntoIterator<Item = KeyId>) -> Result<Self> {
+        let mut pool = Self {
+            client,
+            ring: BTreeMap::new(),
+            keys: Vec::new(),
+        };
+        for key_id in key_ids {
+            pool.add(key_id);
+        }
+        if pool.keys.is_empty() {
+            return Err(POPSignerError::InvalidRequest(
+                "a key pool needs at least one key".to_string(),
+            ));
+        }
+        Ok(pool)
+    }
+
+    /// The keys in the pool, in the order they were added.
+    pub fn keys(&self) -> &[KeyId] {
+        &self.keys
+    }
+
+    /// Add `key_id` to the pool. Returns false if it was already there.
+    ///
+    /// Only shards that land on the new key's points move to it.
+    pub fn add(&mut self, key_id: KeyId) -> bool {
+        if self.keys.contains(&key_id) {
+            return false;
+        }
+        for point in points(&key_id) {
+            self.ring.insert(point, key_id);
+        }
+        self.keys.push(key_id);
+        true
+    }
+
+    /// Remove `key_id` from the pool. Returns false if it was not there.
+    ///
+    /// Only the removed key's shards move, spread over the remaining keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `key_id` is the last
+    /// key in the pool.
+    pub fn remove(&mut self, key_id: &KeyId) -> Result<bool> {
+        if !self.keys.contains(key_id) {
+            return Ok(false);
+        }
+        if self.keys.len() == 1 {
+            return Err(POPSignerError::InvalidRequest(
+                "cannot remove the last key from a key pool".to_string(),
+            ));
+        }
+        self.keys.retain(|k| k != key_id);
+        self.ring.retain(|_, k| k != key_id);
+        Ok(true)
+    }
+
+    /// The key assigned to `shard_key`: the owner of the first ring point
+    /// at or after the shard's hash, wrapping around.
+    pub fn key_for(&self, shard_key: &[u8]) -> KeyId {
+        let position = hash(&[shard_key]);
+        let (_, key_id) = self
+            .ring
+            .range(position..)
+            .next()
+            .or_else(|| self.ring.iter().next())
+            .expect("a key pool is never empty");
+        *key_id
+    }
+
+    /// Sign `message` with the key assigned to `shard_key`.
+    pub async fn sign_for(&self, shard_key: &[u8], message: &[u8]) -> Result<SignResponse> {
+        let key_id = self.key_for(shard_key);
+        self.client.sign().sign(&key_id, message, false).await
+    }
+}
+
+/// The ring points of `key_id`.
+fn points(key_id: &KeyId) -> impl Iterator<Item = u64> + '_ {
+    (0..POINTS_PER_KEY).map(move |i| hash(&[key_id.as_uuid().as_bytes(), &i.to_be_bytes()]))
+}
+
+/// The first 8 bytes of `SHA-256(parts...)` as a ring position.
+fn hash(parts: &[&[u8]]) -> u64 {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let digest = hasher.finalize();
+    u64::from_be_bytes(digest[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn key(i: u128) -> KeyId {
+        Uuid::from_u128(i).into()
+    }
+
+    #[test]
+    fn test_adding_a_key_only_moves_shards_to_it() {
+        let client = Client::new("test_key");
+        let mut pool = KeyPool::new(client, [key(1), key(2), key(3)]).unwrap();
+        let shards: Vec<Vec<u8>> = (0..1000).map(|i| format!("tenant-{}", i).into()).collect();
+        let before: Vec<_> = shards.iter().map(|s| pool.key_for(s)).collect();
+        assert!(before.contains(&key(1)) && before.contains(&key(2)));
+
+        assert!(pool.add(key(4)));
+        let mut moved = 0;
+        for (shard, old) in shards.iter().zip(&before) {
+            let new = pool.key_for(shard);
+            if new != *old {
+                assert_eq!(new, key(4));
+                moved += 1;
+            }
+        }
+        // About a quarter of the shards should move to the new key.
+        assert!((100..400).contains(&moved), "moved {}", moved);
+
+        assert!(pool.remove(&key(4)).unwrap());
+        let after: Vec<_> = shards.iter().map(|s| pool.key_for(s)).collect();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn test_rejects_empty_pool() {
+        let client = Client::new("test_key");
+        assert!(KeyPool::new(client.clone(), []).is_err());
+
+        let mut pool = KeyPool::new(client, [key(1), key(1)]).unwrap();
+        assert_eq!(pool.keys(), [key(1)]);
+        assert!(pool.remove(&key(1)).is_err());
+    }
+}
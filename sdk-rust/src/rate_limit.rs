@@ -0,0 +1,142 @@
+//! Client-side rate limiting.
+//!
+//! A token bucket shared by every clone of a [`Client`](crate::Client), so
+//! cloned handles draw from the same budget. Waiting requests get permits
+//! in [`Priority`] order: a permit is only taken if enough are left for
+//! every waiter of higher priority.
+
+use crate::runtime::{self, Instant};
+use crate::types::Priority;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Token-bucket rate limiter.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    /// Tokens added per second.
+    rate: f64,
+    /// Maximum number of tokens the bucket can hold.
+    burst: f64,
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+    /// Number of callers waiting at each priority, by [`Priority::rank`].
+    waiting: [usize; 3],
+}
+
+/// Counts a caller as waiting until dropped, so a cancelled `acquire`
+/// does not hold back lower priorities.
+struct Waiting<'a> {
+    limiter: &'a RateLimiter,
+    rank: usize,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.limiter.state.lock().unwrap().waiting[self.rank] -= 1;
+    }
+}
+
+impl RateLimiter {
+    /// Create a limiter that starts with a full bucket.
+    pub(crate) fn new(permits_per_second: u32, burst: u32) -> Self {
+        Self {
+            rate: f64::from(permits_per_second),
+            burst: f64::from(burst),
+            state: Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                last_refill: Instant::now(),
+                waiting: [0; 3],
+            }),
+        }
+    }
+
+    /// Wait until a permit is available at `priority` and take it.
+    pub(crate) async fn acquire(&self, priority: Priority) {
+        let rank = priority.rank();
+        self.state.lock().unwrap().waiting[rank] += 1;
+        let _waiting = Waiting {
+            limiter: self,
+            rank,
+        };
+        loop {
+            let wait = {
+                let mut bucket = self.state.lock().unwrap();
+                self.refill(&mut bucket);
+
+                // Leave a permit for each waiter of higher priority.
+                let needed = (bucket.waiting[..rank].iter().sum::<usize>() + 1) as f64;
+                if bucket.tokens >= needed {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((needed - bucket.tokens) / self.rate)
+            };
+
+            runtime::sleep(wait).await;
+        }
+    }
+
+    /// Number of permits that can be taken right now without waiting.
+    pub(crate) fn available_permits(&self) -> u32 {
+        let mut bucket = self.state.lock().unwrap();
+        self.refill(&mut bucket);
+        bucket.tokens as u32
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_burst_is_available_immediately() {
+        let limiter = RateLimiter::new(1, 3);
+        assert_eq!(limiter.available_permits(), 3);
+
+        limiter.acquire(Priority::Normal).await;
+        limiter.acquire(Priority::Normal).await;
+        assert_eq!(limiter.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_when_empty() {
+        let limiter = RateLimiter::new(20, 1);
+        limiter.acquire(Priority::Normal).await;
+        assert_eq!(limiter.available_permits(), 0);
+
+        let start = Instant::now();
+        limiter.acquire(Priority::Normal).await;
+        // One token every 50ms.
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_goes_first() {
+        let limiter = RateLimiter::new(20, 1);
+        limiter.acquire(Priority::Normal).await;
+
+        // The low-priority caller starts waiting first but is served last.
+        let order = Mutex::new(Vec::new());
+        let take = |priority| {
+            let (limiter, order) = (&limiter, &order);
+            async move {
+                limiter.acquire(priority).await;
+                order.lock().unwrap().push(priority);
+            }
+        };
+        futures::join!(take(Priority::Low), take(Priority::High));
+        assert_eq!(*order.lock().unwrap(), [Priority::High, Priority::Low]);
+    }
+}
@@ -0,0 +1,232 @@
+//! Retry policies for transient failures.
+//!
+//! Configure a policy with [`ClientBuilder::retry`](crate::ClientBuilder::retry).
+//! Every request made by the client is then retried on errors where
+//! [`POPSignerError::is_retryable`](crate::POPSignerError::is_retryable)
+//! returns true. A server-provided `Retry-After` always takes precedence over
+//! the computed backoff.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How much randomness to apply to the computed backoff.
+///
+/// The strategies follow the AWS Architecture Blog's "Exponential Backoff
+/// And Jitter". `backoff` below is `min(max_delay, base_delay * 2^(attempt - 1))`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Sleep exactly `backoff`.
+    None,
+    /// Sleep a random duration in `[0, backoff)`.
+    #[default]
+    Full,
+    /// Sleep `backoff / 2` plus a random duration in `[0, backoff / 2)`.
+    Equal,
+    /// Sleep a random duration in `[base_delay, previous * 3)`, capped at
+    /// `max_delay`, where `previous` is the last sleep.
+    Decorrelated,
+}
+
+/// Retry behavior for failed requests.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use popsigner::{Client, retry::{Jitter, RetryPolicy}};
+/// use std::time::Duration;
+///
+/// # fn main() -> popsigner::Result<()> {
+/// let client = Client::builder()
+///     .api_key("psk_live_xxxxx")
+///     .retry(RetryPolicy {
+///         max_attempts: 5,
+///         base_delay: Duration::from_millis(200),
+///         max_delay: Duration::from_secs(5),
+///         jitter: Jitter::Full,
+///     })
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubles on each subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound on the backoff between attempts.
+    pub max_delay: Duration,
+    /// Randomization applied to the backoff.
+    pub jitter: Jitter,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: Jitter::None,
+        }
+    }
+
+    /// Backoff to wait after the given failed attempt (1-based).
+    ///
+    /// [`Jitter::Decorrelated`] depends on the previous sleep; here it is
+    /// taken to be the un-jittered backoff of the previous attempt. Use
+    /// [`backoff_with`](Self::backoff_with) to pass the real one.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let previous = match attempt {
+            0 | 1 => self.base_delay,
+            _ => self.exponential(attempt - 1),
+        };
+        self.backoff_with(attempt, previous, &mut random_unit)
+    }
+
+    /// Backoff to wait after the given failed attempt (1-based), given the
+    /// previous sleep and a source of uniform random values in `[0, 1)`.
+    ///
+    /// A fixed `random` makes the result deterministic, e.g. in tests.
+    pub fn backoff_with(
+        &self,
+        attempt: u32,
+        previous: Duration,
+        random: &mut dyn FnMut() -> f64,
+    ) -> Duration {
+        let delay = self.exponential(attempt);
+        let mut unit = || random().clamp(0.0, 1.0);
+
+        match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => delay.mul_f64(unit()),
+            Jitter::Equal => {
+                let half = delay / 2;
+                half + (delay - half).mul_f64(unit())
+            }
+            Jitter::Decorrelated => {
+                let low = self.base_delay;
+                let high = previous.saturating_mul(3).max(low);
+                (low + (high - low).mul_f64(unit())).min(self.max_delay)
+            }
+        }
+    }
+
+    /// `base_delay * 2^(attempt - 1)`, capped at `max_delay`.
+    fn exponential(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with full jitter, starting at 100ms and capped at 10s.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: Jitter::Full,
+        }
+    }
+}
+
+/// A random value in `[0, 1)`.
+pub(crate) fn random_unit() -> f64 {
+    // RandomState is seeded randomly per instance, which is plenty for jitter.
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_without_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            jitter: Jitter::None,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_backoff_full_jitter_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: Jitter::Full,
+            ..RetryPolicy::default()
+        };
+        for attempt in 1..10 {
+            let cap = policy
+                .base_delay
+                .saturating_mul(1 << (attempt - 1))
+                .min(policy.max_delay);
+            assert!(policy.backoff(attempt) <= cap);
+        }
+    }
+
+    #[test]
+    fn test_backoff_strategies_within_bounds() {
+        let policy = |jitter| RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter,
+        };
+        let previous = Duration::from_millis(200);
+
+        // Attempt 3: backoff is 400ms.
+        for unit in [0.0, 0.5, 0.999_999] {
+            let mut random = || unit;
+            let full = policy(Jitter::Full).backoff_with(3, previous, &mut random);
+            assert!(full < Duration::from_millis(400));
+
+            let equal = policy(Jitter::Equal).backoff_with(3, previous, &mut random);
+            assert!(equal >= Duration::from_millis(200) && equal < Duration::from_millis(400));
+
+            let decorrelated = policy(Jitter::Decorrelated).backoff_with(3, previous, &mut random);
+            assert!(
+                decorrelated >= Duration::from_millis(100)
+                    && decorrelated < Duration::from_millis(600)
+            );
+        }
+
+        let mut random = || 0.5;
+        assert_eq!(
+            policy(Jitter::Equal).backoff_with(3, previous, &mut random),
+            Duration::from_millis(300)
+        );
+        assert_eq!(
+            policy(Jitter::Decorrelated).backoff_with(3, previous, &mut random),
+            Duration::from_millis(350)
+        );
+        // Decorrelated growth is still capped.
+        assert_eq!(
+            policy(Jitter::Decorrelated).backoff_with(9, Duration::from_secs(5), &mut random),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_jitter_default_is_full() {
+        assert_eq!(Jitter::default(), Jitter::Full);
+        assert_eq!(RetryPolicy::default().jitter, Jitter::Full);
+    }
+
+    #[test]
+    fn test_none_policy() {
+        let policy = RetryPolicy::none();
+        assert_eq!(policy.max_attempts, 1);
+        assert_eq!(policy.backoff(1), Duration::ZERO);
+    }
+}
@@ -0,0 +1,35 @@
+//! Clock and timer shims for native and `wasm32` targets.
+//!
+//! On `wasm32-unknown-unknown`, `std::time::Instant::now` and
+//! `SystemTime::now` panic and there is no tokio timer, so the browser's
+//! clock and event loop are used instead.
+
+use std::time::{Duration, SystemTime};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+/// Wait for `duration` without blocking the executor.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// The current wall-clock time.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// The current wall-clock time.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now() -> SystemTime {
+    let since_epoch = web_time::SystemTime::now()
+        .duration_since(web_time::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    SystemTime::UNIX_EPOCH + since_epoch
+}
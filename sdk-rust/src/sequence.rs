@@ -0,0 +1,209 @@
+//! Signing over a monotonically increasing sequence number.
+//!
+//! A [`SequencedSigner`] hands out each sequence number exactly once, even
+//! when shared between tasks, so payloads that embed a counter or nonce are
+//! never signed twice with the same value.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::sequence::SequencedSigner;
+//! use popsigner::{Client, KeyId};
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "...".parse()?;
+//!
+//! let signer = SequencedSigner::new(client, key_id, 0).rollback_on_failure(true);
+//! let (sequence, response) = signer
+//!     .sign_next(|n| format!("transfer 10 #{}", n).into_bytes())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::{POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::types::SignResponse;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Signs payloads built from a shared, atomically reserved counter.
+///
+/// Clones share the counter.
+#[derive(Debug, Clone)]
+pub struct SequencedSigner {
+    client: Client,
+    key_id: KeyId,
+    next: Arc<AtomicU64>,
+    rollback_on_failure: bool,
+}
+
+impl SequencedSigner {
+    /// Create a signer whose first sequence number is `start`.
+    pub fn new(client: Client, key_id: KeyId, start: u64) -> Self {
+        Self {
+            client,
+            key_id,
+            next: Arc::new(AtomicU64::new(start)),
+            rollback_on_failure: false,
+        }
+    }
+
+    /// Release the sequence number of a failed signing call so the next
+    /// call reuses it (default: leave a gap).
+    ///
+    /// Only failures that prove nothing was signed release the number:
+    /// errors raised before the request was sent (validation, an open
+    /// circuit breaker, a shut-down client) and definitive 4xx rejections.
+    /// After a timeout, connection failure, 5xx or exhausted retries the
+    /// server may already have signed the payload, so a gap is left.
+    ///
+    /// The number is also only released if no later number has been
+    /// reserved in the meantime; otherwise a gap is left, so a number is
+    /// never handed out twice while another call may still be using it.
+    pub fn rollback_on_failure(mut self, rollback: bool) -> Self {
+        self.rollback_on_failure = rollback;
+        self
+    }
+
+    /// The key the signer signs with.
+    pub fn key_id(&self) -> &KeyId {
+        &self.key_id
+    }
+
+    /// The sequence number the next call will reserve, if no other call
+    /// reserves it first.
+    pub fn next_sequence(&self) -> u64 {
+        self.next.load(Ordering::SeqCst)
+    }
+
+    /// Reserve the next sequence number, build the payload from it with
+    /// `payload_fn` and sign it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the counter is
+    /// exhausted, and any error from signing.
+    pub async fn sign_next(
+        &self,
+        payload_fn: impl Fn(u64) -> Vec<u8>,
+    ) -> Result<(u64, SignResponse)> {
+        let sequence = self
+            .next
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_add(1))
+            .map_err(|_| POPSignerError::InvalidRequest("sequence exhausted".to_string()))?;
+
+        let payload = payload_fn(sequence);
+        match self.client.sign().sign(&self.key_id, &payload, false).await {
+            Ok(response) => Ok((sequence, response)),
+            Err(e) => {
+                if self.rollback_on_failure && never_signed(&e) {
+                    let _ = self.next.compare_exchange(
+                        sequence + 1,
+                        sequence,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Returns true if `err` proves the payload was never signed, so its
+/// sequence number can be handed out again.
+fn never_signed(err: &POPSignerError) -> bool {
+    match err {
+        // Raised locally before sending, including by a shut-down client.
+        POPSignerError::InvalidRequest(_) | POPSignerError::CircuitOpen { .. } => true,
+        POPSignerError::Unauthorized { .. }
+        | POPSignerError::RateLimited { .. }
+        | POPSignerError::QuotaExceeded { .. }
+        | POPSignerError::KeyNotFound(_)
+        | POPSignerError::KeyDisabled(_)
+        | POPSignerError::KeyVersionNotFound { .. }
+        | POPSignerError::NamespaceNotFound(_) => true,
+        // 408 means the server gave up waiting, not that it refused.
+        POPSignerError::Api { status_code, .. } => {
+            (400..500).contains(status_code) && *status_code != 408
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+    use crate::transport::Response;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use uuid::Uuid;
+
+    fn signature() -> serde_json::Value {
+        serde_json::json!({ "signature": BASE64.encode([1, 2]), "public_key": "pk" })
+    }
+
+    #[tokio::test]
+    async fn test_sign_next_rolls_back_or_leaves_gap() {
+        let mock = MockTransport::new();
+        let failure = serde_json::json!({
+            "error": { "code": "bad_request", "message": "rejected" }
+        });
+        mock.push_data(signature());
+        mock.push_response(Response::json(400, &failure));
+        mock.push_data(signature());
+        mock.push_response(Response::json(400, &failure));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let signer = SequencedSigner::new(client, Uuid::nil().into(), 5).rollback_on_failure(true);
+        let payload = |n: u64| n.to_be_bytes().to_vec();
+        assert_eq!(signer.sign_next(payload).await.unwrap().0, 5);
+        assert!(signer.sign_next(payload).await.is_err());
+        assert_eq!(signer.sign_next(payload).await.unwrap().0, 6);
+
+        let signer = signer.rollback_on_failure(false);
+        assert!(signer.sign_next(payload).await.is_err());
+        assert_eq!(signer.next_sequence(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_failures_leave_gap() {
+        let mock = MockTransport::new();
+        mock.push_error(POPSignerError::Timeout);
+        mock.push_response(Response::json(
+            503,
+            &serde_json::json!({
+                "error": { "code": "service_unavailable", "message": "try later" }
+            }),
+        ));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let signer = SequencedSigner::new(client, Uuid::nil().into(), 5).rollback_on_failure(true);
+        let payload = |n: u64| n.to_be_bytes().to_vec();
+        let err = signer.sign_next(payload).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::Timeout));
+        assert_eq!(signer.next_sequence(), 6);
+        let err = signer.sign_next(payload).await.unwrap_err();
+        assert_eq!(err.status_code(), Some(503));
+        assert_eq!(signer.next_sequence(), 7);
+
+        let exhausted = POPSignerError::RetriesExhausted {
+            attempts: 3,
+            last: Box::new(POPSignerError::Connect("reset".to_string())),
+        };
+        assert!(!never_signed(&exhausted));
+        let unauthorized = POPSignerError::Unauthorized { request_id: None };
+        assert!(never_signed(&unauthorized));
+    }
+}
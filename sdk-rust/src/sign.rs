@@ -3,12 +3,45 @@
 //! This module provides the SignClient for signing data with keys stored
 //! in POPSigner. Supports both single and batch signing operations.
 
-use crate::client::Client;
-use crate::error::{POPSignerError, Result};
-use crate::types::{BatchSignRequest, SignResponse};
+use crate::cache::Verification;
+use crate::client::{
+    cancellable, custom_headers, encode_query_value, is_key_not_found, Client, RequestOptions,
+};
+use crate::envelope::SignedEnvelope;
+use crate::error::{ApiErrorCode, POPSignerError, Result};
+use crate::ids::KeyId;
+use crate::jobs::{SignJobHandle, SignJobStatus};
+use crate::jwt::{self, JwtHeader};
+use crate::trace;
+use crate::types::{
+    BatchOptions, BatchResult, BatchSignItem, DigestAlgorithm, SignRequest, SignResponse,
+    SignatureEncoding, SignatureOptions, SigningAlgorithm, Timed, ValidationIssue,
+    ValidationReport, VerifyRequest, VerifyResult,
+};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::HeaderValue;
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use sha2::digest::DynDigest;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::sync::CancellationToken;
+
+/// Bytes read per chunk by [`SignClient::sign_stream_prehashed`].
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Largest chunk uploaded per call by [`SignClient::sign_stream`].
+const UPLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Longest signing context RFC 8032 allows.
+const MAX_CONTEXT_LEN: usize = 255;
+
+/// Length of the SHA-512 prehash signed by Ed25519ph.
+const ED25519PH_PREHASH_LEN: usize = 64;
 
 /// Client for signing operations.
 ///
@@ -33,13 +66,12 @@ impl SignClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let key_id = Uuid::parse_str("...")?;
+    ///     let key_id: KeyId = "...".parse()?;
     ///     
     ///     // Sign raw data (will be hashed by the server)
     ///     let data = b"hello world";
@@ -54,14 +86,13 @@ impl SignClient {
     /// # Example with prehashed data
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId};
     /// use sha2::{Sha256, Digest};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let key_id = Uuid::parse_str("...")?;
+    ///     let key_id: KeyId = "...".parse()?;
     ///     
     ///     // Hash data locally (requires sha2 crate)
     ///     let data = b"hello world";
@@ -72,38 +103,670 @@ impl SignClient {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn sign(&self, key_id: &Uuid, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+    pub async fn sign(&self, key_id: &KeyId, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+        self.sign_request(&SignRequest::new(*key_id, data).prehashed(prehashed))
+            .await
+    }
+
+    /// Sign a digest computed locally.
+    ///
+    /// The digest is sent as-is and signed directly by the server, so large
+    /// messages never have to be uploaded. Use [`SignClient::sign`] for raw
+    /// messages that the server should hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - The key ID to sign with
+    /// * `digest` - The message digest
+    /// * `algorithm` - The hash function that produced `digest`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the digest length does
+    /// not match `algorithm`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, DigestAlgorithm, KeyId};
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///
+    ///     let digest = Sha256::digest(b"a very large message");
+    ///     let result = client
+    ///         .sign()
+    ///         .sign_digest(&key_id, &digest, DigestAlgorithm::Sha256)
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_digest",
+            skip_all,
+            fields(key_id = %key_id, algorithm = %algorithm)
+        )
+    )]
+    pub async fn sign_digest(
+        &self,
+        key_id: &KeyId,
+        digest: &[u8],
+        algorithm: DigestAlgorithm,
+    ) -> Result<SignResponse> {
+        self.sign_request(&SignRequest::new(*key_id, digest).digest_algorithm(algorithm))
+            .await
+    }
+
+    /// Sign the SHA-512 prehash of a message with Ed25519ph (RFC 8032),
+    /// optionally with a domain-separation `context`.
+    ///
+    /// The key must be an Ed25519 key. The signature is over the full
+    /// message: check it with
+    /// [`verify_ed25519ph`](crate::verify::verify_ed25519ph).
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `digest` is not 64
+    /// bytes or `context` is longer than 255 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId};
+    /// use sha2::{Digest, Sha512};
+    ///
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let key_id: KeyId = "...".parse()?;
+    ///
+    /// let digest = Sha512::digest(b"a very large message");
+    /// let result = client
+    ///     .sign()
+    ///     .sign_digest_ed25519ph(&key_id, &digest, Some(b"partner-v1"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_digest_ed25519ph",
+            skip_all,
+            fields(key_id = %key_id)
+        )
+    )]
+    pub async fn sign_digest_ed25519ph(
+        &self,
+        key_id: &KeyId,
+        digest: &[u8],
+        context: Option<&[u8]>,
+    ) -> Result<SignResponse> {
+        let mut request = SignRequest::new(*key_id, digest)
+            .algorithm(SigningAlgorithm::Ed25519ph)
+            .digest_algorithm(DigestAlgorithm::Sha512);
+        if let Some(context) = context {
+            request = request.context(context);
+        }
+        self.sign_request(&request).await
+    }
+
+    /// Hash `reader` incrementally and sign the digest.
+    ///
+    /// The message is read in chunks and never held in memory as a whole,
+    /// which suits large payloads such as firmware images. Only the digest
+    /// is sent; the result is the same as [`sign_digest`](Self::sign_digest)
+    /// over the full message, so the key must accept prehashed input. For
+    /// keys that sign the raw message, such as plain Ed25519, use
+    /// [`sign_stream`](Self::sign_stream).
+    ///
+    /// Returns [`POPSignerError::Io`] if reading fails and
+    /// [`POPSignerError::InvalidRequest`] for
+    /// [`DigestAlgorithm::Keccak256`], which cannot be computed locally;
+    /// hash such messages yourself and call `sign_digest`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_stream_prehashed",
+            skip_all,
+            fields(key_id = %key_id, algorithm = %algorithm)
+        )
+    )]
+    pub async fn sign_stream_prehashed<R>(
+        &self,
+        key_id: &KeyId,
+        mut reader: R,
+        algorithm: DigestAlgorithm,
+    ) -> Result<SignResponse>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut hasher: Box<dyn DynDigest + Send> = match algorithm {
+            DigestAlgorithm::Sha256 => Box::new(Sha256::new()),
+            DigestAlgorithm::Sha384 => Box::new(Sha384::new()),
+            DigestAlgorithm::Sha512 => Box::new(Sha512::new()),
+            DigestAlgorithm::Keccak256 => {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "streaming {} digests is not supported",
+                    algorithm
+                )));
+            }
+        };
+
+        let mut buffer = vec![0u8; STREAM_CHUNK_SIZE];
+        loop {
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+
+        let digest = hasher.finalize();
+        self.sign_digest(key_id, &digest, algorithm).await
+    }
+
+    /// Upload the message read from `reader` in chunks and sign it.
+    ///
+    /// The server signs the raw message, so this works with every key
+    /// algorithm, while the SDK only holds one chunk in memory. The upload
+    /// is opened with one call, sent as a sequence of chunk calls of at
+    /// most 1 MiB (or the client's payload limit, if smaller) and signed by
+    /// a final call carrying the chunk count, length and SHA-256 of the
+    /// message, which the server checks before signing. Chunks are numbered,
+    /// so retried chunk calls are safe.
+    ///
+    /// If `algorithm` is `None` the key's default algorithm is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Io`] if reading fails and
+    /// [`POPSignerError::Decode`] if the server's byte count disagrees with
+    /// what was sent. On any failure after the upload was opened it is
+    /// cancelled on a best-effort basis.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId};
+    ///
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let key_id: KeyId = "...".parse()?;
+    ///
+    /// let image = tokio::fs::File::open("firmware.bin").await?;
+    /// let response = client.sign().sign_stream(&key_id, image, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign_stream", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn sign_stream<R>(
+        &self,
+        key_id: &KeyId,
+        reader: R,
+        algorithm: Option<SigningAlgorithm>,
+    ) -> Result<SignResponse>
+    where
+        R: AsyncRead + Unpin,
+    {
         #[derive(Serialize)]
-        struct Request {
+        struct OpenRequest {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            algorithm: Option<SigningAlgorithm>,
+        }
+
+        #[derive(Deserialize)]
+        struct OpenResponse {
+            upload_id: String,
+        }
+
+        let uploads = format!("/v1/keys/{}/sign/uploads", key_id);
+        let opened: OpenResponse = self
+            .client
+            .post(&uploads, &OpenRequest { algorithm })
+            .await?;
+        let upload = format!("{}/{}", uploads, encode_query_value(&opened.upload_id));
+
+        let result = self.upload_and_sign(&upload, reader).await;
+        if result.is_err() {
+            // The server also expires abandoned uploads.
+            let _ = self.client.delete(&upload).await;
+        }
+        let response = result?;
+
+        let signature = BASE64
+            .decode(&response.signature)
+            .map_err(|e| POPSignerError::Decode(format!("invalid base64 signature: {}", e)))?;
+        Ok(SignResponse {
+            key_id: *key_id,
+            signature,
+            public_key: response.public_key,
+            options: response.signature_options,
+            algorithm: response.algorithm.or(algorithm),
+            context: None,
+            recovery_id: response.recovery_id,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Send `reader` to the open upload at `upload` chunk by chunk, then
+    /// complete it.
+    async fn upload_and_sign<R>(&self, upload: &str, mut reader: R) -> Result<UploadSignature>
+    where
+        R: AsyncRead + Unpin,
+    {
+        #[derive(Serialize)]
+        struct ChunkRequest {
+            index: u64,
             data: String,
-            prehashed: bool,
         }
 
         #[derive(Deserialize)]
-        struct Response {
-            signature: String,
-            public_key: String,
+        struct ChunkResponse {
+            received: u64,
         }
 
-        let request = Request {
-            data: BASE64.encode(data),
-            prehashed,
+        #[derive(Serialize)]
+        struct CompleteRequest {
+            chunks: u64,
+            length: u64,
+            sha256: String,
+        }
+
+        let chunk_size = self
+            .client
+            .max_payload_bytes()
+            .map_or(UPLOAD_CHUNK_SIZE, |max| max.min(UPLOAD_CHUNK_SIZE));
+        let mut buffer = vec![0u8; chunk_size];
+        let mut hasher = Sha256::new();
+        let mut chunks = 0;
+        let mut length = 0;
+        loop {
+            let read = read_full(&mut reader, &mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            let chunk = &buffer[..read];
+            hasher.update(chunk);
+            length += read as u64;
+
+            let request = ChunkRequest {
+                index: chunks,
+                data: BASE64.encode(chunk),
+            };
+            let response: ChunkResponse = self
+                .client
+                .post(&format!("{}/chunks", upload), &request)
+                .await?;
+            if response.received != length {
+                return Err(POPSignerError::Decode(format!(
+                    "upload has {} bytes after chunk {}, expected {}",
+                    response.received, chunks, length
+                )));
+            }
+            chunks += 1;
+            if read < buffer.len() {
+                break;
+            }
+        }
+
+        let request = CompleteRequest {
+            chunks,
+            length,
+            sha256: BASE64.encode(hasher.finalize()),
         };
+        self.client
+            .post(&format!("{}/complete", upload), &request)
+            .await
+    }
+
+    /// Sign a JWT and return its compact serialization.
+    ///
+    /// The key is fetched first to determine the JWS `alg`, which is written
+    /// into `header` together with the key ID as `kid` unless one is set.
+    /// ECDSA signatures are converted to the raw `r || s` form JWS requires.
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `header.alg` is set and
+    /// does not match the key's algorithm. See the [`jwt`](crate::jwt) module
+    /// for an example.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign_jwt", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn sign_jwt(
+        &self,
+        key_id: &KeyId,
+        header: JwtHeader,
+        claims: serde_json::Value,
+    ) -> Result<String> {
+        let key = self.client.keys().get(key_id).await?;
+        let algorithm: SigningAlgorithm = key.algorithm.parse()?;
+        let header = header.bind(key_id, algorithm)?;
+        let signing_input = jwt::signing_input(&header, &claims)?;
 
+        let request = SignRequest::new(*key_id, signing_input.as_bytes()).algorithm(algorithm);
+        let response = self.sign_request(&request).await?;
+        let signature = if algorithm.is_ecdsa() {
+            response.to_encoding(SignatureEncoding::Raw)?
+        } else {
+            response.signature
+        };
+        Ok(jwt::compact(&signing_input, &signature))
+    }
+
+    /// Wrap `message` in a [`SignedEnvelope`] valid for `ttl` and sign it.
+    ///
+    /// The envelope carries a fresh random nonce; see the
+    /// [`envelope`](crate::envelope) module for verifying it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign_envelope", skip_all, fields(key_id = %key_id))
+    )]
+    pub async fn sign_envelope(
+        &self,
+        key_id: &KeyId,
+        message: &[u8],
+        ttl: Duration,
+    ) -> Result<SignedEnvelope> {
+        let mut envelope = SignedEnvelope::unsigned(message, ttl);
+        let request = SignRequest::new(*key_id, envelope.signing_payload());
+        let response = self.sign_request(&request).await?;
+        envelope.signature = response.signature;
+        envelope.algorithm = response.algorithm;
+        Ok(envelope)
+    }
+
+    /// Sign data described by a [`SignRequest`].
+    ///
+    /// If the request carries an idempotency key it is sent as the
+    /// `Idempotency-Key` header, unchanged across retries.
+    ///
+    /// [`SignatureOptions`] are checked against the request's `algorithm`
+    /// when one is set; otherwise the server validates them against the
+    /// key's algorithm.
+    ///
+    /// If the request has a cancellation token (see
+    /// [`SignRequest::with_cancel`]) and it fires, the in-flight call is
+    /// aborted and [`POPSignerError::Cancelled`] is returned. Requests marked
+    /// [`dry_run`](SignRequest::dry_run) are rejected with
+    /// [`POPSignerError::InvalidRequest`]; use [`validate`](Self::validate).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, SignRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///     
+    ///     let request = SignRequest::new(key_id, b"hello world".to_vec());
+    ///     let result = client.sign().sign_request(&request).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign", skip_all, fields(key_id = %request.key_id))
+    )]
+    pub async fn sign_request(&self, request: &SignRequest) -> Result<SignResponse> {
+        cancellable(request.cancel.as_ref(), self.send_sign_request(request))
+            .await
+            .map(Timed::into_inner)
+    }
+
+    /// Sign like [`sign_request`](Self::sign_request), also returning how
+    /// long the call took and how many attempts it needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, SignRequest};
+    ///
+    /// # async fn example() -> popsigner::Result<()> {
+    /// let client = Client::new("psk_live_xxxxx");
+    /// let key_id: KeyId = "...".parse()?;
+    ///
+    /// let request = SignRequest::new(key_id, b"hello world".to_vec());
+    /// let timed = client.sign().sign_request_timed(&request).await?;
+    /// println!("{:?} in {} attempts", timed.meta.elapsed, timed.meta.attempts);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign", skip_all, fields(key_id = %request.key_id))
+    )]
+    pub async fn sign_request_timed(&self, request: &SignRequest) -> Result<Timed<SignResponse>> {
+        cancellable(request.cancel.as_ref(), self.send_sign_request(request)).await
+    }
+
+    /// Submit a sign request as a job and return without waiting for the
+    /// signature, for keys whose HSM takes long enough that holding a
+    /// connection open is wasteful.
+    ///
+    /// See the [`jobs`](crate::jobs) module for collecting the result.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.sign_async", skip_all, fields(key_id = %request.key_id))
+    )]
+    pub async fn sign_async(&self, request: &SignRequest) -> Result<SignJobHandle> {
+        #[derive(Deserialize)]
+        struct Response {
+            job_id: String,
+        }
+
+        if request.dry_run {
+            return Err(POPSignerError::InvalidRequest(
+                "dry-run requests must be sent with SignClient::validate".to_string(),
+            ));
+        }
+
+        let (body, options) = SignBody::prepare(&self.client, request)?;
         let response: Response = self
             .client
-            .post(&format!("/v1/keys/{}/sign", key_id), &request)
+            .post_with_options(
+                &format!("/v1/keys/{}/sign/jobs", request.key_id),
+                &body,
+                options,
+            )
             .await?;
+        Ok(SignJobHandle::new(
+            self.client.clone(),
+            response.job_id,
+            request,
+        ))
+    }
+
+    /// Check on a job submitted with [`sign_async`](Self::sign_async).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::SignJobNotFound`] if the job has expired
+    /// or was never known to the server.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.poll_sign_job", skip_all, fields(job_id = %job.job_id()))
+    )]
+    pub async fn poll_sign_job(&self, job: &SignJobHandle) -> Result<SignJobStatus> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum State {
+            Pending,
+            Running,
+            Completed,
+            Failed,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            status: State,
+            #[serde(default)]
+            signature: String,
+            #[serde(default)]
+            public_key: String,
+            #[serde(default)]
+            signature_options: Option<SignatureOptions>,
+            #[serde(default)]
+            algorithm: Option<SigningAlgorithm>,
+            #[serde(default)]
+            recovery_id: Option<u8>,
+            #[serde(default)]
+            error: Option<String>,
+        }
+
+        let path = format!("/v1/sign/jobs/{}", encode_query_value(job.job_id()));
+        let response: Response = self.client.get(&path).await.map_err(|e| match e {
+            POPSignerError::Api {
+                status_code: 404 | 410,
+                ..
+            } => POPSignerError::SignJobNotFound(job.job_id().to_string()),
+            other => other,
+        })?;
+
+        match response.status {
+            State::Pending | State::Running => Ok(SignJobStatus::Pending),
+            State::Failed => {
+                let message = response
+                    .error
+                    .unwrap_or_else(|| "signing job failed".to_string());
+                Ok(SignJobStatus::Failed(message))
+            }
+            State::Completed => {
+                let signature = BASE64.decode(&response.signature).map_err(|e| {
+                    POPSignerError::Decode(format!("invalid base64 signature: {}", e))
+                })?;
+                Ok(SignJobStatus::Completed(SignResponse {
+                    key_id: *job.key_id(),
+                    signature,
+                    public_key: response.public_key,
+                    options: response.signature_options,
+                    algorithm: response.algorithm.or(job.algorithm),
+                    context: job.context.clone(),
+                    recovery_id: response.recovery_id,
+                    warnings: Vec::new(),
+                }))
+            }
+        }
+    }
+
+    async fn send_sign_request(&self, request: &SignRequest) -> Result<Timed<SignResponse>> {
+        #[derive(Deserialize)]
+        struct Response {
+            signature: String,
+            public_key: String,
+            #[serde(default)]
+            signature_options: Option<SignatureOptions>,
+            #[serde(default)]
+            algorithm: Option<SigningAlgorithm>,
+            #[serde(default)]
+            recovery_id: Option<u8>,
+        }
+
+        if request.dry_run {
+            return Err(POPSignerError::InvalidRequest(
+                "dry-run requests must be sent with SignClient::validate".to_string(),
+            ));
+        }
+
+        let (body, options) = SignBody::prepare(&self.client, request)?;
+        let result: Result<Timed<Response>> = self
+            .client
+            .post_timed(&format!("/v1/keys/{}/sign", request.key_id), &body, options)
+            .await;
+        let Timed {
+            value: response,
+            meta,
+        } = match (result, request.algorithm) {
+            (Ok(response), _) => response,
+            (
+                Err(POPSignerError::Api {
+                    code: ApiErrorCode::InvalidSignatureAlgorithm,
+                    ..
+                }),
+                Some(algorithm),
+            ) => return Err(self.algorithm_rejected(&request.key_id, algorithm).await),
+            (
+                Err(POPSignerError::Api {
+                    code: ApiErrorCode::KeyDisabled,
+                    ..
+                }),
+                _,
+            ) => {
+                self.client.evict_key(&request.key_id);
+                return Err(POPSignerError::KeyDisabled(request.key_id.to_string()));
+            }
+            (
+                Err(
+                    e @ POPSignerError::Api {
+                        code: ApiErrorCode::KeyVersionNotFound,
+                        ..
+                    },
+                ),
+                _,
+            ) => {
+                return Err(match request.key_version {
+                    Some(version) => POPSignerError::KeyVersionNotFound {
+                        key_id: request.key_id.to_string(),
+                        version,
+                    },
+                    None => e,
+                });
+            }
+            (Err(e), _) => {
+                if is_key_not_found(&e) {
+                    self.client.evict_key(&request.key_id);
+                }
+                return Err(e);
+            }
+        };
+        if request.recoverable && response.recovery_id.is_none() {
+            // Without an explicit algorithm the key's type is only known to
+            // the server, which omits the recovery ID for other curves.
+            return Err(POPSignerError::SigningError(format!(
+                "key {} returned no recovery ID; only secp256k1 keys sign recoverably",
+                request.key_id
+            )));
+        }
 
         let signature = BASE64
             .decode(&response.signature)
-            .map_err(|e| POPSignerError::Decode(e.to_string()))?;
+            .map_err(|e| POPSignerError::Decode(format!("invalid base64 signature: {}", e)))?;
 
-        Ok(SignResponse {
-            key_id: *key_id,
+        let value = SignResponse {
+            key_id: request.key_id,
             signature,
             public_key: response.public_key,
-        })
+            options: response.signature_options,
+            algorithm: response.algorithm.or(request.algorithm),
+            context: request.context.clone(),
+            recovery_id: response.recovery_id,
+            warnings: meta.warnings.clone(),
+        };
+        Ok(Timed { value, meta })
+    }
+
+    /// Build the error for a key that does not support `algorithm`, naming
+    /// the key's own type if it can be looked up.
+    async fn algorithm_rejected(
+        &self,
+        key_id: &KeyId,
+        algorithm: SigningAlgorithm,
+    ) -> POPSignerError {
+        let key_type = match self.client.keys().get(key_id).await {
+            Ok(key) => key.algorithm,
+            Err(_) => "unknown".to_string(),
+        };
+        POPSignerError::SigningError(format!(
+            "algorithm {} is not supported by key {} of type {}",
+            algorithm, key_id, key_type
+        ))
     }
 
     /// Sign multiple messages in parallel.
@@ -112,24 +775,29 @@ impl SignClient {
     /// All signing operations are performed in a single API call, reducing
     /// latency significantly.
     ///
+    /// The returned [`BatchResult`] holds one result per input item, in input
+    /// order, so failed entries can be retried individually. Use
+    /// [`BatchResult::into_result`] to turn any failure into a
+    /// [`POPSignerError::BatchPartialFailure`].
+    ///
     /// # Arguments
     ///
-    /// * `request` - Batch sign request containing multiple sign items
+    /// * `requests` - A [`BatchSignRequest`](crate::types::BatchSignRequest) or any iterator of
+    ///   [`BatchSignItem`]s
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::{Client, types::{BatchSignRequest, BatchSignItem}};
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId, types::{BatchSignRequest, BatchSignItem}};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
     ///     
-    ///     let worker1 = Uuid::parse_str("...")?;
-    ///     let worker2 = Uuid::parse_str("...")?;
-    ///     let worker3 = Uuid::parse_str("...")?;
-    ///     let worker4 = Uuid::parse_str("...")?;
+    ///     let worker1: KeyId = "...".parse()?;
+    ///     let worker2: KeyId = "...".parse()?;
+    ///     let worker3: KeyId = "...".parse()?;
+    ///     let worker4: KeyId = "...".parse()?;
     ///     
     ///     let results = client.sign().sign_batch(BatchSignRequest {
     ///         requests: vec![
@@ -138,14 +806,25 @@ impl SignClient {
     ///             BatchSignItem { key_id: worker3, data: b"tx3".to_vec(), prehashed: false },
     ///             BatchSignItem { key_id: worker4, data: b"tx4".to_vec(), prehashed: false },
     ///         ],
-    ///     }).await?;
+    ///     }).await?.into_result()?;
     ///     
     ///     // All 4 sign in parallel - completes in ~200ms, not 800ms!
     ///     println!("Signed {} transactions", results.len());
     ///     Ok(())
     /// }
     /// ```
-    pub async fn sign_batch(&self, request: BatchSignRequest) -> Result<Vec<SignResponse>> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_batch",
+            skip_all,
+            fields(count = tracing::field::Empty)
+        )
+    )]
+    pub async fn sign_batch(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+    ) -> Result<BatchResult> {
         #[derive(Serialize)]
         struct ApiRequest {
             requests: Vec<ApiRequestItem>,
@@ -153,7 +832,7 @@ impl SignClient {
 
         #[derive(Serialize)]
         struct ApiRequestItem {
-            key_id: Uuid,
+            key_id: KeyId,
             data: String,
             prehashed: bool,
         }
@@ -165,54 +844,500 @@ impl SignClient {
 
         #[derive(Deserialize)]
         struct ApiSignature {
-            key_id: Uuid,
+            key_id: KeyId,
+            #[serde(default)]
             signature: String,
+            #[serde(default)]
             public_key: String,
             error: Option<String>,
         }
 
         let api_request = ApiRequest {
-            requests: request
-                .requests
-                .iter()
+            requests: requests
+                .into_iter()
+                .enumerate()
+                .map(|(i, r)| {
+                    self.client
+                        .check_payload_size(&format!("batch item {} payload", i), r.data.len())?;
+                    Ok(ApiRequestItem {
+                        key_id: r.key_id,
+                        data: BASE64.encode(&r.data),
+                        prehashed: r.prehashed,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+        let count = api_request.requests.len();
+        trace::record_count(count);
+
+        let response: ApiResponse = self.client.post("/v1/sign/batch", &api_request).await?;
+
+        if response.signatures.len() != count {
+            return Err(POPSignerError::Decode(format!(
+                "batch response has {} results for {} requests",
+                response.signatures.len(),
+                count
+            )));
+        }
+
+        let results = response
+            .signatures
+            .into_iter()
+            .map(|sig| -> Result<SignResponse> {
+                if let Some(error) = sig.error {
+                    return Err(self.client.redact(POPSignerError::SigningError(error)));
+                }
+
+                let signature = BASE64.decode(&sig.signature).map_err(|e| {
+                    POPSignerError::Decode(format!("invalid base64 signature: {}", e))
+                })?;
+
+                Ok(SignResponse {
+                    key_id: sig.key_id,
+                    signature,
+                    public_key: sig.public_key,
+                    options: None,
+                    algorithm: None,
+                    context: None,
+                    recovery_id: None,
+                    warnings: Vec::new(),
+                })
+            })
+            .collect();
+
+        Ok(BatchResult { results })
+    }
+
+    /// Sign a batch of any size by splitting it into chunks of at most
+    /// `max_chunk` items, sent one after another.
+    ///
+    /// If the server announces its own limit in an `X-Max-Batch-Size`
+    /// header, that limit is used instead of `max_chunk`; a chunk rejected
+    /// while a smaller limit is announced is split and sent again. The
+    /// results of all chunks are stitched into one [`BatchResult`] aligned
+    /// with the input, so [`BatchResult::into_result`] reports failures
+    /// from every chunk in a single
+    /// [`POPSignerError::BatchPartialFailure`]. When a whole chunk fails,
+    /// its first item carries the error and the others a
+    /// [`POPSignerError::SigningError`] describing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `max_chunk` is zero.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_batch_chunked",
+            skip_all,
+            fields(count = tracing::field::Empty)
+        )
+    )]
+    pub async fn sign_batch_chunked(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        max_chunk: usize,
+    ) -> Result<BatchResult> {
+        if max_chunk == 0 {
+            return Err(POPSignerError::InvalidRequest(
+                "max_chunk must be greater than zero".to_string(),
+            ));
+        }
+        let items: Vec<BatchSignItem> = requests.into_iter().collect();
+        trace::record_count(items.len());
+
+        let mut results = Vec::with_capacity(items.len());
+        let mut start = 0;
+        while start < items.len() {
+            let size = self.client.max_batch_size().unwrap_or(max_chunk);
+            let end = items.len().min(start + size);
+            let result = self.sign_batch(items[start..end].iter().cloned()).await;
+            let shrunk = self
+                .client
+                .max_batch_size()
+                .is_some_and(|max| max < end - start);
+            if result.is_err() && shrunk {
+                // The server announced a smaller limit; retry this chunk
+                // split to fit it.
+                continue;
+            }
+            match result {
+                Ok(batch) => results.extend(batch.results),
+                Err(e) => {
+                    let message = e.to_string();
+                    results.push(Err(e));
+                    results.extend((start + 1..end).map(|_| {
+                        Err(POPSignerError::SigningError(format!(
+                            "batch chunk failed: {}",
+                            message
+                        )))
+                    }));
+                }
+            }
+            start = end;
+        }
+        Ok(BatchResult { results })
+    }
+
+    /// Like [`sign_batch`](Self::sign_batch), with [`BatchOptions`].
+    ///
+    /// With [`BatchOptions::dedup`], repeated items are sent once and the
+    /// result is copied to each of their indices; a failed item's
+    /// duplicates get a [`POPSignerError::SigningError`] describing the
+    /// failure. Deciding which keys are deterministic costs one
+    /// [`KeysClient::get`](crate::keys::KeysClient::get) per key that has
+    /// repeated items, served from the key cache when one is configured.
+    pub async fn sign_batch_with_options(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        options: &BatchOptions,
+    ) -> Result<BatchResult> {
+        if !options.dedup {
+            return self.sign_batch(requests).await;
+        }
+        let (unique, indices) = self.dedup(requests.into_iter().collect()).await;
+        let batch = self.sign_batch(unique).await?;
+        Ok(fan_out(batch, &indices))
+    }
+
+    /// Like [`sign_batch_chunked`](Self::sign_batch_chunked), with
+    /// [`BatchOptions`]. Duplicates are removed before the batch is split
+    /// into chunks; see
+    /// [`sign_batch_with_options`](Self::sign_batch_with_options).
+    pub async fn sign_batch_chunked_with_options(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+        max_chunk: usize,
+        options: &BatchOptions,
+    ) -> Result<BatchResult> {
+        if !options.dedup {
+            return self.sign_batch_chunked(requests, max_chunk).await;
+        }
+        let (unique, indices) = self.dedup(requests.into_iter().collect()).await;
+        let batch = self.sign_batch_chunked(unique, max_chunk).await?;
+        Ok(fan_out(batch, &indices))
+    }
+
+    /// Drop repeated items for deterministic keys, returning the items to
+    /// sign and, for each input, the index of its item among them.
+    async fn dedup(&self, items: Vec<BatchSignItem>) -> (Vec<BatchSignItem>, Vec<usize>) {
+        // Keys with repeated items, in input order.
+        let mut repeated = Vec::new();
+        {
+            let mut seen = HashSet::new();
+            for item in &items {
+                let duplicate = !seen.insert((item.key_id, &item.data, item.prehashed));
+                if duplicate && !repeated.contains(&item.key_id) {
+                    repeated.push(item.key_id);
+                }
+            }
+        }
+
+        // A key that cannot be looked up is left alone; its items fail in
+        // the batch on their own.
+        let mut deterministic = HashSet::new();
+        for key_id in repeated {
+            let Ok(key) = self.client.keys().get(&key_id).await else {
+                continue;
+            };
+            if key
+                .algorithm
+                .parse::<SigningAlgorithm>()
+                .is_ok_and(|algorithm| algorithm.is_deterministic())
+            {
+                deterministic.insert(key_id);
+            }
+        }
+
+        let mut first = HashMap::new();
+        let mut unique = Vec::new();
+        let mut indices = Vec::with_capacity(items.len());
+        for item in items {
+            if deterministic.contains(&item.key_id) {
+                match first.entry((item.key_id, item.data.clone(), item.prehashed)) {
+                    Entry::Occupied(entry) => {
+                        indices.push(*entry.get());
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(unique.len());
+                    }
+                }
+            }
+            indices.push(unique.len());
+            unique.push(item);
+        }
+        (unique, indices)
+    }
+
+    /// Validate a sign request without producing a signature.
+    ///
+    /// The request is sent as a dry run whatever its `dry_run` flag: the
+    /// server checks the key, algorithm, options and payload size, and
+    /// neither signs nor counts the call against your signing quota. A
+    /// missing key is reported as a `key_not_found` issue rather than an
+    /// error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.validate", skip_all, fields(key_id = %request.key_id))
+    )]
+    pub async fn validate(&self, request: &SignRequest) -> Result<ValidationReport> {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(default)]
+            issues: Vec<ValidationIssue>,
+        }
+
+        let (mut body, options) = SignBody::prepare(&self.client, request)?;
+        body.dry_run = true;
+        let result: Result<Response> = self
+            .client
+            .post_with_options(&format!("/v1/keys/{}/sign", request.key_id), &body, options)
+            .await;
+
+        let issues = match result {
+            Ok(response) => response.issues,
+            Err(e) if is_key_not_found(&e) => vec![ValidationIssue {
+                code: "key_not_found".to_string(),
+                message: e.to_string(),
+            }],
+            Err(e) => return Err(e),
+        };
+        Ok(ValidationReport {
+            key_id: request.key_id,
+            issues,
+        })
+    }
+
+    /// Validate a batch without signing, like [`validate`](Self::validate).
+    ///
+    /// Reports are aligned by index with `requests`. Dry runs do not count
+    /// against your signing quota.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.validate_batch",
+            skip_all,
+            fields(count = tracing::field::Empty)
+        )
+    )]
+    pub async fn validate_batch(
+        &self,
+        requests: impl IntoIterator<Item = BatchSignItem>,
+    ) -> Result<Vec<ValidationReport>> {
+        #[derive(Serialize)]
+        struct ApiRequest {
+            requests: Vec<ApiRequestItem>,
+            dry_run: bool,
+        }
+
+        #[derive(Serialize)]
+        struct ApiRequestItem {
+            key_id: KeyId,
+            data: String,
+            prehashed: bool,
+        }
+
+        #[derive(Deserialize)]
+        struct ApiResponse {
+            reports: Vec<ValidationReport>,
+        }
+
+        let api_request = ApiRequest {
+            requests: requests
+                .into_iter()
                 .map(|r| ApiRequestItem {
                     key_id: r.key_id,
                     data: BASE64.encode(&r.data),
                     prehashed: r.prehashed,
                 })
                 .collect(),
+            dry_run: true,
         };
+        let count = api_request.requests.len();
+        trace::record_count(count);
 
         let response: ApiResponse = self.client.post("/v1/sign/batch", &api_request).await?;
+        if response.reports.len() != count {
+            return Err(POPSignerError::Decode(format!(
+                "batch response has {} reports for {} requests",
+                response.reports.len(),
+                count
+            )));
+        }
+        Ok(response.reports)
+    }
 
-        let mut results = Vec::new();
-        let mut errors = 0;
+    /// Sign several messages with one key in a single API call.
+    ///
+    /// Signatures are returned in the order of `messages`. If any message
+    /// fails, the whole call fails with
+    /// [`POPSignerError::BatchPartialFailure`]; use
+    /// [`sign_batch`](Self::sign_batch) to keep the partial results.
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if `messages` is empty.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_multi",
+            skip_all,
+            fields(key_id = %key_id, count = messages.len())
+        )
+    )]
+    pub async fn sign_multi(
+        &self,
+        key_id: &KeyId,
+        messages: Vec<Vec<u8>>,
+        prehashed: bool,
+    ) -> Result<Vec<SignResponse>> {
+        if messages.is_empty() {
+            return Err(POPSignerError::InvalidRequest(
+                "at least one message is required".to_string(),
+            ));
+        }
 
-        for sig in response.signatures {
-            if sig.error.is_some() {
-                errors += 1;
-                continue;
-            }
+        let requests = messages.into_iter().map(|data| BatchSignItem {
+            key_id: *key_id,
+            data,
+            prehashed,
+        });
+        self.sign_batch(requests).await?.into_result()
+    }
 
-            let signature = BASE64
-                .decode(&sig.signature)
-                .map_err(|e| POPSignerError::Decode(e.to_string()))?;
+    /// Sign many independent requests concurrently.
+    ///
+    /// Each request is sent as its own API call, with at most `concurrency`
+    /// calls in flight at once. A failed item never cancels the others; the
+    /// returned [`BatchResult`] is aligned by index with `requests`.
+    /// Requests are consumed lazily, so they need not be collected first.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, SignRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///     
+    ///     let requests = (0..100)
+    ///         .map(|i| SignRequest::new(key_id, format!("tx-{}", i).into_bytes()));
+    ///     
+    ///     let results = client.sign().sign_batch_concurrent(requests, 8).await;
+    ///     println!("{} failed", results.failed_indices().len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_batch_concurrent",
+            skip_all,
+            fields(count = tracing::field::Empty, concurrency = concurrency)
+        )
+    )]
+    pub async fn sign_batch_concurrent(
+        &self,
+        requests: impl IntoIterator<Item = SignRequest>,
+        concurrency: usize,
+    ) -> BatchResult {
+        self.sign_batch_concurrent_stream(stream::iter(requests), concurrency)
+            .await
+    }
 
-            results.push(SignResponse {
-                key_id: sig.key_id,
-                signature,
-                public_key: sig.public_key,
-            });
-        }
+    /// Like [`sign_batch_concurrent`](Self::sign_batch_concurrent), but
+    /// takes requests from a stream as they are produced.
+    ///
+    /// Signing starts as soon as the first request arrives. Results are
+    /// aligned by index with the order in which the stream yielded requests.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.sign_batch_concurrent_stream",
+            skip_all,
+            fields(count = tracing::field::Empty, concurrency = concurrency)
+        )
+    )]
+    pub async fn sign_batch_concurrent_stream(
+        &self,
+        requests: impl Stream<Item = SignRequest>,
+        concurrency: usize,
+    ) -> BatchResult {
+        let mut indexed: Vec<_> = self.sign_unordered(requests, concurrency).collect().await;
+        indexed.sort_unstable_by_key(|(index, _)| *index);
+        let results: Vec<_> = indexed.into_iter().map(|(_, result)| result).collect();
+        trace::record_count(results.len());
 
-        if errors > 0 && results.is_empty() {
-            return Err(POPSignerError::BatchPartialFailure {
-                failed: errors,
-                total: request.requests.len(),
-            });
-        }
+        BatchResult { results }
+    }
 
-        Ok(results)
+    /// Sign many independent requests concurrently, yielding each result
+    /// as soon as it completes.
+    ///
+    /// Like [`sign_batch_concurrent`](Self::sign_batch_concurrent), but
+    /// results come out in completion order, each tagged with the index of
+    /// its request, so downstream work can start and memory can be freed
+    /// before the slowest call finishes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use popsigner::{Client, KeyId, SignRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///
+    ///     let requests = (0..100)
+    ///         .map(|i| SignRequest::new(key_id, format!("tx-{}", i).into_bytes()));
+    ///     let sign = client.sign();
+    ///     let mut results = std::pin::pin!(sign.sign_batch_stream(requests, 8));
+    ///     while let Some((index, result)) = results.next().await {
+    ///         println!("request {} done: {}", index, result.is_ok());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sign_batch_stream(
+        &self,
+        requests: impl IntoIterator<Item = SignRequest>,
+        concurrency: usize,
+    ) -> impl Stream<Item = (usize, Result<SignResponse>)> {
+        self.sign_unordered(stream::iter(requests), concurrency)
+    }
+
+    /// Sign `requests` with at most `concurrency` calls in flight, yielding
+    /// `(index, result)` pairs in completion order.
+    fn sign_unordered(
+        &self,
+        requests: impl Stream<Item = SignRequest>,
+        concurrency: usize,
+    ) -> impl Stream<Item = (usize, Result<SignResponse>)> {
+        requests
+            .enumerate()
+            .map(|(index, request)| async move { (index, self.sign_request(&request).await) })
+            .buffer_unordered(concurrency.max(1))
+    }
+
+    /// Like [`sign_batch_concurrent`](Self::sign_batch_concurrent), but
+    /// stops when `cancel` fires.
+    ///
+    /// Items that completed before cancellation keep their results; the
+    /// rest fail with [`POPSignerError::Cancelled`]. Requests that carry
+    /// their own token keep it.
+    pub async fn sign_batch_concurrent_with_cancel(
+        &self,
+        requests: impl IntoIterator<Item = SignRequest>,
+        concurrency: usize,
+        cancel: CancellationToken,
+    ) -> BatchResult {
+        let requests = requests.into_iter().map(|request| match request.cancel {
+            Some(_) => request,
+            None => request.with_cancel(cancel.clone()),
+        });
+        self.sign_batch_concurrent(requests, concurrency).await
     }
 
     /// Verify a signature against the public key of a key.
@@ -227,13 +1352,12 @@ impl SignClient {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use popsigner::Client;
-    /// use uuid::Uuid;
+    /// use popsigner::{Client, KeyId};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("psk_live_xxxxx");
-    ///     let key_id = Uuid::parse_str("...")?;
+    ///     let key_id: KeyId = "...".parse()?;
     ///     
     ///     let data = b"hello world";
     ///     let result = client.sign().sign(&key_id, data, false).await?;
@@ -244,9 +1368,13 @@ impl SignClient {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "sign.verify", skip_all, fields(key_id = %key_id))
+    )]
     pub async fn verify(
         &self,
-        key_id: &Uuid,
+        key_id: &KeyId,
         data: &[u8],
         signature: &[u8],
         prehashed: bool,
@@ -263,6 +1391,17 @@ impl SignClient {
             valid: bool,
         }
 
+        let cached = self
+            .client
+            .verify_cache()
+            .map(|cache| (cache, Verification::new(key_id, data, signature, prehashed)));
+        if let Some(valid) = cached
+            .as_ref()
+            .and_then(|(cache, verification)| cache.get(verification))
+        {
+            return Ok(valid);
+        }
+
         let request = Request {
             data: BASE64.encode(data),
             signature: BASE64.encode(signature),
@@ -274,13 +1413,323 @@ impl SignClient {
             .post(&format!("/v1/keys/{}/verify", key_id), &request)
             .await?;
 
+        if let Some((cache, verification)) = cached {
+            cache.insert(verification, response.valid);
+        }
         Ok(response.valid)
     }
+
+    /// Verify multiple signatures in a single API call.
+    ///
+    /// The returned [`BatchResult`] holds one result per input item, in input
+    /// order. A signature that does not verify is `Ok` with `valid: false`;
+    /// only items the server could not process at all (e.g. an unknown key)
+    /// are `Err`, and [`BatchResult::into_result`] turns those into a
+    /// [`POPSignerError::BatchPartialFailure`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use popsigner::{Client, KeyId, VerifyRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("psk_live_xxxxx");
+    ///     let key_id: KeyId = "...".parse()?;
+    ///
+    ///     let results = client
+    ///         .sign()
+    ///         .verify_batch(vec![
+    ///             VerifyRequest::new(key_id, b"tx1".to_vec(), vec![0u8; 64]),
+    ///             VerifyRequest::new(key_id, b"tx2".to_vec(), vec![0u8; 64]),
+    ///         ])
+    ///         .await?
+    ///         .into_result()?;
+    ///
+    ///     let valid = results.iter().filter(|r| r.valid).count();
+    ///     println!("{} of {} signatures valid", valid, results.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign.verify_batch",
+            skip_all,
+            fields(count = tracing::field::Empty)
+        )
+    )]
+    pub async fn verify_batch(
+        &self,
+        requests: impl IntoIterator<Item = VerifyRequest>,
+    ) -> Result<BatchResult<VerifyResult>> {
+        #[derive(Serialize)]
+        struct ApiRequest {
+            requests: Vec<ApiRequestItem>,
+        }
+
+        #[derive(Serialize)]
+        struct ApiRequestItem {
+            key_id: KeyId,
+            data: String,
+            signature: String,
+            prehashed: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            context: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            algorithm: Option<SigningAlgorithm>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            key_version: Option<u32>,
+        }
+
+        #[derive(Deserialize)]
+        struct ApiResponse {
+            results: Vec<ApiVerifyResult>,
+        }
+
+        #[derive(Deserialize)]
+        struct ApiVerifyResult {
+            key_id: KeyId,
+            #[serde(default)]
+            valid: bool,
+            error: Option<String>,
+        }
+
+        let api_request = ApiRequest {
+            requests: requests
+                .into_iter()
+                .map(|r| ApiRequestItem {
+                    key_id: r.key_id,
+                    data: BASE64.encode(&r.data),
+                    signature: BASE64.encode(&r.signature),
+                    prehashed: r.prehashed,
+                    context: r.context.as_ref().map(|c| BASE64.encode(c)),
+                    algorithm: r.algorithm,
+                    key_version: r.key_version,
+                })
+                .collect(),
+        };
+
+        let count = api_request.requests.len();
+        trace::record_count(count);
+
+        let response: ApiResponse = self.client.post("/v1/verify/batch", &api_request).await?;
+
+        if response.results.len() != count {
+            return Err(POPSignerError::Decode(format!(
+                "batch response has {} results for {} requests",
+                response.results.len(),
+                count
+            )));
+        }
+
+        let results = response
+            .results
+            .into_iter()
+            .map(|result| match result.error {
+                Some(error) => Err(POPSignerError::InvalidRequest(error)),
+                None => Ok(VerifyResult {
+                    key_id: result.key_id,
+                    valid: result.valid,
+                }),
+            })
+            .collect();
+
+        Ok(BatchResult { results })
+    }
+}
+
+/// Body of `POST /v1/keys/{id}/sign`.
+#[derive(Serialize)]
+struct SignBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_version: Option<u32>,
+    data: String,
+    prehashed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    digest_algorithm: Option<DigestAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    algorithm: Option<SigningAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature_options: Option<SignatureOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<SignatureEncoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    recoverable: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+}
+
+impl SignBody {
+    /// Check `request` locally and build its body and request options.
+    fn prepare(client: &Client, request: &SignRequest) -> Result<(Self, RequestOptions)> {
+        client.check_payload_size("payload", request.data.len())?;
+        if let (Some(_), Some(algorithm)) = (request.options, request.algorithm) {
+            if !algorithm.is_ecdsa() {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "signature options are only supported for ECDSA, not {}",
+                    algorithm
+                )));
+            }
+        }
+        if let Some(context) = &request.context {
+            if context.len() > MAX_CONTEXT_LEN {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "signing context must be at most {} bytes, got {}",
+                    MAX_CONTEXT_LEN,
+                    context.len()
+                )));
+            }
+            if let Some(algorithm) = request.algorithm.filter(|a| !a.supports_context()) {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "a signing context is only supported for ed25519ph and ed25519ctx, not {}",
+                    algorithm
+                )));
+            }
+        }
+        let mut data = Cow::Borrowed(request.data.as_slice());
+        let mut prehashed = request.prehashed;
+        let mut digest_algorithm = request.digest_algorithm;
+        if request.algorithm == Some(SigningAlgorithm::Ed25519ph) {
+            if !prehashed && request.prehash_locally {
+                data = Cow::Owned(Sha512::digest(&request.data).to_vec());
+                prehashed = true;
+                digest_algorithm = Some(DigestAlgorithm::Sha512);
+            }
+            if !prehashed {
+                return Err(POPSignerError::InvalidRequest(
+                    "ed25519ph needs a SHA-512 prehash; set prehashed or prehash_locally"
+                        .to_string(),
+                ));
+            }
+            if data.len() != ED25519PH_PREHASH_LEN {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "ed25519ph prehash must be {} bytes, got {}",
+                    ED25519PH_PREHASH_LEN,
+                    data.len()
+                )));
+            }
+        }
+        if let Some(algorithm) = digest_algorithm {
+            if !prehashed {
+                return Err(POPSignerError::InvalidRequest(
+                    "a digest algorithm is only valid for prehashed data".to_string(),
+                ));
+            }
+            if data.len() != algorithm.digest_len() {
+                return Err(POPSignerError::InvalidRequest(format!(
+                    "{} digest must be {} bytes, got {}",
+                    algorithm,
+                    algorithm.digest_len(),
+                    data.len()
+                )));
+            }
+        }
+        if let Some(algorithm) = request
+            .algorithm
+            .filter(|a| request.recoverable && *a != SigningAlgorithm::EcdsaSecp256k1)
+        {
+            return Err(POPSignerError::SigningError(format!(
+                "recoverable signatures are only supported for secp256k1, not {}",
+                algorithm
+            )));
+        }
+
+        let mut headers = custom_headers(&request.headers)?;
+        if let Some(key) = &request.idempotency_key {
+            let value = HeaderValue::from_str(key).map_err(|_| {
+                POPSignerError::InvalidRequest(format!("invalid idempotency key: {:?}", key))
+            })?;
+            headers.insert("Idempotency-Key", value);
+        }
+
+        let body = Self {
+            key_version: request.key_version,
+            data: BASE64.encode(&data),
+            prehashed,
+            digest_algorithm,
+            algorithm: request.algorithm,
+            signature_options: request.options,
+            encoding: request.encoding,
+            context: request.context.as_ref().map(|c| BASE64.encode(c)),
+            recoverable: request.recoverable,
+            dry_run: request.dry_run,
+        };
+        let options = RequestOptions {
+            headers,
+            timeout: request.timeout,
+            deadline: request.deadline,
+            retry: request.retry.clone(),
+            priority: request.priority,
+        };
+        Ok((body, options))
+    }
+}
+
+/// Copy the results of a deduplicated batch back to every input index.
+///
+/// The first input of each item takes the original result; its duplicates
+/// get a copy.
+fn fan_out(batch: BatchResult, indices: &[usize]) -> BatchResult {
+    let copies: Vec<std::result::Result<SignResponse, String>> = batch
+        .results
+        .iter()
+        .map(|result| match result {
+            Ok(response) => Ok(response.clone()),
+            Err(e) => Err(e.to_string()),
+        })
+        .collect();
+    let mut originals: Vec<_> = batch.results.into_iter().map(Some).collect();
+
+    let results = indices
+        .iter()
+        .map(|&i| {
+            originals[i].take().unwrap_or_else(|| match &copies[i] {
+                Ok(response) => Ok(response.clone()),
+                Err(message) => Err(POPSignerError::SigningError(format!(
+                    "duplicate of a failed item: {}",
+                    message
+                ))),
+            })
+        })
+        .collect();
+    BatchResult { results }
+}
+
+/// Signature returned by completing an upload from
+/// [`SignClient::sign_stream`].
+#[derive(Deserialize)]
+struct UploadSignature {
+    signature: String,
+    public_key: String,
+    #[serde(default)]
+    signature_options: Option<SignatureOptions>,
+    #[serde(default)]
+    algorithm: Option<SigningAlgorithm>,
+    #[serde(default)]
+    recovery_id: Option<u8>,
+}
+
+/// Fill `buffer` from `reader`, stopping early only at end of input.
+async fn read_full<R: AsyncRead + Unpin>(reader: &mut R, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = reader.read(&mut buffer[filled..]).await?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::runtime::Instant;
+    use uuid::Uuid;
 
     #[test]
     fn test_sign_client_creation() {
@@ -288,4 +1737,584 @@ mod tests {
         let _sign = client.sign();
         // Just verify it compiles and doesn't panic
     }
+
+    #[tokio::test]
+    async fn test_sign_digest_rejects_wrong_length() {
+        let client = Client::new("test_key");
+        let err = client
+            .sign()
+            .sign_digest(&Uuid::nil().into(), &[0u8; 20], DigestAlgorithm::Sha256)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_digest_forwards_digest_algorithm() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk"
+        }));
+
+        client
+            .sign()
+            .sign_digest(&Uuid::nil().into(), &[0u8; 32], DigestAlgorithm::Keccak256)
+            .await
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(mock.requests()[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["prehashed"], true);
+        assert_eq!(body["digest_algorithm"], "keccak256");
+
+        let unhashed = SignRequest::new(Uuid::nil(), vec![0u8; 32])
+            .digest_algorithm(DigestAlgorithm::Sha256)
+            .prehashed(false);
+        let err = client.sign().sign_request(&unhashed).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sign_stream_uploads_chunks() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .max_payload_bytes(4)
+            .build()
+            .unwrap();
+        mock.push_data(serde_json::json!({ "upload_id": "up_1" }));
+        for received in [4, 8, 10] {
+            mock.push_data(serde_json::json!({ "received": received }));
+        }
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk",
+            "algorithm": "ed25519"
+        }));
+
+        let response = client
+            .sign()
+            .sign_stream(&Uuid::nil().into(), &b"0123456789"[..], None)
+            .await
+            .unwrap();
+        assert_eq!(response.signature, vec![1, 2, 3]);
+        assert_eq!(response.algorithm, Some(SigningAlgorithm::Ed25519));
+
+        let requests = mock.requests();
+        let upload = format!("/v1/keys/{}/sign/uploads", Uuid::nil());
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].url.path(), upload);
+        let chunks = [&b"0123"[..], &b"4567"[..], &b"89"[..]];
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let request = &requests[i + 1];
+            let body = request.body_json().unwrap();
+            assert_eq!(request.url.path(), format!("{}/up_1/chunks", upload));
+            assert_eq!(body["index"], i);
+            assert_eq!(body["data"], BASE64.encode(chunk));
+        }
+        let complete = requests[4].body_json().unwrap();
+        assert_eq!(requests[4].url.path(), format!("{}/up_1/complete", upload));
+        assert_eq!(complete["chunks"], 3);
+        assert_eq!(complete["length"], 10);
+        let sha256 = BASE64.encode(Sha256::digest(b"0123456789"));
+        assert_eq!(complete["sha256"], sha256);
+    }
+
+    #[tokio::test]
+    async fn test_sign_stream_cancels_upload_on_mismatch() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        mock.push_data(serde_json::json!({ "upload_id": "up_2" }));
+        mock.push_data(serde_json::json!({ "received": 3 }));
+        mock.push_response(crate::transport::Response::new(204, ""));
+
+        let err = client
+            .sign()
+            .sign_stream(&Uuid::nil().into(), &b"firmware"[..], None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::Decode(_)));
+        let cancel = mock.last_request().unwrap();
+        assert_eq!(cancel.method, reqwest::Method::DELETE);
+        assert!(cancel.url.path().ends_with("/sign/uploads/up_2"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_multi_rejects_empty() {
+        let client = Client::new("test_key");
+        let err = client
+            .sign()
+            .sign_multi(&Uuid::nil().into(), Vec::new(), false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_signature_options_rejected_for_ed25519() {
+        let client = Client::new("test_key");
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519)
+            .options(SignatureOptions {
+                deterministic: true,
+                canonical_s: false,
+            });
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_ed25519ph_requires_prehash() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let request = SignRequest::new(Uuid::nil(), b"abc".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519ph)
+            .context(b"partner-v1".to_vec());
+
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+        let err = client
+            .sign()
+            .sign_digest_ed25519ph(&Uuid::nil().into(), &[0u8; 32], None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+        assert_eq!(mock.requests().len(), 0);
+
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk"
+        }));
+        let request = request.prehash_locally(true);
+        client.sign().sign_request(&request).await.unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(mock.requests()[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"], BASE64.encode(Sha512::digest(b"abc")));
+        assert_eq!(body["prehashed"], true);
+        assert_eq!(body["algorithm"], "ed25519ph");
+        assert_eq!(body["digest_algorithm"], "sha512");
+    }
+
+    #[tokio::test]
+    async fn test_context_requires_supporting_algorithm() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let plain = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519)
+            .context(b"app-v1".to_vec());
+        let err = client.sign().sign_request(&plain).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+
+        let too_long = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519ctx)
+            .context(vec![0u8; 256]);
+        let err = client.sign().sign_request(&too_long).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+        assert_eq!(mock.requests().len(), 0);
+
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk"
+        }));
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519ctx)
+            .context(b"app-v1".to_vec());
+        let response = client.sign().sign_request(&request).await.unwrap();
+        assert_eq!(response.context.as_deref(), Some(&b"app-v1"[..]));
+
+        let body = mock.last_request().unwrap().body_json().unwrap();
+        assert_eq!(body["context"], BASE64.encode(b"app-v1"));
+        assert_eq!(body["algorithm"], "ed25519ctx");
+    }
+
+    #[tokio::test]
+    async fn test_recoverable_requires_secp256k1() {
+        let mock = crate::testing::MockTransport::new();
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519)
+            .recoverable(true);
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::SigningError(_)));
+        assert_eq!(mock.requests().len(), 0);
+
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1u8; 64]),
+            "public_key": "pk",
+            "algorithm": "secp256k1",
+            "recovery_id": 0
+        }));
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec()).recoverable(true);
+        let response = client.sign().sign_request(&request).await.unwrap();
+        assert_eq!(response.recovery_id, Some(0));
+        assert_eq!(response.to_eth_signature().unwrap()[64], 27);
+        let body = mock.last_request().unwrap().body_json().unwrap();
+        assert_eq!(body["recoverable"], true);
+    }
+
+    #[tokio::test]
+    async fn test_payload_size_checked_before_sending() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1]),
+            "public_key": "pk"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .max_payload_bytes(4)
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(Uuid::nil());
+        let sign = client.sign();
+        let err = sign.sign(&key_id, b"12345", false).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(m) if m.contains("5 bytes")));
+        let items = [b"1234".to_vec(), b"12345".to_vec()].map(|data| BatchSignItem {
+            key_id,
+            data,
+            prehashed: false,
+        });
+        let err = sign.sign_batch(items).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(m) if m.contains("batch item 1")));
+        assert!(mock.requests().is_empty());
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .max_payload_bytes(4)
+            .check_payload_size(false)
+            .build()
+            .unwrap();
+        client.sign().sign(&key_id, b"12345", false).await.unwrap();
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sign_async_job() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({ "job_id": "job_1" }));
+        mock.push_data(serde_json::json!({ "status": "running" }));
+        mock.push_data(serde_json::json!({
+            "status": "completed",
+            "signature": BASE64.encode([4, 2]),
+            "public_key": "pk"
+        }));
+        let body = serde_json::json!({
+            "error": { "code": "not_found", "message": "job expired" }
+        });
+        mock.push_response(crate::transport::Response::json(404, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec());
+        let job = client.sign().sign_async(&request).await.unwrap();
+        assert_eq!(job.job_id(), "job_1");
+        let response = job.await_completion(Duration::ZERO).await.unwrap();
+        assert_eq!(response.signature, vec![4, 2]);
+        assert_eq!(mock.requests()[2].url.path(), "/v1/sign/jobs/job_1");
+
+        let err = client.sign().poll_sign_job(&job).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::SignJobNotFound(id) if id == "job_1"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_batch_chunked_adopts_server_limit() {
+        let mock = crate::testing::MockTransport::new();
+        let too_big = serde_json::json!({
+            "error": { "code": "bad_request", "message": "batch too large" }
+        });
+        mock.push_response(
+            crate::transport::Response::json(400, &too_big).with_header("x-max-batch-size", "1"),
+        );
+        for i in 1..=3u128 {
+            let signature = if i == 2 {
+                serde_json::json!({ "key_id": Uuid::from_u128(i), "error": "boom" })
+            } else {
+                serde_json::json!({
+                    "key_id": Uuid::from_u128(i),
+                    "signature": BASE64.encode([i as u8]),
+                    "public_key": "pk"
+                })
+            };
+            mock.push_data(serde_json::json!({ "signatures": [signature] }));
+        }
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let items = (1..=3u128).map(|i| BatchSignItem {
+            key_id: Uuid::from_u128(i).into(),
+            data: b"tx".to_vec(),
+            prehashed: false,
+        });
+        let batch = client.sign().sign_batch_chunked(items, 2).await.unwrap();
+        assert_eq!(mock.requests().len(), 4);
+        assert_eq!(batch.failed_indices(), vec![1]);
+        assert_eq!(batch.results[2].as_ref().unwrap().signature, vec![3]);
+        assert!(matches!(
+            batch.into_result(),
+            Err(POPSignerError::BatchPartialFailure { total: 3, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_batch_dedup_skips_nondeterministic_keys() {
+        let key = |id: u128, algorithm: &str| {
+            serde_json::json!({
+                "id": Uuid::from_u128(id),
+                "name": "key",
+                "namespace_id": Uuid::nil(),
+                "public_key": "pk",
+                "address": "addr",
+                "algorithm": algorithm,
+                "exportable": false,
+                "created_at": "2025-01-01T00:00:00Z"
+            })
+        };
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(key(1, "ed25519"));
+        mock.push_data(key(2, "secp256k1"));
+        let signatures: Vec<_> = [1u128, 2, 2, 1]
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| {
+                serde_json::json!({
+                    "key_id": Uuid::from_u128(id),
+                    "signature": BASE64.encode([i as u8]),
+                    "public_key": "pk"
+                })
+            })
+            .collect();
+        mock.push_data(serde_json::json!({ "signatures": signatures }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let item = |id: u128, data: &[u8]| BatchSignItem {
+            key_id: Uuid::from_u128(id).into(),
+            data: data.to_vec(),
+            prehashed: false,
+        };
+        let items = vec![
+            item(1, b"a"),
+            item(1, b"a"),
+            item(2, b"a"),
+            item(2, b"a"),
+            item(1, b"b"),
+        ];
+        let batch = client
+            .sign()
+            .sign_batch_with_options(items, &BatchOptions { dedup: true })
+            .await
+            .unwrap();
+
+        let signatures: Vec<_> = batch
+            .results
+            .iter()
+            .map(|result| result.as_ref().unwrap().signature.clone())
+            .collect();
+        assert_eq!(
+            signatures,
+            vec![vec![0], vec![0], vec![1], vec![2], vec![3]]
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(mock.requests()[2].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["requests"].as_array().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sign_batch_concurrent_stream() {
+        let mock = crate::testing::MockTransport::new();
+        for i in 1..=3u8 {
+            mock.push_data(serde_json::json!({
+                "signature": BASE64.encode([i]),
+                "public_key": "pk"
+            }));
+        }
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        for i in 1..=3u128 {
+            tx.unbounded_send(SignRequest::new(Uuid::from_u128(i), b"tx".to_vec()))
+                .unwrap();
+        }
+        drop(tx);
+
+        let batch = client.sign().sign_batch_concurrent_stream(rx, 1).await;
+        let responses = batch.into_result().unwrap();
+        assert_eq!(responses.len(), 3);
+        for (i, response) in (1..=3u8).zip(&responses) {
+            assert_eq!(response.signature, vec![i]);
+            assert_eq!(response.key_id, Uuid::from_u128(i.into()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_request_timed_counts_retries() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_error(POPSignerError::Timeout);
+        let body = serde_json::json!({
+            "data": { "signature": BASE64.encode([1]), "public_key": "pk" }
+        });
+        mock.push_response(
+            crate::transport::Response::json(200, &body).with_header("x-request-id", "req_2"),
+        );
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .retry(crate::RetryPolicy {
+                max_attempts: 3,
+                ..crate::RetryPolicy::none()
+            })
+            .build()
+            .unwrap();
+
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec());
+        let timed = client.sign().sign_request_timed(&request).await.unwrap();
+        assert_eq!(timed.value.signature, vec![1]);
+        assert_eq!(timed.meta.attempts, 2);
+        assert_eq!(timed.meta.request_id.as_deref(), Some("req_2"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_cache() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({ "valid": true }));
+        mock.push_data(serde_json::json!({ "valid": false }));
+        mock.push_data(serde_json::json!({
+            "id": Uuid::nil(),
+            "name": "k",
+            "namespace_id": Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": "ed25519",
+            "exportable": false,
+            "state": "disabled",
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        mock.push_data(serde_json::json!({ "valid": true }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .verify_cache(10)
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(Uuid::nil());
+        let sign = client.sign();
+        assert!(sign.verify(&key_id, b"tx", b"good", false).await.unwrap());
+        assert!(sign.verify(&key_id, b"tx", b"good", false).await.unwrap());
+        // Negative results are cached too.
+        assert!(!sign.verify(&key_id, b"tx", b"bad", false).await.unwrap());
+        assert!(!sign.verify(&key_id, b"tx", b"bad", false).await.unwrap());
+        assert_eq!(mock.requests().len(), 2);
+
+        client.keys().disable(&key_id).await.unwrap();
+        assert!(sign.verify(&key_id, b"tx", b"good", false).await.unwrap());
+        assert_eq!(mock.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sign_request_deadline() {
+        let mock = crate::testing::MockTransport::new();
+        let body = serde_json::json!({
+            "error": { "code": "rate_limited", "message": "slow down" }
+        });
+        mock.push_response(
+            crate::transport::Response::json(429, &body).with_header("retry-after", "60"),
+        );
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .retry(crate::RetryPolicy {
+                max_attempts: 3,
+                ..crate::RetryPolicy::none()
+            })
+            .build()
+            .unwrap();
+
+        // Already past: nothing is sent.
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec()).deadline(Instant::now());
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::Timeout));
+        assert!(mock.requests().is_empty());
+
+        // Waiting out the Retry-After would overrun the deadline.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec()).deadline(deadline);
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert!(matches!(err, POPSignerError::Timeout));
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sign_request_retry_overrides_client_policy() {
+        let mock = crate::testing::MockTransport::new();
+        let body = serde_json::json!({
+            "error": { "code": "service_unavailable", "message": "busy" }
+        });
+        mock.push_response(crate::transport::Response::json(503, &body));
+        mock.push_data(serde_json::json!({ "signature": "AQ==", "public_key": "pk" }));
+        mock.push_response(crate::transport::Response::json(503, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .retry(crate::RetryPolicy {
+                max_attempts: 5,
+                ..crate::RetryPolicy::none()
+            })
+            .build()
+            .unwrap();
+
+        let policy = crate::RetryPolicy {
+            max_attempts: 2,
+            ..crate::RetryPolicy::none()
+        };
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec()).retry(policy);
+        assert!(client.sign().sign_request(&request).await.is_ok());
+        assert_eq!(mock.requests().len(), 2);
+
+        // The per-call policy allows no retry, despite the client's five.
+        let request = request.retry(crate::RetryPolicy::none());
+        let err = client.sign().sign_request(&request).await.unwrap_err();
+        assert_eq!(err.status_code(), Some(503));
+        assert_eq!(mock.requests().len(), 3);
+    }
 }
@@ -0,0 +1,145 @@
+//! A trait over the client's signing and key operations.
+//!
+//! Code that depends on [`Signer`] instead of [`Client`] can be tested with
+//! a hand-written fake or a `mockall` mock, without a server or a
+//! [`MockTransport`](crate::testing::MockTransport). Not to be confused with
+//! [`celestia::Signer`](crate::celestia::Signer), which signs Celestia
+//! transactions with one fixed key.
+//!
+//! With `mockall`, declare the mock with `mock!` in the test crate:
+//!
+//! ```rust,ignore
+//! mockall::mock! {
+//!     pub Signer {}
+//!
+//!     #[async_trait::async_trait]
+//!     impl popsigner::Signer for Signer {
+//!         async fn sign(&self, key_id: &KeyId, data: &[u8], prehashed: bool) -> Result<SignResponse>;
+//!         // ...the remaining methods
+//!     }
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use popsigner::{Client, KeyId, Signer};
+//!
+//! async fn approve(signer: &impl Signer, key_id: &KeyId) -> popsigner::Result<Vec<u8>> {
+//!     Ok(signer.sign(key_id, b"approved", false).await?.signature)
+//! }
+//!
+//! # async fn example() -> popsigner::Result<()> {
+//! let client = Client::new("psk_live_xxxxx");
+//! let key_id: KeyId = "...".parse()?;
+//! let signature = approve(&client, &key_id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::ids::{KeyId, NamespaceId};
+use crate::types::{BatchResult, BatchSignItem, Key, SignRequest, SignResponse};
+use async_trait::async_trait;
+
+/// Signing and key operations, implemented by [`Client`].
+///
+/// Each method behaves like the [`Client`] operation it is named after.
+/// On a `Client` itself, the inherent [`Client::sign`] shadows
+/// [`Signer::sign`]; call it as `Signer::sign(&client, ...)` or through a
+/// generic or `dyn Signer`.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Signer: Send + Sync {
+    /// See [`SignClient::sign`](crate::sign::SignClient::sign).
+    async fn sign(&self, key_id: &KeyId, data: &[u8], prehashed: bool) -> Result<SignResponse>;
+
+    /// See [`SignClient::sign_request`](crate::sign::SignClient::sign_request).
+    async fn sign_request(&self, request: &SignRequest) -> Result<SignResponse>;
+
+    /// See [`SignClient::sign_batch`](crate::sign::SignClient::sign_batch).
+    async fn sign_batch(&self, requests: Vec<BatchSignItem>) -> Result<BatchResult>;
+
+    /// See [`SignClient::verify`](crate::sign::SignClient::verify).
+    async fn verify(
+        &self,
+        key_id: &KeyId,
+        data: &[u8],
+        signature: &[u8],
+        prehashed: bool,
+    ) -> Result<bool>;
+
+    /// See [`KeysClient::get`](crate::keys::KeysClient::get).
+    async fn get_key(&self, key_id: &KeyId) -> Result<Key>;
+
+    /// See [`KeysClient::list`](crate::keys::KeysClient::list).
+    async fn list_keys(&self, namespace_id: Option<&NamespaceId>) -> Result<Vec<Key>>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for Client {
+    async fn sign(&self, key_id: &KeyId, data: &[u8], prehashed: bool) -> Result<SignResponse> {
+        self.sign().sign(key_id, data, prehashed).await
+    }
+
+    async fn sign_request(&self, request: &SignRequest) -> Result<SignResponse> {
+        self.sign().sign_request(request).await
+    }
+
+    async fn sign_batch(&self, requests: Vec<BatchSignItem>) -> Result<BatchResult> {
+        self.sign().sign_batch(requests).await
+    }
+
+    async fn verify(
+        &self,
+        key_id: &KeyId,
+        data: &[u8],
+        signature: &[u8],
+        prehashed: bool,
+    ) -> Result<bool> {
+        self.sign().verify(key_id, data, signature, prehashed).await
+    }
+
+    async fn get_key(&self, key_id: &KeyId) -> Result<Key> {
+        self.keys().get(key_id).await
+    }
+
+    async fn list_keys(&self, namespace_id: Option<&NamespaceId>) -> Result<Vec<Key>> {
+        self.keys().list(namespace_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use uuid::Uuid;
+
+    async fn signature_len(signer: &dyn Signer, key_id: &KeyId) -> Result<usize> {
+        Ok(signer.sign(key_id, b"tx", false).await?.signature.len())
+    }
+
+    #[tokio::test]
+    async fn test_client_implements_signer() {
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "signature": BASE64.encode([1, 2, 3]),
+            "public_key": "pk"
+        }));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let key_id = KeyId::from(Uuid::nil());
+        assert_eq!(signature_len(&client, &key_id).await.unwrap(), 3);
+        assert_eq!(
+            mock.requests()[0].url.path(),
+            format!("/v1/keys/{}/sign", key_id)
+        );
+    }
+}
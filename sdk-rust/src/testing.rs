@@ -0,0 +1,133 @@
+//! Test helpers for code built on the SDK.
+//!
+//! [`MockTransport`] replaces the HTTP layer with a queue of canned
+//! responses, so tests run without a server.
+//!
+//! # Example
+//!
+//! ```rust
+//! use popsigner::testing::MockTransport;
+//! use popsigner::transport::Response;
+//! use popsigner::Client;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> popsigner::Result<()> {
+//! let mock = MockTransport::new();
+//! mock.push_response(Response::json(200, &serde_json::json!({ "data": [] })));
+//!
+//! let client = Client::builder()
+//!     .api_key("test_key")
+//!     .transport(mock.clone())
+//!     .build()?;
+//!
+//! let keys = client.keys().list(None).await?;
+//! assert!(keys.is_empty());
+//! assert_eq!(mock.requests()[0].url.path(), "/v1/keys");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{POPSignerError, Result};
+use crate::transport::{Request, Response, Transport};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// A [`Transport`] that replays queued responses and records requests.
+///
+/// Clones share the same queue and request log, so keep a clone to make
+/// assertions after handing one to the client.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: VecDeque<Result<Response>>,
+    requests: Vec<Request>,
+}
+
+impl MockTransport {
+    /// Create a mock with an empty response queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response for the next request.
+    pub fn push_response(&self, response: Response) {
+        self.state().responses.push_back(Ok(response));
+    }
+
+    /// Queue a `{"data": ...}` response with status 200 for the next request.
+    pub fn push_data(&self, data: serde_json::Value) {
+        self.push_response(Response::json(200, &serde_json::json!({ "data": data })));
+    }
+
+    /// Queue a transport-level error, such as a timeout, for the next request.
+    pub fn push_error(&self, error: POPSignerError) {
+        self.state().responses.push_back(Err(error));
+    }
+
+    /// All requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state().requests.clone()
+    }
+
+    /// The most recent request, if any.
+    pub fn last_request(&self) -> Option<Request> {
+        self.state().requests.last().cloned()
+    }
+
+    /// Number of queued responses not yet consumed.
+    pub fn pending(&self) -> usize {
+        self.state().responses.len()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for MockTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let mut state = self.state();
+        let description = format!("{} {}", request.method, request.url.path());
+        state.requests.push(request);
+        state.responses.pop_front().unwrap_or_else(|| {
+            Err(POPSignerError::InvalidRequest(format!(
+                "MockTransport has no response queued for {}",
+                description
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_mock_transport_records_requests() {
+        let mock = MockTransport::new();
+        mock.push_data(serde_json::json!([]));
+
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        client.keys().list(None).await.unwrap();
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.method, reqwest::Method::GET);
+        assert_eq!(request.headers["authorization"], "Bearer test_key");
+
+        // Nothing left in the queue.
+        assert!(client.keys().list(None).await.is_err());
+        assert_eq!(mock.requests().len(), 2);
+    }
+}
@@ -0,0 +1,91 @@
+//! Optional `tracing` instrumentation.
+//!
+//! Enabled by the `tracing` feature. Without it every function here has an
+//! empty body and compiles away.
+
+use crate::error::POPSignerError;
+use std::time::Duration;
+
+/// Span covering one logical request, including all retry attempts.
+#[cfg(feature = "tracing")]
+pub(crate) fn request_span(method: &reqwest::Method, path: &str) -> tracing::Span {
+    tracing::info_span!(
+        "popsigner.request",
+        http.method = %method,
+        http.path = path,
+        http.status_code = tracing::field::Empty,
+        error = tracing::field::Empty,
+    )
+}
+
+/// Emit a `debug` event when an attempt is sent.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn on_request_start(attempt: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(attempt, "sending request");
+}
+
+/// Record the response status and emit `info` on success or `warn` otherwise.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn on_response(status: u16, elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        tracing::Span::current().record("http.status_code", status);
+        if status < 400 {
+            tracing::info!(status, elapsed_ms, "request completed");
+        } else {
+            tracing::warn!(status, elapsed_ms, "request failed");
+        }
+    }
+}
+
+/// Emit a `warn` event when a failed attempt is retried after `backoff`.
+///
+/// `retry_after` is true if the server's `Retry-After` replaced the
+/// computed backoff.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn on_retry(attempt: u32, err: &POPSignerError, backoff: Duration, retry_after: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        attempt,
+        category = %err.category(),
+        backoff_ms = backoff.as_millis() as u64,
+        retry_after,
+        "retrying request"
+    );
+}
+
+/// Emit an `error` event when the retry policy gives up.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn on_retries_exhausted(attempts: u32, elapsed: Duration, err: &POPSignerError) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(
+        attempts,
+        elapsed_ms = elapsed.as_millis() as u64,
+        category = %err.category(),
+        error = %err,
+        "retries exhausted"
+    );
+}
+
+/// Record a final error on the current request span.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn record_error(err: &POPSignerError) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("error", tracing::field::display(err));
+}
+
+/// Record the number of items in a batch on the current span.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn record_count(count: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("count", count);
+}
+
+/// Emit a `warn` event for a key left out of a JWKS export.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn skipped_jwk(key_id: &crate::KeyId, err: &POPSignerError) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(key_id = %key_id, error = %err, "skipping key with a malformed public key");
+}
@@ -0,0 +1,223 @@
+//! Pluggable HTTP transport.
+//!
+//! [`Client`](crate::Client) builds a [`Request`] for every attempt and hands
+//! it to a [`Transport`]. The default, [`ReqwestTransport`], sends it with
+//! `reqwest`; swap it with
+//! [`ClientBuilder::transport`](crate::ClientBuilder::transport), for example
+//! to use [`MockTransport`](crate::testing::MockTransport) in tests.
+//!
+//! [`Interceptor`]s registered with
+//! [`ClientBuilder::add_interceptor`](crate::ClientBuilder::add_interceptor)
+//! can rewrite each request just before it reaches the transport.
+
+use crate::error::{POPSignerError, Result};
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Url};
+use std::time::Duration;
+
+/// An HTTP request ready to be sent.
+///
+/// Authentication and content-type headers have already been applied.
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// HTTP method.
+    pub method: Method,
+    /// Absolute request URL.
+    pub url: Url,
+    /// Request headers.
+    pub headers: HeaderMap,
+    /// Serialized JSON body, if any.
+    pub body: Option<Vec<u8>>,
+    /// Per-request timeout, if the client has one.
+    pub timeout: Option<Duration>,
+    /// Largest response body to read, in bytes. Transports should stop
+    /// reading and return [`POPSignerError::ResponseTooLarge`] past it.
+    pub max_response_bytes: Option<usize>,
+}
+
+impl Request {
+    /// Decode the body as JSON, if present and valid.
+    pub fn body_json(&self) -> Option<serde_json::Value> {
+        self.body
+            .as_deref()
+            .and_then(|body| serde_json::from_slice(body).ok())
+    }
+}
+
+/// An HTTP response with a fully read body.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// Raw response body.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Create a response with no headers.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Create a response with a JSON body.
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self::new(status, body.to_string())
+    }
+
+    /// Add a header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` or `value` is not a valid header.
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers
+            .insert(name, value.parse().expect("invalid header value"));
+        self
+    }
+
+    /// Returns true for a 2xx status.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends requests on behalf of the client.
+///
+/// Implementations only move bytes: retries, rate limiting and error
+/// mapping are handled by the client. Transport failures should be returned
+/// as [`POPSignerError::Timeout`](crate::POPSignerError::Timeout) or
+/// [`POPSignerError::Connect`](crate::POPSignerError::Connect) where
+/// applicable so they are retried.
+///
+/// On `wasm32` the returned future is not required to be `Send`, since
+/// browser fetch futures are not.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Transport: Send + Sync {
+    /// Send `request` and return the response, whatever its status.
+    async fn execute(&self, request: Request) -> Result<Response>;
+}
+
+/// Rewrites outgoing requests just before they are sent.
+///
+/// Interceptors run on every attempt, retries included, in the order they
+/// were added, each seeing the changes made by the ones before it. They run
+/// after the client has applied authentication, default and content
+/// headers, and after the body has been compressed, so `request` is exactly
+/// what the transport will send. A request that is never sent, e.g.
+/// because the circuit breaker is open, is not intercepted.
+///
+/// # Example
+///
+/// ```rust
+/// use popsigner::transport::{Interceptor, Request};
+///
+/// struct BodyLength;
+///
+/// #[async_trait::async_trait]
+/// impl Interceptor for BodyLength {
+///     async fn intercept(&self, request: &mut Request) {
+///         let len = request.body.as_ref().map_or(0, Vec::len);
+///         request.headers.insert("x-body-length", len.into());
+///     }
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Interceptor: Send + Sync {
+    /// Modify `request` before it is sent.
+    async fn intercept(&self, request: &mut Request);
+}
+
+/// The default transport, backed by a `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    http: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Wrap an existing `reqwest::Client`.
+    pub fn new(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for ReqwestTransport {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let mut builder = self
+            .http
+            .request(request.method, request.url)
+            .headers(request.headers);
+        // reqwest's fetch backend has no timeouts; the browser's apply.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = read_body(response, request.max_response_bytes).await?;
+
+        Ok(Response {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Read the whole body, failing as soon as it grows past `limit`.
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_body(mut response: reqwest::Response, limit: Option<usize>) -> Result<Vec<u8>> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let too_large = POPSignerError::ResponseTooLarge { limit };
+    if response
+        .content_length()
+        .is_some_and(|len| len > limit as u64)
+    {
+        return Err(too_large);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// The fetch backend only reads whole bodies; the client checks the size
+/// afterwards.
+#[cfg(target_arch = "wasm32")]
+async fn read_body(response: reqwest::Response, _limit: Option<usize>) -> Result<Vec<u8>> {
+    Ok(response.bytes().await?.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_helpers() {
+        let response = Response::json(201, &serde_json::json!({ "data": 1 }))
+            .with_header("x-request-id", "req_1");
+        assert!(response.is_success());
+        assert_eq!(response.headers["x-request-id"], "req_1");
+        assert!(!Response::new(404, "").is_success());
+    }
+}
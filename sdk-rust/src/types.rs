@@ -1,20 +1,265 @@
 //! Type definitions for the POPSigner SDK.
 //!
 //! This module contains all the request and response types used by the SDK.
+//!
+//! # Forward compatibility
+//!
+//! Response types ignore fields they do not know and default optional
+//! fields the server leaves out, so a server release that adds fields does
+//! not break clients pinned to an older SDK. New fields are only surfaced
+//! by upgrading. To fail fast on unknown fields instead, e.g. in a
+//! contract test, use
+//! [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema).
 
+use crate::client::{is_key_not_found, Client};
+use crate::crypto::{PublicKey, Signature};
+use crate::error::{POPSignerError, Result};
+use crate::ids::{KeyId, NamespaceId};
+use crate::retry::RetryPolicy;
+use crate::runtime::Instant;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Signing algorithm of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SigningAlgorithm {
+    /// EdDSA over Curve25519.
+    #[serde(rename = "ed25519")]
+    Ed25519,
+    /// Ed25519ph (RFC 8032): Ed25519 over a SHA-512 prehash, with an
+    /// optional context.
+    #[serde(rename = "ed25519ph")]
+    Ed25519ph,
+    /// Ed25519ctx (RFC 8032): Ed25519 with a context.
+    #[serde(rename = "ed25519ctx")]
+    Ed25519ctx,
+    /// ECDSA over secp256k1 (Cosmos, Celestia, Ethereum).
+    #[serde(rename = "secp256k1")]
+    EcdsaSecp256k1,
+    /// ECDSA over NIST P-256.
+    #[serde(rename = "secp256r1", alias = "p256")]
+    EcdsaP256,
+    /// RSASSA-PKCS1-v1_5 with SHA-256.
+    #[serde(rename = "rsa-pkcs1-sha256")]
+    RsaPkcs1Sha256,
+    /// RSASSA-PSS with SHA-256.
+    #[serde(rename = "rsa-pss-sha256")]
+    RsaPssSha256,
+}
+
+impl SigningAlgorithm {
+    /// Returns the algorithm name used by the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SigningAlgorithm::Ed25519 => "ed25519",
+            SigningAlgorithm::Ed25519ph => "ed25519ph",
+            SigningAlgorithm::Ed25519ctx => "ed25519ctx",
+            SigningAlgorithm::EcdsaSecp256k1 => "secp256k1",
+            SigningAlgorithm::EcdsaP256 => "secp256r1",
+            SigningAlgorithm::RsaPkcs1Sha256 => "rsa-pkcs1-sha256",
+            SigningAlgorithm::RsaPssSha256 => "rsa-pss-sha256",
+        }
+    }
+
+    /// Returns the JWS `alg` name (RFC 7518, RFC 8037, RFC 8812), or `None`
+    /// for Ed25519ph and Ed25519ctx, which JOSE does not define.
+    pub fn jws_alg(&self) -> Option<&'static str> {
+        match self {
+            SigningAlgorithm::Ed25519 => Some("EdDSA"),
+            SigningAlgorithm::Ed25519ph | SigningAlgorithm::Ed25519ctx => None,
+            SigningAlgorithm::EcdsaSecp256k1 => Some("ES256K"),
+            SigningAlgorithm::EcdsaP256 => Some("ES256"),
+            SigningAlgorithm::RsaPkcs1Sha256 => Some("RS256"),
+            SigningAlgorithm::RsaPssSha256 => Some("PS256"),
+        }
+    }
+
+    /// Returns the COSE `alg` value (RFC 8152, RFC 8812), or `None` for
+    /// Ed25519ph and Ed25519ctx, which COSE does not define.
+    pub fn cose_alg(&self) -> Option<i64> {
+        match self {
+            SigningAlgorithm::Ed25519 => Some(-8),
+            SigningAlgorithm::Ed25519ph | SigningAlgorithm::Ed25519ctx => None,
+            SigningAlgorithm::EcdsaSecp256k1 => Some(-47),
+            SigningAlgorithm::EcdsaP256 => Some(-7),
+            SigningAlgorithm::RsaPkcs1Sha256 => Some(-257),
+            SigningAlgorithm::RsaPssSha256 => Some(-37),
+        }
+    }
+
+    /// Returns true for Ed25519 algorithms, whose keys are all the same
+    /// 32-byte Curve25519 points.
+    pub fn is_ed25519(&self) -> bool {
+        matches!(
+            self,
+            SigningAlgorithm::Ed25519 | SigningAlgorithm::Ed25519ph | SigningAlgorithm::Ed25519ctx
+        )
+    }
+
+    /// Returns true for algorithms that take a signing context
+    /// ([`SignRequest::context`]).
+    pub fn supports_context(&self) -> bool {
+        matches!(
+            self,
+            SigningAlgorithm::Ed25519ph | SigningAlgorithm::Ed25519ctx
+        )
+    }
+
+    /// Returns true for ECDSA algorithms.
+    pub fn is_ecdsa(&self) -> bool {
+        matches!(
+            self,
+            SigningAlgorithm::EcdsaSecp256k1 | SigningAlgorithm::EcdsaP256
+        )
+    }
+
+    /// Returns true if signing the same input always yields the same
+    /// signature: Ed25519 and RSA-PKCS1. ECDSA (unless RFC 6979 is
+    /// requested per call) and RSA-PSS use a random nonce or salt.
+    pub fn is_deterministic(&self) -> bool {
+        self.is_ed25519() || *self == SigningAlgorithm::RsaPkcs1Sha256
+    }
+}
+
+impl FromStr for SigningAlgorithm {
+    type Err = POPSignerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ed25519" => Ok(SigningAlgorithm::Ed25519),
+            "ed25519ph" => Ok(SigningAlgorithm::Ed25519ph),
+            "ed25519ctx" => Ok(SigningAlgorithm::Ed25519ctx),
+            "secp256k1" => Ok(SigningAlgorithm::EcdsaSecp256k1),
+            "secp256r1" | "p256" => Ok(SigningAlgorithm::EcdsaP256),
+            "rsa-pkcs1-sha256" => Ok(SigningAlgorithm::RsaPkcs1Sha256),
+            "rsa-pss-sha256" => Ok(SigningAlgorithm::RsaPssSha256),
+            other => Err(POPSignerError::InvalidRequest(format!(
+                "unsupported signing algorithm: {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SigningAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hash function used to produce a digest passed to
+/// [`SignClient::sign_digest`](crate::sign::SignClient::sign_digest).
+///
+/// Sent with prehashed requests as `digest_algorithm` (`sha256`, `sha384`,
+/// `sha512`, `keccak256`), so the server can tell digests of the same
+/// length apart and, for RSA PKCS#1 v1.5, encode the right hash OID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestAlgorithm {
+    /// SHA-256 (32 bytes).
+    Sha256,
+    /// SHA-384 (48 bytes).
+    Sha384,
+    /// SHA-512 (64 bytes).
+    Sha512,
+    /// Keccak-256, as used by Ethereum (32 bytes).
+    Keccak256,
+}
+
+impl DigestAlgorithm {
+    /// Length of a digest produced by this algorithm, in bytes.
+    pub fn digest_len(&self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Keccak256 => 32,
+            DigestAlgorithm::Sha384 => 48,
+            DigestAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha384 => "SHA-384",
+            DigestAlgorithm::Sha512 => "SHA-512",
+            DigestAlgorithm::Keccak256 => "Keccak-256",
+        })
+    }
+}
+
+/// Encoding of an ECDSA signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    /// Fixed-size 64-byte `r || s`.
+    Raw,
+    /// ASN.1 DER `SEQUENCE { r INTEGER, s INTEGER }`.
+    Der,
+}
+
+impl fmt::Display for SignatureEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignatureEncoding::Raw => "raw",
+            SignatureEncoding::Der => "DER",
+        })
+    }
+}
+
+/// Scheduling hint for a request; see [`SignRequest::priority`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Interactive, user-facing work.
+    High,
+    /// The default.
+    #[default]
+    Normal,
+    /// Background work that can wait, such as bulk jobs.
+    Low,
+}
+
+impl Priority {
+    /// Returns the name sent in the priority header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::High => "high",
+            Priority::Normal => "normal",
+            Priority::Low => "low",
+        }
+    }
+
+    /// Position in the rate limiter's queue, highest priority first.
+    pub(crate) fn rank(self) -> usize {
+        match self {
+            Priority::High => 0,
+            Priority::Normal => 1,
+            Priority::Low => 2,
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// A cryptographic key.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Key {
     /// Unique key identifier.
-    pub id: Uuid,
+    pub id: KeyId,
     /// Human-readable key name.
     pub name: String,
     /// Namespace the key belongs to.
-    pub namespace_id: Uuid,
+    pub namespace_id: NamespaceId,
     /// Base64-encoded public key.
     pub public_key: String,
     /// Bech32-encoded address (for Cosmos/Celestia).
@@ -23,11 +268,141 @@ pub struct Key {
     pub algorithm: String,
     /// Whether the key can be exported.
     pub exportable: bool,
+    /// Lifecycle state; [`KeyState::Active`] if the server omits it.
+    #[serde(default)]
+    pub state: KeyState,
+    /// What the key may be used for.
+    #[serde(default)]
+    pub usage: KeyUsage,
+    /// Optional description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Labels for organizing and selecting keys.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     /// Optional metadata.
     #[serde(default)]
     pub metadata: Option<HashMap<String, String>>,
     /// Creation timestamp.
     pub created_at: String,
+    /// When the key was last rotated, if ever.
+    #[serde(default)]
+    pub rotated_at: Option<String>,
+}
+
+impl Key {
+    /// Decode the key's public key for local verification.
+    pub fn to_public_key(&self) -> Result<PublicKey> {
+        PublicKey::from_base64(self.algorithm.parse()?, &self.public_key)
+    }
+}
+
+/// Selects keys by their labels.
+///
+/// A key matches if it carries every label in the selector with the same
+/// value; an empty selector matches every key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelSelector {
+    labels: BTreeMap<String, String>,
+}
+
+impl LabelSelector {
+    /// Create a selector that matches every key.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the label `name` to have `value`.
+    pub fn label(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns true if the selector matches every key.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns true if `key` carries every label of the selector.
+    pub fn matches(&self, key: &Key) -> bool {
+        self.labels
+            .iter()
+            .all(|(name, value)| key.labels.get(name) == Some(value))
+    }
+
+    /// The selector in the API's `name=value,name=value` form.
+    pub(crate) fn to_query(&self) -> String {
+        self.labels
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl From<BTreeMap<String, String>> for LabelSelector {
+    fn from(labels: BTreeMap<String, String>) -> Self {
+        Self { labels }
+    }
+}
+
+/// What a [`Key`] may be used for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyUsage {
+    /// Signing transactions and messages.
+    #[default]
+    Signing,
+    /// Challenge-response authentication (e.g. WebAuthn, mTLS). Only
+    /// Ed25519 and P-256 keys support it.
+    Authentication,
+}
+
+impl KeyUsage {
+    /// Returns the usage name used by the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyUsage::Signing => "signing",
+            KeyUsage::Authentication => "authentication",
+        }
+    }
+}
+
+/// Lifecycle state of a [`Key`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyState {
+    /// The key can sign.
+    #[default]
+    Active,
+    /// Signing is refused until the key is enabled again.
+    Disabled,
+    /// A new key version is being created.
+    PendingRotation,
+    /// The key material is gone; the key can never sign again.
+    Destroyed,
+}
+
+impl KeyState {
+    /// Returns true if keys in this state can sign.
+    pub fn can_sign(&self) -> bool {
+        matches!(self, KeyState::Active | KeyState::PendingRotation)
+    }
+}
+
+/// One version of a key, as listed by
+/// [`NamespaceClient::list_key_versions`](crate::namespace::NamespaceClient::list_key_versions).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeyVersion {
+    /// Version number, starting at 1.
+    pub version: u32,
+    /// Lifecycle state of this version.
+    #[serde(default)]
+    pub state: KeyState,
+    /// Base64-encoded public key of this version.
+    pub public_key: String,
+    /// Creation timestamp.
+    pub created_at: String,
 }
 
 /// Request to create a key.
@@ -36,7 +411,7 @@ pub struct CreateKeyRequest {
     /// Key name (must be unique within namespace).
     pub name: String,
     /// Namespace ID for the key.
-    pub namespace_id: Uuid,
+    pub namespace_id: NamespaceId,
     /// Key algorithm (default: "secp256k1").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub algorithm: Option<String>,
@@ -46,6 +421,126 @@ pub struct CreateKeyRequest {
     /// Optional metadata.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    /// Key usage (default: signing).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<KeyUsage>,
+    /// Optional description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Optional labels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<String, String>>,
+}
+
+/// Specification of one key for
+/// [`KeysClient::create_keys`](crate::keys::KeysClient::create_keys).
+#[derive(Debug, Clone)]
+pub struct KeySpec {
+    /// Key name (must be unique within namespace).
+    pub name: String,
+    /// Key algorithm (default: secp256k1).
+    pub algorithm: SigningAlgorithm,
+    /// Key usage (default: signing).
+    pub usage: KeyUsage,
+    /// Whether the key can be exported (default: false).
+    pub exportable: Option<bool>,
+    /// Optional description.
+    pub description: Option<String>,
+    /// Labels to attach to the key.
+    pub labels: BTreeMap<String, String>,
+    /// Optional metadata.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl Default for KeySpec {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            algorithm: SigningAlgorithm::EcdsaSecp256k1,
+            usage: KeyUsage::default(),
+            exportable: None,
+            description: None,
+            labels: BTreeMap::new(),
+            metadata: None,
+        }
+    }
+}
+
+impl KeySpec {
+    /// Create a spec for a key named `name` with default settings.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the key algorithm.
+    pub fn algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Set the key usage.
+    pub fn usage(mut self, usage: KeyUsage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Set the key description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a label.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Build the create request for this spec in `namespace_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the server would
+    /// reject the spec: an Ed25519 signing mode (ed25519ph, ed25519ctx)
+    /// given as the key algorithm, authentication usage on a key other
+    /// than Ed25519 or P-256, or an empty label name.
+    pub fn into_request(self, namespace_id: NamespaceId) -> Result<CreateKeyRequest> {
+        let invalid = |message: String| Err(POPSignerError::InvalidRequest(message));
+        match (self.algorithm, self.usage) {
+            (SigningAlgorithm::Ed25519ph | SigningAlgorithm::Ed25519ctx, _) => {
+                return invalid(format!(
+                    "{} is a signing mode, not a key algorithm; create an ed25519 key",
+                    self.algorithm.as_str()
+                ));
+            }
+            (SigningAlgorithm::Ed25519 | SigningAlgorithm::EcdsaP256, KeyUsage::Authentication)
+            | (_, KeyUsage::Signing) => {}
+            (algorithm, usage) => {
+                return invalid(format!(
+                    "{} keys do not support {} usage",
+                    algorithm.as_str(),
+                    usage.as_str()
+                ));
+            }
+        }
+        if self.labels.keys().any(|name| name.is_empty()) {
+            return invalid("label names must not be empty".to_string());
+        }
+
+        Ok(CreateKeyRequest {
+            name: self.name,
+            namespace_id,
+            algorithm: Some(self.algorithm.as_str().to_string()),
+            exportable: self.exportable,
+            metadata: self.metadata,
+            usage: Some(self.usage),
+            description: self.description,
+            labels: (!self.labels.is_empty()).then_some(self.labels),
+        })
+    }
 }
 
 /// Request to create multiple keys at once.
@@ -56,21 +551,692 @@ pub struct CreateBatchRequest {
     /// Number of keys to create.
     pub count: u32,
     /// Namespace ID for all keys.
-    pub namespace_id: Uuid,
+    pub namespace_id: NamespaceId,
     /// Whether keys can be exported (default: false).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exportable: Option<bool>,
 }
 
+/// Request to sign a single message.
+///
+/// # Example
+///
+/// ```rust
+/// use popsigner::SignRequest;
+/// use uuid::Uuid;
+///
+/// let request = SignRequest::new(Uuid::nil(), b"hello".to_vec()).prehashed(false);
+/// ```
+///
+/// # Serialization
+///
+/// Requests serialize with serde, e.g. to queue them on disk and replay
+/// them later. The field names are part of the stable format; `data` and
+/// `context` are base64. Fields added in later versions default when
+/// missing and unknown fields are ignored, so records outlive upgrades.
+/// The `deadline`, `retry` policy and `cancel` token only make sense in
+/// the process that set them and are not serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// Key ID to sign with.
+    pub key_id: KeyId,
+    /// Version of the key to sign with. When `None`, the latest version is
+    /// used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_version: Option<u32>,
+    /// Raw data to sign.
+    #[serde(with = "crate::base64_bytes")]
+    pub data: Vec<u8>,
+    /// Whether the data is already hashed.
+    #[serde(default)]
+    pub prehashed: bool,
+    /// For [`SigningAlgorithm::Ed25519ph`], hash data that is not
+    /// prehashed with SHA-512 before sending it, instead of rejecting it.
+    #[serde(default)]
+    pub prehash_locally: bool,
+    /// Hash that produced prehashed `data`, sent so the server knows it.
+    /// When `None`, the server assumes SHA-256.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_algorithm: Option<DigestAlgorithm>,
+    /// Sent as the `Idempotency-Key` header so the server applies the
+    /// request at most once. The same key is reused when the request is
+    /// retried. When `None`, no header is sent and retries may be counted
+    /// as separate requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Algorithm to sign with. When `None`, the key's primary algorithm is
+    /// used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<SigningAlgorithm>,
+    /// ECDSA normalization to request. Only valid for ECDSA algorithms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<SignatureOptions>,
+    /// Signature encoding to request. When `None`, the server's default is
+    /// used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<SignatureEncoding>,
+    /// Domain-separation context, at most 255 bytes. Only valid for
+    /// [`SigningAlgorithm::Ed25519ph`] and [`SigningAlgorithm::Ed25519ctx`].
+    #[serde(
+        default,
+        with = "crate::base64_bytes::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub context: Option<Vec<u8>>,
+    /// Ask for the recovery ID alongside the signature, as Ethereum needs.
+    /// Only valid for [`SigningAlgorithm::EcdsaSecp256k1`].
+    #[serde(default)]
+    pub recoverable: bool,
+    /// Timeout for this call, overriding the client's timeout. It applies
+    /// to each retry attempt separately, e.g. for slow HSM-backed keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<Duration>,
+    /// Point in time by which the whole call, retries included, must
+    /// finish.
+    #[serde(skip)]
+    pub deadline: Option<Instant>,
+    /// Retry policy for this call, replacing the client's.
+    #[serde(skip)]
+    pub retry: Option<RetryPolicy>,
+    /// Validate the request without signing; see
+    /// [`SignClient::validate`](crate::sign::SignClient::validate).
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Token that aborts the call when cancelled.
+    #[serde(skip)]
+    pub cancel: Option<CancellationToken>,
+    /// Scheduling hint; see [`priority`](Self::priority).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Extra headers for this request, e.g. a correlation ID. They override
+    /// client-wide defaults with the same name; reserved headers such as
+    /// `Authorization` are rejected.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+impl SignRequest {
+    /// Create a request to sign `data` with `key_id`.
+    pub fn new(key_id: impl Into<KeyId>, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key_id: key_id.into(),
+            key_version: None,
+            data: data.into(),
+            prehashed: false,
+            prehash_locally: false,
+            digest_algorithm: None,
+            idempotency_key: None,
+            algorithm: None,
+            options: None,
+            encoding: None,
+            context: None,
+            recoverable: false,
+            timeout: None,
+            deadline: None,
+            retry: None,
+            dry_run: false,
+            cancel: None,
+            priority: None,
+            headers: HashMap::new(),
+        }
+    }
+
+    /// Mark the data as already hashed (SHA-256).
+    pub fn prehashed(mut self, prehashed: bool) -> Self {
+        self.prehashed = prehashed;
+        self
+    }
+
+    /// With [`SigningAlgorithm::Ed25519ph`], hash the data with SHA-512
+    /// here when it is not [`prehashed`](Self::prehashed).
+    ///
+    /// Ed25519ph signs the 64-byte SHA-512 prehash of a message. By default
+    /// a request for it must carry that prehash and anything else is
+    /// rejected, so a message is never signed as if it were its own hash.
+    /// With this flag, only the prehash of the message is sent.
+    pub fn prehash_locally(mut self, prehash_locally: bool) -> Self {
+        self.prehash_locally = prehash_locally;
+        self
+    }
+
+    /// Name the hash that produced the prehashed `data`, e.g.
+    /// [`DigestAlgorithm::Keccak256`] for an Ethereum digest. Implies
+    /// [`prehashed`](Self::prehashed); the data length must match.
+    pub fn digest_algorithm(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.digest_algorithm = Some(algorithm);
+        self.prehashed = true;
+        self
+    }
+
+    /// Sign with a specific version of the key, e.g. one retired by a
+    /// rotation.
+    pub fn key_version(mut self, version: u32) -> Self {
+        self.key_version = Some(version);
+        self
+    }
+
+    /// Set the idempotency key for this request.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Sign with a specific algorithm instead of the key's primary one.
+    pub fn algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Request deterministic and/or low-S signatures.
+    pub fn options(mut self, options: SignatureOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Request a specific signature encoding.
+    pub fn encoding(mut self, encoding: SignatureEncoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// Mix a domain-separation context into the signature.
+    pub fn context(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Request a recoverable secp256k1 signature; see
+    /// [`SignResponse::recovery_id`].
+    pub fn recoverable(mut self, recoverable: bool) -> Self {
+        self.recoverable = recoverable;
+        self
+    }
+
+    /// Override the client's timeout for this call.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Finish the call, retries included, by `deadline`, e.g. the deadline
+    /// of the request being handled.
+    ///
+    /// Each attempt's timeout is capped to the time remaining. The call
+    /// fails with [`POPSignerError::Timeout`] once the deadline has passed,
+    /// or as soon as waiting for the next retry would overrun it.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Retry this call according to `policy` instead of the client's
+    /// [`retry`](crate::ClientBuilder::retry) policy, e.g. for one critical
+    /// operation.
+    ///
+    /// The policy replaces the client's as a whole; fields it leaves at
+    /// their defaults are not taken from the client. A
+    /// [`deadline`](Self::deadline) still bounds the call, retries
+    /// included.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Schedule this call ahead of or behind others.
+    ///
+    /// The priority is sent in the `X-Priority` header; whether the server
+    /// honors it depends on the deployment. With a client-side
+    /// [`rate_limit`](crate::ClientBuilder::rate_limit), waiting calls get
+    /// permits highest priority first instead of in arrival order. Retries
+    /// follow the same rules at every priority.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Mark the request as a dry run, to be checked with
+    /// [`SignClient::validate`](crate::sign::SignClient::validate).
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Abort the call with [`POPSignerError::Cancelled`] when `token` is
+    /// cancelled.
+    pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Add a header to this request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set a random (UUID v4) idempotency key for this request.
+    pub fn with_random_idempotency_key(self) -> Self {
+        self.idempotency_key(Uuid::new_v4().to_string())
+    }
+}
+
+/// ECDSA signature normalization options.
+///
+/// Both options only apply to ECDSA; setting them for
+/// [`SigningAlgorithm::Ed25519`] (always deterministic and canonical) or RSA
+/// is rejected with [`POPSignerError::InvalidRequest`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureOptions {
+    /// Derive the nonce per RFC 6979 so the same input always yields the
+    /// same signature.
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Normalize `s` to the lower half of the curve order (low-S), as most
+    /// chains require.
+    #[serde(default)]
+    pub canonical_s: bool,
+}
+
 /// Response from a sign operation.
-#[derive(Debug, Clone)]
+///
+/// Serializes with serde under stable field names, with `signature` and
+/// `context` in base64, e.g. for audit snapshots. Optional fields default
+/// when missing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignResponse {
     /// Key ID that was used for signing.
-    pub key_id: Uuid,
+    pub key_id: KeyId,
     /// Raw signature bytes.
+    #[serde(with = "crate::base64_bytes")]
     pub signature: Vec<u8>,
     /// Base64-encoded public key.
     pub public_key: String,
+    /// Normalization the server applied, if it reported it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<SignatureOptions>,
+    /// Algorithm that produced the signature, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<SigningAlgorithm>,
+    /// Context the signature was made with, echoed from the request. Pass
+    /// the same context when verifying.
+    #[serde(
+        default,
+        with = "crate::base64_bytes::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub context: Option<Vec<u8>>,
+    /// secp256k1 recovery ID (0 or 1), present when the request was
+    /// [`recoverable`](SignRequest::recoverable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_id: Option<u8>,
+    /// Warnings the server attached to the response; see [`ServerWarning`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ServerWarning>,
+}
+
+impl SignResponse {
+    /// The signature as lowercase hex.
+    pub fn signature_hex(&self) -> String {
+        hex::encode(&self.signature)
+    }
+
+    /// Re-encode the signature locally, e.g. DER to 64-byte raw for ECDSA.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::SigningError`] if the algorithm is unknown
+    /// or the conversion is impossible (such as DER-encoding an Ed25519
+    /// signature), and [`POPSignerError::Decode`] if the signature bytes are
+    /// malformed.
+    pub fn to_encoding(&self, encoding: SignatureEncoding) -> Result<Vec<u8>> {
+        let signature = match self.algorithm {
+            Some(algorithm) => Signature::with_algorithm(algorithm, self.signature.clone()),
+            None => Signature::new(self.signature.clone()),
+        };
+        signature.to_encoding(encoding)
+    }
+
+    /// Assemble the 65-byte Ethereum signature `r || s || v`, with
+    /// `v = 27 + recovery_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::SigningError`] if the response has no
+    /// recovery ID (the request was not
+    /// [`recoverable`](SignRequest::recoverable)) or is not a secp256k1
+    /// signature, and [`POPSignerError::Decode`] if the signature bytes are
+    /// malformed.
+    pub fn to_eth_signature(&self) -> Result<[u8; 65]> {
+        let recovery_id = self.recovery_id.ok_or_else(|| {
+            POPSignerError::SigningError(
+                "signature has no recovery ID; sign with SignRequest::recoverable".to_string(),
+            )
+        })?;
+        if recovery_id > 1 {
+            return Err(POPSignerError::SigningError(format!(
+                "recovery ID {} cannot be expressed as an Ethereum v",
+                recovery_id
+            )));
+        }
+        let algorithm = self.algorithm.unwrap_or(SigningAlgorithm::EcdsaSecp256k1);
+        if algorithm != SigningAlgorithm::EcdsaSecp256k1 {
+            return Err(POPSignerError::SigningError(format!(
+                "Ethereum signatures require secp256k1, not {}",
+                algorithm
+            )));
+        }
+
+        let raw = Signature::with_algorithm(algorithm, self.signature.clone())
+            .to_encoding(SignatureEncoding::Raw)?;
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&raw);
+        out[64] = 27 + recovery_id;
+        Ok(out)
+    }
+}
+
+/// How a request went, across all of its attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// Time from the first attempt to the final response, including retry
+    /// delays.
+    pub elapsed: Duration,
+    /// Number of requests sent, including retries.
+    pub attempts: u32,
+    /// `X-Request-ID` of the final response, if the server sent one.
+    pub request_id: Option<String>,
+    /// Warnings the server attached to the response.
+    pub warnings: Vec<ServerWarning>,
+}
+
+/// A warning the server attached to a successful response, e.g. that a key
+/// is nearing its rotation deadline.
+///
+/// Warnings never fail a call. They are passed to the hook set with
+/// [`ClientBuilder::on_warning`](crate::ClientBuilder::on_warning) and
+/// returned in [`ResponseMeta::warnings`] and [`SignResponse::warnings`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "WarningRepr")]
+pub struct ServerWarning {
+    /// Machine-readable code; empty if the server sent only a message.
+    pub code: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for ServerWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.code.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.code, self.message)
+        }
+    }
+}
+
+/// A warning as sent by the server: a bare message or a code and message.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WarningRepr {
+    Message(String),
+    Detailed {
+        #[serde(default)]
+        code: String,
+        message: String,
+    },
+}
+
+impl From<WarningRepr> for ServerWarning {
+    fn from(repr: WarningRepr) -> Self {
+        match repr {
+            WarningRepr::Message(message) => Self {
+                code: String::new(),
+                message,
+            },
+            WarningRepr::Detailed { code, message } => Self { code, message },
+        }
+    }
+}
+
+/// A response together with its [`ResponseMeta`], returned by the `*_timed`
+/// methods such as
+/// [`SignClient::sign_request_timed`](crate::sign::SignClient::sign_request_timed).
+#[derive(Debug, Clone)]
+pub struct Timed<T> {
+    /// The response.
+    pub value: T,
+    /// Timing and attempt count.
+    pub meta: ResponseMeta,
+}
+
+impl<T> Timed<T> {
+    /// Discard the metadata.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Apply `f` to the value, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Timed<U> {
+        Timed {
+            value: f(self.value),
+            meta: self.meta,
+        }
+    }
+}
+
+/// A signature to verify, for use with
+/// [`SignClient::verify_batch`](crate::sign::SignClient::verify_batch).
+///
+/// Serializes like [`SignRequest`], with `data`, `signature` and `context`
+/// in base64.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyRequest {
+    /// Key ID whose public key verifies the signature.
+    pub key_id: KeyId,
+    /// The signed data.
+    #[serde(with = "crate::base64_bytes")]
+    pub data: Vec<u8>,
+    /// The signature to check.
+    #[serde(with = "crate::base64_bytes")]
+    pub signature: Vec<u8>,
+    /// Whether the data is already hashed.
+    #[serde(default)]
+    pub prehashed: bool,
+    /// Context the signature was made with; see [`SignRequest::context`].
+    #[serde(
+        default,
+        with = "crate::base64_bytes::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub context: Option<Vec<u8>>,
+    /// Algorithm the signature was made with. Left to the server if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<SigningAlgorithm>,
+    /// Version of the key the signature was made with. When `None`, the
+    /// latest version is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_version: Option<u32>,
+}
+
+impl VerifyRequest {
+    /// Start building a request, with the algorithm optionally inferred
+    /// from the key; see [`VerifyRequestBuilder`].
+    pub fn builder() -> VerifyRequestBuilder {
+        VerifyRequestBuilder::default()
+    }
+
+    /// Create a request to verify `signature` over `data` with `key_id`.
+    pub fn new(
+        key_id: impl Into<KeyId>,
+        data: impl Into<Vec<u8>>,
+        signature: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            key_id: key_id.into(),
+            data: data.into(),
+            signature: signature.into(),
+            prehashed: false,
+            context: None,
+            algorithm: None,
+            key_version: None,
+        }
+    }
+
+    /// Mark the data as already hashed (SHA-256).
+    pub fn prehashed(mut self, prehashed: bool) -> Self {
+        self.prehashed = prehashed;
+        self
+    }
+
+    /// Set the context the signature was made with.
+    pub fn context(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Create a request from hex-encoded data and signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::Decode`] if either is not valid hex.
+    pub fn from_hex(key_id: impl Into<KeyId>, data: &str, signature: &str) -> Result<Self> {
+        let data = hex::decode(data)
+            .map_err(|e| POPSignerError::Decode(format!("invalid hex data: {}", e)))?;
+        let signature = hex::decode(signature)
+            .map_err(|e| POPSignerError::Decode(format!("invalid hex signature: {}", e)))?;
+        Ok(Self::new(key_id, data, signature))
+    }
+}
+
+/// Builder for a [`VerifyRequest`].
+///
+/// [`resolve`](Self::resolve) fills in the algorithm from the key's
+/// metadata when none was set, so callers need not track which algorithm
+/// each key uses.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use popsigner::{Client, KeyId, VerifyRequest};
+///
+/// # async fn example(signature: Vec<u8>) -> popsigner::Result<()> {
+/// let client = Client::new("psk_live_xxxxx");
+/// let key_id: KeyId = "...".parse()?;
+///
+/// let request = VerifyRequest::builder()
+///     .key(key_id)
+///     .message(b"tx".to_vec())
+///     .signature(signature)
+///     .resolve(&client)
+///     .await?;
+/// let results = client.sign().verify_batch([request]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VerifyRequestBuilder {
+    key_id: Option<KeyId>,
+    data: Vec<u8>,
+    signature: Option<Vec<u8>>,
+    prehashed: bool,
+    context: Option<Vec<u8>>,
+    algorithm: Option<SigningAlgorithm>,
+    key_version: Option<u32>,
+}
+
+impl VerifyRequestBuilder {
+    /// Set the key whose public key verifies the signature (required).
+    pub fn key(mut self, key_id: impl Into<KeyId>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+
+    /// Verify against a specific version of the key.
+    pub fn key_version(mut self, version: u32) -> Self {
+        self.key_version = Some(version);
+        self
+    }
+
+    /// Set the signed data.
+    pub fn message(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// Set the signature to check (required).
+    pub fn signature(mut self, signature: impl Into<Vec<u8>>) -> Self {
+        self.signature = Some(signature.into());
+        self
+    }
+
+    /// Set the algorithm explicitly, skipping inference.
+    pub fn algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Mark the data as already hashed (SHA-256).
+    pub fn prehashed(mut self, prehashed: bool) -> Self {
+        self.prehashed = prehashed;
+        self
+    }
+
+    /// Set the context the signature was made with.
+    pub fn context(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Build the request without inferring the algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`POPSignerError::InvalidRequest`] if the key or the
+    /// signature is missing.
+    pub fn build(self) -> Result<VerifyRequest> {
+        let missing = |what: &str| POPSignerError::InvalidRequest(format!("{} is required", what));
+        Ok(VerifyRequest {
+            key_id: self.key_id.ok_or_else(|| missing("key"))?,
+            data: self.data,
+            signature: self.signature.ok_or_else(|| missing("signature"))?,
+            prehashed: self.prehashed,
+            context: self.context,
+            algorithm: self.algorithm,
+            key_version: self.key_version,
+        })
+    }
+
+    /// Build the request, looking up the key's algorithm with `client` if
+    /// none was set. The lookup uses the client's key cache if enabled.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`build`](Self::build), plus
+    /// [`POPSignerError::KeyNotFound`] if the key does not exist and any
+    /// error from fetching it.
+    pub async fn resolve(self, client: &Client) -> Result<VerifyRequest> {
+        let mut request = self.build()?;
+        if request.algorithm.is_none() {
+            let key = client.keys().get(&request.key_id).await.map_err(|e| {
+                if is_key_not_found(&e) {
+                    POPSignerError::KeyNotFound(request.key_id.to_string())
+                } else {
+                    e
+                }
+            })?;
+            request.algorithm = Some(key.algorithm.parse()?);
+        }
+        Ok(request)
+    }
+}
+
+/// Outcome of verifying one signature in a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyResult {
+    /// Key ID used for verification.
+    pub key_id: KeyId,
+    /// Whether the signature is valid.
+    pub valid: bool,
 }
 
 /// Request to sign multiple messages in batch.
@@ -80,17 +1246,144 @@ pub struct BatchSignRequest {
     pub requests: Vec<BatchSignItem>,
 }
 
+impl IntoIterator for BatchSignRequest {
+    type Item = BatchSignItem;
+    type IntoIter = std::vec::IntoIter<BatchSignItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.requests.into_iter()
+    }
+}
+
+impl FromIterator<BatchSignItem> for BatchSignRequest {
+    fn from_iter<I: IntoIterator<Item = BatchSignItem>>(iter: I) -> Self {
+        Self {
+            requests: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Options for
+/// [`SignClient::sign_batch_with_options`](crate::sign::SignClient::sign_batch_with_options)
+/// and
+/// [`SignClient::sign_batch_chunked_with_options`](crate::sign::SignClient::sign_batch_chunked_with_options).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Sign each distinct item (key, data and prehashed flag) once and
+    /// copy its result to every duplicate, saving quota. Items for keys
+    /// whose algorithm is not
+    /// [deterministic](SigningAlgorithm::is_deterministic) are always
+    /// signed separately.
+    pub dedup: bool,
+}
+
 /// Single item in a batch sign request.
-#[derive(Debug, Clone)]
+///
+/// Serializes like [`SignRequest`], with `data` in base64.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchSignItem {
     /// Key ID to sign with.
-    pub key_id: Uuid,
+    pub key_id: KeyId,
     /// Raw data to sign.
+    #[serde(with = "crate::base64_bytes")]
     pub data: Vec<u8>,
     /// Whether the data is already hashed.
+    #[serde(default)]
     pub prehashed: bool,
 }
 
+/// Outcome of validating a sign request without signing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Key the request would sign with.
+    pub key_id: KeyId,
+    /// Problems found; empty if the request would be accepted.
+    #[serde(default)]
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns true if no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A single problem found by a dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Machine-readable code, e.g. `key_not_found` or `payload_too_large`.
+    pub code: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Per-item results of a batch operation, aligned by index with the input.
+#[derive(Debug)]
+pub struct BatchResult<T = SignResponse> {
+    /// One result per input item, in input order.
+    pub results: Vec<std::result::Result<T, POPSignerError>>,
+}
+
+impl<T> BatchResult<T> {
+    /// Number of items in the batch.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if the batch was empty.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns true if every item succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|r| r.is_ok())
+    }
+
+    /// Iterate over successful items with their input indices.
+    pub fn successes(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.as_ref().ok().map(|v| (i, v)))
+    }
+
+    /// Iterate over failed items with their input indices.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &POPSignerError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.as_ref().err().map(|e| (i, e)))
+    }
+
+    /// Input indices of the failed items, for retrying just those entries.
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.failures().map(|(i, _)| i).collect()
+    }
+
+    /// Convert into the successful values, or a
+    /// [`POPSignerError::BatchPartialFailure`] if any item failed.
+    pub fn into_result(self) -> Result<Vec<T>> {
+        let total = self.results.len();
+        let mut values = Vec::with_capacity(total);
+        let mut failures = Vec::new();
+
+        for (i, result) in self.results.into_iter().enumerate() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(e) => failures.push((i, e)),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(values)
+        } else {
+            Err(POPSignerError::BatchPartialFailure { failures, total })
+        }
+    }
+}
+
 /// An organization.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Organization {
@@ -106,11 +1399,142 @@ pub struct Organization {
     pub created_at: String,
 }
 
+/// Signing quota usage for an organization, from
+/// [`OrgsClient::get_quota`](crate::orgs::OrgsClient::get_quota).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct QuotaStatus {
+    /// Signing operations used in the current window.
+    pub used: u64,
+    /// Signing operations allowed per window, or `None` if unlimited.
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// When the current window resets.
+    pub resets_at: String,
+}
+
+/// Result of
+/// [`NamespaceClient::self_test`](crate::namespace::NamespaceClient::self_test).
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    /// Key the test signed with.
+    pub key_id: KeyId,
+    /// Every stage in order: `auth`, `dry_run`, `sign` and `verify`.
+    pub stages: Vec<SelfTestStage>,
+}
+
+/// One stage of a [`SelfTestReport`].
+#[derive(Debug, Clone)]
+pub struct SelfTestStage {
+    /// Stage name, e.g. `sign`.
+    pub name: &'static str,
+    /// What happened.
+    pub outcome: StageOutcome,
+    /// Time the stage took; zero if it was skipped.
+    pub elapsed: Duration,
+}
+
+/// Outcome of a [`SelfTestStage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageOutcome {
+    /// The stage succeeded.
+    Passed,
+    /// The stage failed with this error.
+    Failed(String),
+    /// An earlier stage failed, so this one did not run.
+    Skipped,
+}
+
+impl SelfTestReport {
+    /// Stage names, in the order they run.
+    pub(crate) const STAGES: [&'static str; 4] = ["auth", "dry_run", "sign", "verify"];
+
+    /// Returns true if every stage passed.
+    pub fn passed(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| stage.outcome == StageOutcome::Passed)
+    }
+
+    /// The first stage that failed, if any.
+    pub fn failure(&self) -> Option<&SelfTestStage> {
+        self.stages
+            .iter()
+            .find(|stage| matches!(stage.outcome, StageOutcome::Failed(_)))
+    }
+
+    /// Record the next stage, which started at `start`, returning its value
+    /// if it passed.
+    pub(crate) fn record<T>(&mut self, start: Instant, result: Result<T>) -> Option<T> {
+        let (outcome, value) = match result {
+            Ok(value) => (StageOutcome::Passed, Some(value)),
+            Err(e) => (StageOutcome::Failed(e.to_string()), None),
+        };
+        self.stages.push(SelfTestStage {
+            name: Self::STAGES[self.stages.len()],
+            outcome,
+            elapsed: start.elapsed(),
+        });
+        value
+    }
+
+    /// Mark the stages that did not run as skipped.
+    pub(crate) fn finish(mut self) -> Self {
+        for &name in &Self::STAGES[self.stages.len()..] {
+            self.stages.push(SelfTestStage {
+                name,
+                outcome: StageOutcome::Skipped,
+                elapsed: Duration::ZERO,
+            });
+        }
+        self
+    }
+}
+
+/// Public keys fetched in bulk, from
+/// [`NamespaceClient::get_public_keys`](crate::namespace::NamespaceClient::get_public_keys).
+#[derive(Debug, Clone, Default)]
+pub struct PublicKeys {
+    /// The public keys found, by key ID.
+    pub keys: HashMap<KeyId, PublicKey>,
+    /// Requested IDs with no key in the namespace, in request order.
+    pub missing: Vec<KeyId>,
+}
+
+/// Estimated cost of a set of sign requests, from
+/// [`Client::estimate_cost`](crate::Client::estimate_cost).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CostEstimate {
+    /// Quota units the requests would use.
+    pub quota_units: u64,
+    /// Cost as a decimal string in [`currency`](Self::currency), e.g.
+    /// `"12.50"`, so no precision is lost.
+    pub cost: String,
+    /// ISO 4217 currency code, e.g. `USD`.
+    pub currency: String,
+}
+
+impl QuotaStatus {
+    /// Operations left in the current window; `u64::MAX` if unlimited.
+    pub fn remaining(&self) -> u64 {
+        self.limit
+            .map_or(u64::MAX, |limit| limit.saturating_sub(self.used))
+    }
+
+    /// Share of the quota used, from `0.0` to `1.0`; `0.0` if unlimited.
+    pub fn fraction_used(&self) -> f64 {
+        match self.limit {
+            None => 0.0,
+            Some(0) => 1.0,
+            Some(limit) => (self.used as f64 / limit as f64).min(1.0),
+        }
+    }
+}
+
 /// A namespace within an organization.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Namespace {
     /// Namespace ID.
-    pub id: Uuid,
+    pub id: NamespaceId,
     /// Namespace name.
     pub name: String,
     /// Organization ID.
@@ -119,6 +1543,18 @@ pub struct Namespace {
     pub created_at: String,
 }
 
+/// Settings for a new namespace, for
+/// [`Client::create_namespace`](crate::Client::create_namespace).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NamespaceSettings {
+    /// Human-readable description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Optional metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
 /// An audit log entry.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AuditLog {
@@ -127,14 +1563,18 @@ pub struct AuditLog {
     /// Event type.
     pub event: String,
     /// Actor ID (user or API key).
+    #[serde(default)]
     pub actor_id: Option<Uuid>,
     /// Actor type ("user" or "api_key").
     pub actor_type: String,
     /// Resource type affected.
+    #[serde(default)]
     pub resource_type: Option<String>,
     /// Resource ID affected.
+    #[serde(default)]
     pub resource_id: Option<Uuid>,
     /// Additional metadata.
+    #[serde(default)]
     pub metadata: Option<serde_json::Value>,
     /// Timestamp.
     pub created_at: String,
@@ -160,6 +1600,24 @@ pub struct ListAuditLogsQuery {
     pub offset: Option<u32>,
 }
 
+/// An operation on a key, from
+/// [`AuditClient::list_key_events`](crate::audit::AuditClient::list_key_events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// Audit log entry ID.
+    pub id: Uuid,
+    /// When the operation happened.
+    pub timestamp: SystemTime,
+    /// Operation, e.g. `key.signed` or `key.created`.
+    pub operation: String,
+    /// ID of the caller (user or API key), if recorded.
+    pub actor_id: Option<Uuid>,
+    /// Caller type (`user` or `api_key`).
+    pub actor_type: String,
+    /// Request ID of the call, if recorded.
+    pub request_id: Option<String>,
+}
+
 /// Paginated response wrapper.
 #[derive(Debug, Clone, Deserialize)]
 pub struct PaginatedResponse<T> {
@@ -173,18 +1631,84 @@ pub struct PaginatedResponse<T> {
     pub limit: u64,
 }
 
+/// A page of results from a cursor-paginated endpoint.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// Items in this page.
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` if this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Result of [`Client::health_check`](crate::Client::health_check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// The server is reachable and healthy.
+    Healthy {
+        /// Server version, if reported.
+        version: Option<String>,
+    },
+    /// The server is reachable but reported itself unavailable (5xx).
+    Degraded {
+        /// Server version, if reported.
+        version: Option<String>,
+    },
+}
+
+impl HealthStatus {
+    /// Returns true if the server reported itself healthy.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, HealthStatus::Healthy { .. })
+    }
+
+    /// Returns the server version, if reported.
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            HealthStatus::Healthy { version } | HealthStatus::Degraded { version } => {
+                version.as_deref()
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_quota_status() {
+        let quota = QuotaStatus {
+            used: 2_500,
+            limit: Some(10_000),
+            resets_at: "2025-02-01T00:00:00Z".to_string(),
+        };
+        assert_eq!(quota.remaining(), 7_500);
+        assert_eq!(quota.fraction_used(), 0.25);
+
+        let over = QuotaStatus {
+            used: 12_000,
+            ..quota.clone()
+        };
+        assert_eq!(over.remaining(), 0);
+        assert_eq!(over.fraction_used(), 1.0);
+
+        let unlimited = QuotaStatus {
+            limit: None,
+            ..quota
+        };
+        assert_eq!(unlimited.remaining(), u64::MAX);
+        assert_eq!(unlimited.fraction_used(), 0.0);
+    }
+
     #[test]
     fn test_create_key_request_serialization() {
         let req = CreateKeyRequest {
             name: "test-key".to_string(),
-            namespace_id: Uuid::nil(),
+            namespace_id: Uuid::nil().into(),
             algorithm: None,
             exportable: Some(true),
             metadata: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -212,12 +1736,203 @@ mod tests {
         assert!(!key.exportable);
     }
 
+    #[test]
+    fn test_batch_result_into_result() {
+        let ok = || SignResponse {
+            key_id: Uuid::nil().into(),
+            signature: vec![1],
+            public_key: "pk".to_string(),
+            options: None,
+            algorithm: None,
+            context: None,
+            recovery_id: None,
+            warnings: Vec::new(),
+        };
+
+        let batch = BatchResult {
+            results: vec![
+                Ok(ok()),
+                Err(POPSignerError::SigningError("boom".to_string())),
+                Ok(ok()),
+            ],
+        };
+        assert!(!batch.is_complete());
+        assert_eq!(batch.failed_indices(), vec![1]);
+        assert_eq!(batch.successes().count(), 2);
+
+        match batch.into_result() {
+            Err(POPSignerError::BatchPartialFailure { failures, total }) => {
+                assert_eq!(total, 3);
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].0, 1);
+            }
+            other => panic!("expected BatchPartialFailure, got {:?}", other),
+        }
+
+        let batch = BatchResult {
+            results: vec![Ok(ok()), Ok(ok())],
+        };
+        assert_eq!(batch.into_result().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_to_eth_signature() {
+        let mut response = SignResponse {
+            key_id: Uuid::nil().into(),
+            signature: vec![1; 64],
+            public_key: "pk".to_string(),
+            options: None,
+            algorithm: Some(SigningAlgorithm::EcdsaSecp256k1),
+            context: None,
+            recovery_id: None,
+            warnings: Vec::new(),
+        };
+        let err = response.to_eth_signature().unwrap_err();
+        assert!(matches!(err, POPSignerError::SigningError(_)));
+
+        response.recovery_id = Some(1);
+        let eth = response.to_eth_signature().unwrap();
+        assert_eq!(eth[..64], [1; 64]);
+        assert_eq!(eth[64], 28);
+    }
+
+    #[test]
+    fn test_hex_signatures() {
+        let request = VerifyRequest::from_hex(Uuid::nil(), "7478", "0a0b").unwrap();
+        assert_eq!(request.data, b"tx");
+        assert_eq!(request.signature, vec![0x0a, 0x0b]);
+
+        let err = VerifyRequest::from_hex(Uuid::nil(), "7478", "zz").unwrap_err();
+        assert!(
+            matches!(err, POPSignerError::Decode(msg) if msg.starts_with("invalid hex signature"))
+        );
+
+        let response = SignResponse {
+            key_id: Uuid::nil().into(),
+            signature: request.signature,
+            public_key: "pk".to_string(),
+            options: None,
+            algorithm: None,
+            context: None,
+            recovery_id: None,
+            warnings: Vec::new(),
+        };
+        assert_eq!(response.signature_hex(), "0a0b");
+    }
+
+    #[tokio::test]
+    async fn test_verify_request_builder_infers_algorithm() {
+        let mock = crate::testing::MockTransport::new();
+        mock.push_data(serde_json::json!({
+            "id": Uuid::from_u128(1),
+            "name": "verifier",
+            "namespace_id": Uuid::nil(),
+            "public_key": "pk",
+            "address": "addr",
+            "algorithm": "ed25519",
+            "exportable": false,
+            "created_at": "2025-01-01T00:00:00Z"
+        }));
+        let body = serde_json::json!({
+            "error": { "code": "key_not_found", "message": "no such key" }
+        });
+        mock.push_response(crate::transport::Response::json(404, &body));
+        let client = Client::builder()
+            .api_key("test_key")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let request = VerifyRequest::builder()
+            .key(Uuid::from_u128(1))
+            .message(b"tx".to_vec())
+            .signature(vec![1; 64])
+            .resolve(&client)
+            .await
+            .unwrap();
+        assert_eq!(request.algorithm, Some(SigningAlgorithm::Ed25519));
+
+        let err = VerifyRequest::builder()
+            .key(Uuid::from_u128(2))
+            .signature(vec![1; 64])
+            .resolve(&client)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::KeyNotFound(_)));
+        assert_eq!(mock.requests().len(), 2);
+
+        let err = VerifyRequest::builder()
+            .key(Uuid::nil())
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn test_sign_request_idempotency_key() {
+        let request = SignRequest::new(Uuid::nil(), b"data".to_vec());
+        assert!(request.idempotency_key.is_none());
+
+        let a = request.clone().with_random_idempotency_key();
+        let b = request.with_random_idempotency_key();
+        assert!(a.idempotency_key.is_some());
+        assert_ne!(a.idempotency_key, b.idempotency_key);
+    }
+
+    #[test]
+    fn test_sign_request_serde_round_trip() {
+        let request = SignRequest::new(Uuid::nil(), b"tx".to_vec())
+            .algorithm(SigningAlgorithm::Ed25519ctx)
+            .context(b"app-v1".to_vec())
+            .idempotency_key("order-42")
+            .with_cancel(CancellationToken::new());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["data"], "dHg=");
+        assert_eq!(json["context"], "YXBwLXYx");
+        assert_eq!(json["algorithm"], "ed25519ctx");
+        assert!(json.get("cancel").is_none());
+
+        let replayed: SignRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(replayed.data, request.data);
+        assert_eq!(replayed.context, request.context);
+        assert_eq!(replayed.idempotency_key.as_deref(), Some("order-42"));
+        assert!(replayed.cancel.is_none());
+
+        // Only the required fields of a response need to be present.
+        let response: SignResponse = serde_json::from_value(serde_json::json!({
+            "key_id": Uuid::nil(),
+            "signature": "AQID",
+            "public_key": "pk"
+        }))
+        .unwrap();
+        assert_eq!(response.signature, vec![1, 2, 3]);
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["signature"], "AQID");
+    }
+
+    #[test]
+    fn test_signing_algorithm_parsing() {
+        assert_eq!(
+            "secp256k1".parse::<SigningAlgorithm>().unwrap(),
+            SigningAlgorithm::EcdsaSecp256k1
+        );
+        assert_eq!(
+            "p256".parse::<SigningAlgorithm>().unwrap(),
+            SigningAlgorithm::EcdsaP256
+        );
+        assert_eq!(
+            "ed25519ph".parse::<SigningAlgorithm>().unwrap(),
+            SigningAlgorithm::Ed25519ph
+        );
+        assert!("rot13".parse::<SigningAlgorithm>().is_err());
+    }
+
     #[test]
     fn test_create_batch_request() {
         let req = CreateBatchRequest {
             prefix: "worker".to_string(),
             count: 4,
-            namespace_id: Uuid::nil(),
+            namespace_id: Uuid::nil().into(),
             exportable: None,
         };
 
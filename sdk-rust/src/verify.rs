@@ -0,0 +1,377 @@
+//! Local signature verification.
+//!
+//! Verifies signatures offline against a known public key, without a round
+//! trip to POPSigner. ECDSA messages are hashed with SHA-256 before
+//! verification, matching how the server signs non-prehashed data.
+//! Ed25519ph signatures, which may carry a context, are checked with
+//! [`verify_ed25519ph`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use popsigner::crypto::{PublicKey, Signature};
+//! use popsigner::types::SigningAlgorithm;
+//! use popsigner::verify::verify_signature;
+//!
+//! # fn main() -> popsigner::Result<()> {
+//! let public_key = PublicKey::from_hex(
+//!     SigningAlgorithm::Ed25519,
+//!     "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+//! )?;
+//! let signature = Signature::new(hex::decode(
+//!     "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+//! ).unwrap());
+//!
+//! assert!(verify_signature(&public_key, b"", &signature)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cose::CoseSign1;
+use crate::crypto::{PublicKey, Signature};
+use crate::error::{POPSignerError, Result};
+use crate::types::SigningAlgorithm;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Longest context Ed25519ph accepts (RFC 8032).
+const MAX_CONTEXT_LEN: usize = 255;
+
+/// Verify `signature` over `message` with `public_key`.
+///
+/// Returns `Ok(false)` for a well-formed signature that does not verify.
+///
+/// # Errors
+///
+/// * [`POPSignerError::Decode`] if the key or signature bytes are malformed.
+/// * [`POPSignerError::SigningError`] if the signature is tagged with a
+///   different algorithm than the key, or the algorithm (RSA, Ed25519ctx)
+///   cannot be verified locally.
+///
+/// Ed25519ph signatures are checked without a context; see
+/// [`verify_ed25519ph`].
+pub fn verify_signature(
+    public_key: &PublicKey,
+    message: &[u8],
+    signature: &Signature,
+) -> Result<bool> {
+    if public_key.algorithm() == SigningAlgorithm::Ed25519ph
+        || signature.algorithm() == Some(SigningAlgorithm::Ed25519ph)
+    {
+        return verify_ed25519ph(public_key, message, None, signature);
+    }
+    check_algorithm(public_key, signature)?;
+
+    let key = public_key.as_bytes();
+    let sig = signature.as_bytes();
+
+    match public_key.algorithm() {
+        SigningAlgorithm::Ed25519 => verify_ed25519(key, message, sig),
+        SigningAlgorithm::EcdsaSecp256k1 => verify_secp256k1(key, &Sha256::digest(message), sig),
+        SigningAlgorithm::EcdsaP256 => verify_p256(key, &Sha256::digest(message), sig),
+        // Ed25519ctx and RSA.
+        algorithm => Err(POPSignerError::SigningError(format!(
+            "local verification is not supported for {}",
+            algorithm
+        ))),
+    }
+}
+
+/// Verify an Ed25519ph (RFC 8032) `signature` over `message`, made with
+/// `context` if one was given when signing.
+///
+/// The message is hashed with SHA-512 here, as the signer did before
+/// signing.
+///
+/// # Errors
+///
+/// * [`POPSignerError::Decode`] if the key or signature bytes are malformed.
+/// * [`POPSignerError::InvalidRequest`] if `context` is longer than 255
+///   bytes.
+/// * [`POPSignerError::SigningError`] if the key is not an Ed25519 key or
+///   the signature is tagged with another algorithm.
+pub fn verify_ed25519ph(
+    public_key: &PublicKey,
+    message: &[u8],
+    context: Option<&[u8]>,
+    signature: &Signature,
+) -> Result<bool> {
+    verify_ed25519ph_prehashed(
+        public_key,
+        Sha512::new_with_prefix(message),
+        context,
+        signature,
+    )
+}
+
+/// Like [`verify_ed25519ph`], for a message already fed into `prehash`, so
+/// a large message never has to be held in memory as a whole.
+///
+/// # Errors
+///
+/// Same as [`verify_ed25519ph`].
+pub fn verify_ed25519ph_prehashed(
+    public_key: &PublicKey,
+    prehash: Sha512,
+    context: Option<&[u8]>,
+    signature: &Signature,
+) -> Result<bool> {
+    match public_key.algorithm() {
+        SigningAlgorithm::Ed25519 | SigningAlgorithm::Ed25519ph => {}
+        algorithm => {
+            return Err(POPSignerError::SigningError(format!(
+                "ed25519ph needs an Ed25519 public key, not {}",
+                algorithm
+            )));
+        }
+    }
+    if let Some(algorithm) = signature
+        .algorithm()
+        .filter(|a| *a != SigningAlgorithm::Ed25519ph)
+    {
+        return Err(POPSignerError::SigningError(format!(
+            "signature algorithm {} is not ed25519ph",
+            algorithm
+        )));
+    }
+    if let Some(context) = context.filter(|c| c.len() > MAX_CONTEXT_LEN) {
+        return Err(POPSignerError::InvalidRequest(format!(
+            "signing context must be at most {} bytes, got {}",
+            MAX_CONTEXT_LEN,
+            context.len()
+        )));
+    }
+
+    let (key, signature) = ed25519_parts(public_key.as_bytes(), signature.as_bytes())?;
+    Ok(key.verify_prehashed(prehash, context, &signature).is_ok())
+}
+
+/// Verify the signature of the COSE_Sign1 message `message` (RFC 8152)
+/// with `public_key`.
+///
+/// The protected `alg` header must name the key's algorithm. Returns
+/// `Ok(false)` for a well-formed message whose signature does not verify.
+///
+/// # Errors
+///
+/// * [`POPSignerError::Decode`] if the message or key is malformed.
+/// * [`POPSignerError::SigningError`] if the `alg` header is missing or
+///   does not match the key, or the key cannot be verified locally.
+pub fn verify_cose_sign1(public_key: &PublicKey, message: &[u8]) -> Result<bool> {
+    let message = CoseSign1::decode(message)?;
+    let expected = public_key.algorithm().cose_alg();
+    if message.alg().is_none() || message.alg() != expected {
+        return Err(POPSignerError::SigningError(format!(
+            "COSE alg {:?} does not match {} public key",
+            message.alg(),
+            public_key.algorithm()
+        )));
+    }
+    let signature = Signature::new(message.signature.clone());
+    verify_signature(public_key, &message.to_be_signed(), &signature)
+}
+
+fn check_algorithm(public_key: &PublicKey, signature: &Signature) -> Result<()> {
+    match signature.algorithm() {
+        Some(algorithm) if algorithm != public_key.algorithm() => {
+            Err(POPSignerError::SigningError(format!(
+                "signature algorithm {} does not match {} public key",
+                algorithm,
+                public_key.algorithm()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn verify_ed25519(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool> {
+    use ed25519_dalek::Verifier;
+
+    let (key, signature) = ed25519_parts(public_key, signature)?;
+    Ok(key.verify(message, &signature).is_ok())
+}
+
+fn ed25519_parts(
+    public_key: &[u8],
+    signature: &[u8],
+) -> Result<(ed25519_dalek::VerifyingKey, ed25519_dalek::Signature)> {
+    let key_bytes: &[u8; 32] = public_key.try_into().map_err(|_| {
+        POPSignerError::Decode(format!(
+            "Ed25519 public key must be 32 bytes, got {}",
+            public_key.len()
+        ))
+    })?;
+    let key = ed25519_dalek::VerifyingKey::from_bytes(key_bytes)
+        .map_err(|e| POPSignerError::Decode(format!("invalid Ed25519 public key: {}", e)))?;
+    let signature = ed25519_dalek::Signature::from_slice(signature)
+        .map_err(|e| POPSignerError::Decode(format!("invalid Ed25519 signature: {}", e)))?;
+    Ok((key, signature))
+}
+
+fn verify_secp256k1(public_key: &[u8], digest: &[u8], signature: &[u8]) -> Result<bool> {
+    use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| POPSignerError::Decode(format!("invalid secp256k1 public key: {}", e)))?;
+    let signature = if signature.len() == 64 {
+        k256::ecdsa::Signature::from_slice(signature)
+    } else {
+        k256::ecdsa::Signature::from_der(signature)
+    }
+    .map_err(|e| POPSignerError::Decode(format!("invalid secp256k1 signature: {}", e)))?;
+
+    // k256 only accepts low-S signatures; accept either form.
+    let signature = signature.normalize_s().unwrap_or(signature);
+    Ok(key.verify_prehash(digest, &signature).is_ok())
+}
+
+fn verify_p256(public_key: &[u8], digest: &[u8], signature: &[u8]) -> Result<bool> {
+    use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+    let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| POPSignerError::Decode(format!("invalid P-256 public key: {}", e)))?;
+    let signature = if signature.len() == 64 {
+        p256::ecdsa::Signature::from_slice(signature)
+    } else {
+        p256::ecdsa::Signature::from_der(signature)
+    }
+    .map_err(|e| POPSignerError::Decode(format!("invalid P-256 signature: {}", e)))?;
+
+    Ok(key.verify_prehash(digest, &signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8032, section 7.1, TEST 1.
+    const ED25519_PUBLIC_KEY: &str =
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const ED25519_SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+    // RFC 8032, section 7.3, TEST abc (Ed25519ph over "abc").
+    const ED25519PH_PUBLIC_KEY: &str =
+        "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf";
+    const ED25519PH_SIGNATURE: &str = "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406";
+    // The same key and message with context "popsigner", from the RFC 8032
+    // reference implementation.
+    const ED25519PH_CONTEXT_SIGNATURE: &str = "c520a70b23f0817c10d9aae2678713d7f9c2b8e2aadd83b6d6f0d132f059ad4006d6caa9ef7e133d346f8568c38065608d4cc8741b0e1988cf1805b5f3363e0c";
+
+    #[test]
+    fn test_verify_ed25519() {
+        let key = PublicKey::from_hex(SigningAlgorithm::Ed25519, ED25519_PUBLIC_KEY).unwrap();
+        let signature = Signature::new(hex::decode(ED25519_SIGNATURE).unwrap());
+
+        assert!(verify_signature(&key, b"", &signature).unwrap());
+        assert!(!verify_signature(&key, b"tampered", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_verify_ed25519ph() {
+        let key = PublicKey::from_hex(SigningAlgorithm::Ed25519, ED25519PH_PUBLIC_KEY).unwrap();
+        let signature = Signature::with_algorithm(
+            SigningAlgorithm::Ed25519ph,
+            hex::decode(ED25519PH_SIGNATURE).unwrap(),
+        );
+        assert!(verify_ed25519ph(&key, b"abc", None, &signature).unwrap());
+        assert!(verify_signature(&key, b"abc", &signature).unwrap());
+        assert!(!verify_ed25519ph(&key, b"abd", None, &signature).unwrap());
+        // Plain Ed25519 over the same bytes is a different signature.
+        assert!(!verify_ed25519(key.as_bytes(), b"abc", signature.as_bytes()).unwrap());
+
+        let prehash = Sha512::new().chain_update(b"a").chain_update(b"bc");
+        assert!(verify_ed25519ph_prehashed(&key, prehash, None, &signature).unwrap());
+
+        let signature = Signature::new(hex::decode(ED25519PH_CONTEXT_SIGNATURE).unwrap());
+        assert!(verify_ed25519ph(&key, b"abc", Some(b"popsigner"), &signature).unwrap());
+        assert!(!verify_ed25519ph(&key, b"abc", None, &signature).unwrap());
+        assert!(matches!(
+            verify_ed25519ph(&key, b"abc", Some(&[0u8; 256]), &signature),
+            Err(POPSignerError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_secp256k1() {
+        use k256::ecdsa::signature::Signer;
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let raw: k256::ecdsa::Signature = signing_key.sign(b"hello world");
+        let key = PublicKey::new(
+            SigningAlgorithm::EcdsaSecp256k1,
+            signing_key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        );
+
+        let signature = Signature::new(raw.to_bytes().to_vec());
+        assert!(verify_signature(&key, b"hello world", &signature).unwrap());
+        assert!(!verify_signature(&key, b"goodbye", &signature).unwrap());
+
+        let der = Signature::new(raw.to_der().as_bytes().to_vec());
+        assert!(verify_signature(&key, b"hello world", &der).unwrap());
+    }
+
+    #[test]
+    fn test_verify_cose_sign1() {
+        use crate::cose::{self, CborMap, HEADER_ALG};
+        use k256::ecdsa::signature::Signer;
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let key = PublicKey::new(
+            SigningAlgorithm::EcdsaSecp256k1,
+            signing_key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        );
+        let sign1 = |alg: i64, payload: &[u8]| {
+            let mut header = CborMap::new();
+            header.insert(HEADER_ALG, alg);
+            let protected = cose::encode_header(&header);
+            let raw: k256::ecdsa::Signature =
+                signing_key.sign(&cose::sig_structure(&protected, b"payload"));
+            let signature = raw.to_bytes().to_vec();
+            cose::encode_sign1(&protected, CborMap::new(), payload, signature)
+        };
+
+        assert!(verify_cose_sign1(&key, &sign1(-47, b"payload")).unwrap());
+        assert!(!verify_cose_sign1(&key, &sign1(-47, b"tampered")).unwrap());
+        assert!(matches!(
+            verify_cose_sign1(&key, &sign1(-7, b"payload")),
+            Err(POPSignerError::SigningError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_malformed_input() {
+        let key = PublicKey::new(SigningAlgorithm::Ed25519, vec![0u8; 31]);
+        let signature = Signature::new(vec![0u8; 64]);
+        assert!(matches!(
+            verify_signature(&key, b"", &signature),
+            Err(POPSignerError::Decode(_))
+        ));
+
+        let key = PublicKey::from_hex(SigningAlgorithm::Ed25519, ED25519_PUBLIC_KEY).unwrap();
+        let signature = Signature::new(vec![0u8; 10]);
+        assert!(matches!(
+            verify_signature(&key, b"", &signature),
+            Err(POPSignerError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_algorithm_mismatch() {
+        let key = PublicKey::from_hex(SigningAlgorithm::Ed25519, ED25519_PUBLIC_KEY).unwrap();
+        let signature = Signature::with_algorithm(
+            SigningAlgorithm::EcdsaSecp256k1,
+            hex::decode(ED25519_SIGNATURE).unwrap(),
+        );
+        assert!(matches!(
+            verify_signature(&key, b"", &signature),
+            Err(POPSignerError::SigningError(_))
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+//! Integration tests for the blocking client.
+
+#![cfg(feature = "blocking")]
+
+use popsigner::ClientConfig;
+use popsigner::blocking::Client;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_blocking_list_keys() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // The blocking client must live outside the async runtime.
+    let base_url = mock_server.uri();
+    let keys = std::thread::spawn(move || {
+        let client = Client::with_config(
+            "test_key",
+            ClientConfig {
+                base_url: Some(base_url),
+                ..Default::default()
+            },
+        );
+        client.keys().list(None)
+    })
+    .join()
+    .unwrap()
+    .unwrap();
+
+    assert!(keys.is_empty());
+}
@@ -1,6 +1,8 @@
 //! Integration tests for the POPSigner client.
 
-use popsigner::{Client, ClientConfig};
+use popsigner::{Client, ClientConfig, HealthStatus, Jitter, RetryPolicy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -56,12 +58,16 @@ async fn test_unauthorized_error() {
 
     Mock::given(method("GET"))
         .and(path("/v1/keys"))
-        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
-            "error": {
-                "code": "unauthorized",
-                "message": "Invalid API key"
-            }
-        })))
+        .respond_with(
+            ResponseTemplate::new(401)
+                .insert_header("X-Request-Id", "req_401")
+                .set_body_json(serde_json::json!({
+                    "error": {
+                        "code": "unauthorized",
+                        "message": "Invalid API key"
+                    }
+                })),
+        )
         .mount(&mock_server)
         .await;
 
@@ -75,10 +81,13 @@ async fn test_unauthorized_error() {
 
     let result: Result<Vec<popsigner::Key>, _> = client.keys().list(None).await;
     assert!(result.is_err());
+    let err = result.unwrap_err();
     assert!(matches!(
-        result.unwrap_err(),
-        popsigner::POPSignerError::Unauthorized
+        err,
+        popsigner::POPSignerError::Unauthorized { .. }
     ));
+    assert_eq!(err.request_id(), Some("req_401"));
+    assert!(err.to_string().ends_with("(request id: req_401)"));
 }
 
 #[tokio::test]
@@ -87,12 +96,17 @@ async fn test_rate_limited_error() {
 
     Mock::given(method("GET"))
         .and(path("/v1/keys"))
-        .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
-            "error": {
-                "code": "rate_limited",
-                "message": "Too many requests"
-            }
-        })))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "7")
+                .insert_header("X-Request-Id", "req_429")
+                .set_body_json(serde_json::json!({
+                    "error": {
+                        "code": "rate_limited",
+                        "message": "Too many requests"
+                    }
+                })),
+        )
         .mount(&mock_server)
         .await;
 
@@ -106,10 +120,10 @@ async fn test_rate_limited_error() {
 
     let result: Result<Vec<popsigner::Key>, _> = client.keys().list(None).await;
     assert!(result.is_err());
-    assert!(matches!(
-        result.unwrap_err(),
-        popsigner::POPSignerError::RateLimited
-    ));
+    let err = result.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::RateLimited { .. }));
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+    assert_eq!(err.request_id(), Some("req_429"));
 }
 
 #[tokio::test]
@@ -119,12 +133,16 @@ async fn test_api_error_parsing() {
 
     Mock::given(method("GET"))
         .and(path(format!("/v1/keys/{}", key_id_str)))
-        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
-            "error": {
-                "code": "key_not_found",
-                "message": "Key does not exist"
-            }
-        })))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .insert_header("X-Request-Id", "req_abc123")
+                .set_body_json(serde_json::json!({
+                    "error": {
+                        "code": "key_not_found",
+                        "message": "Key does not exist"
+                    }
+                })),
+        )
         .mount(&mock_server)
         .await;
 
@@ -136,7 +154,7 @@ async fn test_api_error_parsing() {
         },
     );
 
-    let key_id = uuid::Uuid::parse_str(key_id_str).unwrap();
+    let key_id: popsigner::KeyId = key_id_str.parse().unwrap();
     let result = client.keys().get(&key_id).await;
 
     match result {
@@ -144,11 +162,549 @@ async fn test_api_error_parsing() {
             code,
             message,
             status_code,
+            request_id,
         }) => {
-            assert_eq!(code, "key_not_found");
+            assert_eq!(code, popsigner::ApiErrorCode::KeyNotFound);
+            assert_eq!(code.as_str(), "key_not_found");
             assert_eq!(message, "Key does not exist");
             assert_eq!(status_code, 404);
+            assert_eq!(request_id.as_deref(), Some("req_abc123"));
         }
         _ => panic!("Expected Api error"),
     }
 }
+
+#[tokio::test]
+async fn test_request_id_on_unparseable_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(
+            ResponseTemplate::new(500)
+                .insert_header("X-Request-Id", "req_500")
+                .set_body_string("<html>bad gateway</html>"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let err = client.keys().list(None).await.unwrap_err();
+    assert_eq!(err.status_code(), Some(500));
+    assert_eq!(err.request_id(), Some("req_500"));
+    assert!(err.to_string().contains("req_500"));
+}
+
+#[tokio::test]
+async fn test_quota_exceeded_reset_header() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("X-Quota-Reset", "1767225600")
+                .set_body_json(serde_json::json!({
+                    "error": {
+                        "code": "quota_exceeded",
+                        "message": "Monthly signature quota exceeded"
+                    }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let err = client.keys().list(None).await.unwrap_err();
+    match &err {
+        popsigner::POPSignerError::QuotaExceeded {
+            message, resets_at, ..
+        } => {
+            assert_eq!(message, "Monthly signature quota exceeded");
+            assert_eq!(
+                *resets_at,
+                Some(std::time::UNIX_EPOCH + Duration::from_secs(1_767_225_600))
+            );
+        }
+        other => panic!("expected QuotaExceeded, got {:?}", other),
+    }
+    // The reset time is in the past, so retrying right away is allowed.
+    assert_eq!(err.retry_after(), Some(Duration::ZERO));
+}
+
+#[tokio::test]
+async fn test_health_check() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "ok",
+            "version": "1.4.0"
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503).set_body_json(serde_json::json!({
+            "status": "error",
+            "component": "database"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(fast_retry(3))
+        .build()
+        .unwrap();
+
+    let status = client.health_check().await.unwrap();
+    assert_eq!(
+        status,
+        HealthStatus::Healthy {
+            version: Some("1.4.0".to_string())
+        }
+    );
+
+    let status = client.health_check().await.unwrap();
+    assert!(!status.is_healthy());
+    assert_eq!(status.version(), None);
+}
+
+#[tokio::test]
+async fn test_health_check_unauthorized() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let err = client.health_check().await.unwrap_err();
+    assert!(matches!(
+        err,
+        popsigner::POPSignerError::Unauthorized { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_timeout_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(5))
+                .set_body_json(serde_json::json!({ "data": [] })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        },
+    );
+
+    let err = client.keys().list(None).await.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::Timeout));
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_connect_error() {
+    // Nothing listens on port 1, so the connection is refused.
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some("http://127.0.0.1:1".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let err = client.keys().list(None).await.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::Connect(_)));
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_injected_http_client() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .and(header("Authorization", "Bearer test_api_key"))
+        .and(header("User-Agent", "shared-pool/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let http = reqwest::Client::builder()
+        .user_agent("shared-pool/1.0")
+        .build()
+        .unwrap();
+
+    let client = Client::builder()
+        .api_key("test_api_key")
+        .base_url(mock_server.uri())
+        .with_http_client(http)
+        .build()
+        .unwrap();
+
+    let keys = client.keys().list(None).await.unwrap();
+    assert!(keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_resolve_override() {
+    let mock_server = MockServer::start().await;
+    let addr = *mock_server.address();
+    let host = format!("popsigner.staging.test:{}", addr.port());
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .and(header("Host", host.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // The hostname does not exist; only the override makes it reachable.
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(format!("http://{}", host))
+        .resolve("popsigner.staging.test", addr)
+        .build()
+        .unwrap();
+
+    let keys = client.keys().list(None).await.unwrap();
+    assert!(keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_on_raw_response_sees_error_bodies() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/org"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("upstream exploded"))
+        .mount(&mock_server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .on_raw_response(move |body, status| sink.lock().unwrap().push((body.to_string(), status)))
+        .build()
+        .unwrap();
+
+    client.keys().list(None).await.unwrap();
+    assert!(client.orgs().get_current().await.is_err());
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(*seen, vec![("upstream exploded".to_string(), 500)]);
+}
+
+#[tokio::test]
+async fn test_on_quota_exceeded_observes_error() {
+    let mock_server = MockServer::start().await;
+    let org_id = uuid::Uuid::from_u128(7);
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("X-Request-Id", "req_quota")
+                .set_body_json(serde_json::json!({
+                    "error": {
+                        "code": "quota_exceeded",
+                        "message": "Monthly signature quota exceeded",
+                        "org_id": org_id,
+                        "namespace": "production",
+                        "used": 1000,
+                        "limit": 1000
+                    }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .on_quota_exceeded(move |info| sink.lock().unwrap().push(info.clone()))
+        .build()
+        .unwrap();
+
+    let err = client.keys().list(None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        popsigner::POPSignerError::QuotaExceeded {
+            used: Some(1000),
+            ..
+        }
+    ));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].org_id, Some(org_id));
+    assert_eq!(seen[0].namespace.as_deref(), Some("production"));
+    assert_eq!(seen[0].limit, Some(1000));
+    assert_eq!(seen[0].request_id.as_deref(), Some("req_quota"));
+}
+
+#[tokio::test]
+async fn test_on_warning_observes_server_warnings() {
+    let mock_server = MockServer::start().await;
+    let key_id = uuid::Uuid::from_u128(7);
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "signature": "AQID", "public_key": "pk" },
+            "warnings": [
+                "key nearing rotation deadline",
+                { "code": "quota_nearly_exhausted", "message": "90% of quota used" }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .on_warning(move |warning| sink.lock().unwrap().push(warning.clone()))
+        .build()
+        .unwrap();
+
+    let response = client
+        .sign()
+        .sign(&key_id.into(), b"tx", false)
+        .await
+        .unwrap();
+    assert_eq!(response.signature, vec![1, 2, 3]);
+    assert_eq!(response.warnings.len(), 2);
+    let warning = &response.warnings[0];
+    assert_eq!(warning.code, "");
+    assert_eq!(warning.message, "key nearing rotation deadline");
+    assert_eq!(response.warnings[1].code, "quota_nearly_exhausted");
+    assert_eq!(*seen.lock().unwrap(), response.warnings);
+}
+
+fn fast_retry(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(5),
+        jitter: Jitter::None,
+    }
+}
+
+#[tokio::test]
+async fn test_retry_recovers_from_server_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(fast_retry(3))
+        .build()
+        .unwrap();
+
+    let keys = client.keys().list(None).await.unwrap();
+    assert!(keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_retry_classifier_overrides_default() {
+    let mock_server = MockServer::start().await;
+
+    // 409 is not retryable by default.
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(409))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(fast_retry(3))
+        .retry_classifier(|e| e.status_code() == Some(409))
+        .build()
+        .unwrap();
+
+    let keys = client.keys().list(None).await.unwrap();
+    assert!(keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_retry_honors_retry_after() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(fast_retry(2))
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    client.keys().list(None).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_retry_stops_on_unauthorized() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(fast_retry(5))
+        .build()
+        .unwrap();
+
+    let err = client.keys().list(None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        popsigner::POPSignerError::Unauthorized { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_api_key_provider_refreshes_on_unauthorized() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .and(header("Authorization", "Bearer rotated_key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // The first call hands out a stale key; later calls return the new one.
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider_calls = calls.clone();
+    let client = Client::builder()
+        .api_key_provider(move || {
+            if provider_calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                "stale_key".to_string()
+            } else {
+                "rotated_key".to_string()
+            }
+        })
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let keys = client.keys().list(None).await.unwrap();
+    assert!(keys.is_empty());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -1,7 +1,12 @@
 //! Integration tests for key management.
 
-use popsigner::{Client, ClientConfig, CreateBatchRequest, CreateKeyRequest, Key};
-use wiremock::matchers::{body_json, method, path};
+use futures::StreamExt;
+use popsigner::{
+    Client, ClientConfig, CreateBatchRequest, CreateKeyRequest, Key, KeyId, KeySpec, NamespaceId,
+    POPSignerError,
+};
+use std::time::Duration;
+use wiremock::matchers::{body_json, body_partial_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn create_mock_key(id: &str, name: &str) -> serde_json::Value {
@@ -48,7 +53,7 @@ async fn test_create_key() {
         .keys()
         .create(CreateKeyRequest {
             name: "test-key".to_string(),
-            namespace_id: uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000002").unwrap(),
+            namespace_id: "00000000-0000-0000-0000-000000000002".parse().unwrap(),
             algorithm: Some("secp256k1".to_string()),
             ..Default::default()
         })
@@ -91,7 +96,7 @@ async fn test_create_batch() {
         .create_batch(CreateBatchRequest {
             prefix: "worker".to_string(),
             count: 4,
-            namespace_id: uuid::Uuid::nil(),
+            namespace_id: NamespaceId::default(),
             exportable: None,
         })
         .await
@@ -102,6 +107,55 @@ async fn test_create_batch() {
     assert_eq!(keys[3].name, "worker-4");
 }
 
+#[tokio::test]
+async fn test_create_keys_partial_failure() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/keys"))
+        .and(body_partial_json(serde_json::json!({ "name": "batcher" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": create_mock_key("00000000-0000-0000-0000-000000000001", "batcher")
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/keys"))
+        .and(body_partial_json(serde_json::json!({ "name": "taken" })))
+        .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+            "error": {
+                "code": "conflict",
+                "message": "key name already exists"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let namespace_id = NamespaceId::from(uuid::Uuid::from_u128(2));
+    let specs = vec![KeySpec::new("batcher"), KeySpec::new("taken")];
+    let results = client.keys().create_keys(&namespace_id, specs).await;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.failed_indices(), vec![1]);
+
+    match results.into_result() {
+        Err(POPSignerError::BatchPartialFailure { failures, total }) => {
+            assert_eq!(total, 2);
+            assert_eq!(failures[0].0, 1);
+            assert_eq!(failures[0].1.status_code(), Some(409));
+        }
+        other => panic!("expected BatchPartialFailure, got {:?}", other),
+    }
+}
+
 #[tokio::test]
 async fn test_get_key() {
     let mock_server = MockServer::start().await;
@@ -123,13 +177,41 @@ async fn test_get_key() {
         },
     );
 
-    let key = client
-        .keys()
-        .get(&uuid::Uuid::parse_str(key_id).unwrap())
-        .await
+    let key = client.keys().get(&key_id.parse().unwrap()).await.unwrap();
+
+    assert_eq!(key.name, "my-key");
+}
+
+#[tokio::test]
+async fn test_get_key_cached() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let namespace_id = NamespaceId::from(uuid::Uuid::from_u128(2));
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/keys/{}", key_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": create_mock_key(&key_id.to_string(), "my-key")
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .key_cache(Duration::from_secs(60), 100)
+        .build()
         .unwrap();
 
+    // The second read is served from the cache...
+    client.keys().get(&key_id).await.unwrap();
+    let key = client.keys().get(&key_id).await.unwrap();
     assert_eq!(key.name, "my-key");
+
+    // ...until the key is invalidated.
+    client.invalidate_key(&namespace_id, &key_id);
+    client.keys().get(&key_id).await.unwrap();
 }
 
 #[tokio::test]
@@ -182,12 +264,139 @@ async fn test_list_keys_with_namespace_filter() {
         },
     );
 
-    let ns_id = uuid::Uuid::parse_str(namespace_id).unwrap();
+    let ns_id: NamespaceId = namespace_id.parse().unwrap();
     let keys = client.keys().list(Some(&ns_id)).await.unwrap();
     assert_eq!(keys.len(), 1);
     assert_eq!(keys[0].name, "filtered-key");
 }
 
+#[tokio::test]
+async fn test_list_keys_stream_follows_cursor() {
+    let mock_server = MockServer::start().await;
+
+    // Mounted first so it takes precedence for the second page.
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .and(query_param("cursor", "page+2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                create_mock_key("00000000-0000-0000-0000-000000000003", "key-3"),
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                create_mock_key("00000000-0000-0000-0000-000000000001", "key-1"),
+                create_mock_key("00000000-0000-0000-0000-000000000002", "key-2"),
+            ],
+            "meta": { "next_cursor": "page+2" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let names: Vec<String> = client
+        .keys()
+        .list_stream(None)
+        .map(|key| key.unwrap().name)
+        .collect()
+        .await;
+    assert_eq!(names, vec!["key-1", "key-2", "key-3"]);
+}
+
+#[tokio::test]
+async fn test_list_keys_stream_yields_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .and(query_param("cursor", "next"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/keys"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [create_mock_key("00000000-0000-0000-0000-000000000001", "key-1")],
+            "meta": { "next_cursor": "next" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let items: Vec<_> = client.keys().list_stream(None).collect().await;
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert!(items[1].is_err());
+}
+
+#[tokio::test]
+async fn test_namespace_handle() {
+    let mock_server = MockServer::start().await;
+    let namespace_id = "00000000-0000-0000-0000-000000000002";
+    let other_namespace = "00000000-0000-0000-0000-000000000404";
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/keys/by-name/{}/my-key", namespace_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": create_mock_key("00000000-0000-0000-0000-000000000001", "my-key")
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/keys/by-name/{}/my-key", other_namespace)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": {
+                "code": "namespace_not_found",
+                "message": "Namespace does not exist"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let namespace = client.namespace(&namespace_id.parse().unwrap());
+    let key = namespace.get_key("my-key").await.unwrap();
+    assert_eq!(key.name, "my-key");
+
+    let missing = client.namespace(&other_namespace.parse().unwrap());
+    match missing.get_key("my-key").await {
+        Err(popsigner::POPSignerError::Api { code, .. }) => {
+            assert_eq!(code, popsigner::ApiErrorCode::NamespaceNotFound);
+        }
+        _ => panic!("Expected NamespaceNotFound API error"),
+    }
+}
+
 #[tokio::test]
 async fn test_delete_key() {
     let mock_server = MockServer::start().await;
@@ -207,10 +416,7 @@ async fn test_delete_key() {
         },
     );
 
-    let result = client
-        .keys()
-        .delete(&uuid::Uuid::parse_str(key_id).unwrap())
-        .await;
+    let result = client.keys().delete(&key_id.parse().unwrap()).await;
 
     assert!(result.is_ok());
 }
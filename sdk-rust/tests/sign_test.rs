@@ -1,8 +1,13 @@
 //! Integration tests for signing operations.
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
-use popsigner::{BatchSignItem, BatchSignRequest, Client, ClientConfig};
-use wiremock::matchers::{method, path};
+use popsigner::{
+    BatchSignItem, BatchSignRequest, CancellationToken, Client, ClientConfig, DigestAlgorithm,
+    KeyId, RetryPolicy, SignRequest, SignatureEncoding, SignatureOptions, SigningAlgorithm,
+    VerifyRequest,
+};
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -34,11 +39,7 @@ async fn test_sign() {
 
     let result = client
         .sign()
-        .sign(
-            &uuid::Uuid::parse_str(key_id).unwrap(),
-            b"hello world",
-            false,
-        )
+        .sign(&key_id.parse().unwrap(), b"hello world", false)
         .await
         .unwrap();
 
@@ -81,13 +82,303 @@ async fn test_sign_prehashed() {
     let hash = [0u8; 32];
     let result = client
         .sign()
-        .sign(&uuid::Uuid::parse_str(key_id).unwrap(), &hash, true)
+        .sign(&key_id.parse().unwrap(), &hash, true)
         .await
         .unwrap();
 
     assert_eq!(result.signature, signature_bytes);
 }
 
+#[tokio::test]
+async fn test_sign_digest() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let digest = [0xab; 32];
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({
+            "data": BASE64.encode(digest),
+            "prehashed": true,
+            "digest_algorithm": "sha256"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([4, 5, 6]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let result = client
+        .sign()
+        .sign_digest(&key_id, &digest, DigestAlgorithm::Sha256)
+        .await
+        .unwrap();
+    assert_eq!(result.signature, vec![4, 5, 6]);
+}
+
+#[tokio::test]
+async fn test_sign_algorithm_rejected() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({
+            "algorithm": "ed25519"
+        })))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "code": "invalid_signature_algorithm",
+                "message": "algorithm not supported by key"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/keys/{}", key_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "id": key_id,
+                "name": "my-key",
+                "namespace_id": "00000000-0000-0000-0000-000000000002",
+                "public_key": "A1234567890abcdef",
+                "address": "celestia1abc123",
+                "algorithm": "secp256k1",
+                "exportable": false,
+                "created_at": "2025-01-01T00:00:00Z"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let request = SignRequest::new(key_id, b"tx".to_vec()).algorithm(SigningAlgorithm::Ed25519);
+    match client.sign().sign_request(&request).await {
+        Err(popsigner::POPSignerError::SigningError(message)) => {
+            assert!(message.contains("ed25519"));
+            assert!(message.contains("secp256k1"));
+        }
+        other => panic!("Expected SigningError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_sign_signature_options() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({
+            "algorithm": "secp256k1",
+            "signature_options": { "deterministic": true, "canonical_s": true }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([1, 2, 3]),
+                "public_key": "pubkey1",
+                "signature_options": { "deterministic": true, "canonical_s": true }
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let options = SignatureOptions {
+        deterministic: true,
+        canonical_s: true,
+    };
+    let request = SignRequest::new(key_id, b"tx".to_vec())
+        .algorithm(SigningAlgorithm::EcdsaSecp256k1)
+        .options(options);
+    let result = client.sign().sign_request(&request).await.unwrap();
+    assert_eq!(result.options, Some(options));
+}
+
+#[tokio::test]
+async fn test_sign_encoding() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let raw = [1u8; 64];
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({ "encoding": "raw" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode(raw),
+                "public_key": "pubkey1",
+                "algorithm": "secp256k1"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let request = SignRequest::new(key_id, b"tx".to_vec()).encoding(SignatureEncoding::Raw);
+    let result = client.sign().sign_request(&request).await.unwrap();
+    assert_eq!(result.algorithm, Some(SigningAlgorithm::EcdsaSecp256k1));
+
+    let der = result.to_encoding(SignatureEncoding::Der).unwrap();
+    assert_eq!(der[0], 0x30);
+    let back = popsigner::Signature::with_algorithm(SigningAlgorithm::EcdsaSecp256k1, der)
+        .to_encoding(SignatureEncoding::Raw)
+        .unwrap();
+    assert_eq!(back, raw);
+}
+
+#[tokio::test]
+async fn test_sign_request_timeout_override() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(500))
+                .set_body_json(serde_json::json!({
+                    "data": {
+                        "signature": BASE64.encode([1, 2, 3]),
+                        "public_key": "pubkey1"
+                    }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    // The client default is too short for this slow key...
+    let request = SignRequest::new(key_id, b"tx".to_vec());
+    let err = client.sign().sign_request(&request).await.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::Timeout));
+
+    // ...but a per-request timeout overrides it.
+    let request = request.timeout(Duration::from_secs(5));
+    let result = client.sign().sign_request(&request).await.unwrap();
+    assert_eq!(result.signature, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_sign_idempotency_key_reused_on_retry() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(header("Idempotency-Key", "sign-once"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(header("Idempotency-Key", "sign-once"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([1, 2, 3]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .retry(RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap();
+
+    let request = SignRequest::new(key_id, b"tx".to_vec()).idempotency_key("sign-once");
+    let result = client.sign().sign_request(&request).await.unwrap();
+    assert_eq!(result.signature, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_sign_custom_headers() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(header("Authorization", "Bearer test_key"))
+        .and(header("X-Tenant", "override"))
+        .and(header("X-Region", "eu"))
+        .and(header("X-Correlation-Id", "corr-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([1, 2, 3]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test_key")
+        .base_url(mock_server.uri())
+        .default_header("X-Tenant", "acme")
+        .default_header("X-Region", "eu")
+        .build()
+        .unwrap();
+
+    let request = SignRequest::new(key_id, b"tx".to_vec())
+        .header("X-Tenant", "override")
+        .header("X-Correlation-Id", "corr-1");
+    client.sign().sign_request(&request).await.unwrap();
+
+    let request = SignRequest::new(key_id, b"tx".to_vec()).header("Authorization", "Bearer x");
+    let err = client.sign().sign_request(&request).await.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::InvalidRequest(_)));
+}
+
 #[tokio::test]
 async fn test_sign_batch() {
     let mock_server = MockServer::start().await;
@@ -141,28 +432,30 @@ async fn test_sign_batch() {
         .sign_batch(BatchSignRequest {
             requests: vec![
                 BatchSignItem {
-                    key_id: uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap(),
+                    key_id: "00000000-0000-0000-0000-000000000001".parse().unwrap(),
                     data: b"tx1".to_vec(),
                     prehashed: false,
                 },
                 BatchSignItem {
-                    key_id: uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000002").unwrap(),
+                    key_id: "00000000-0000-0000-0000-000000000002".parse().unwrap(),
                     data: b"tx2".to_vec(),
                     prehashed: false,
                 },
                 BatchSignItem {
-                    key_id: uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000003").unwrap(),
+                    key_id: "00000000-0000-0000-0000-000000000003".parse().unwrap(),
                     data: b"tx3".to_vec(),
                     prehashed: false,
                 },
                 BatchSignItem {
-                    key_id: uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000004").unwrap(),
+                    key_id: "00000000-0000-0000-0000-000000000004".parse().unwrap(),
                     data: b"tx4".to_vec(),
                     prehashed: false,
                 },
             ],
         })
         .await
+        .unwrap()
+        .into_result()
         .unwrap();
 
     assert_eq!(results.len(), 4);
@@ -172,6 +465,383 @@ async fn test_sign_batch() {
     assert_eq!(results[3].signature, vec![13, 14, 15, 16]);
 }
 
+#[tokio::test]
+async fn test_sign_batch_partial_failure() {
+    let mock_server = MockServer::start().await;
+
+    let sig1 = BASE64.encode([1, 2, 3, 4]);
+    let sig3 = BASE64.encode([9, 10, 11, 12]);
+
+    Mock::given(method("POST"))
+        .and(path("/v1/sign/batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signatures": [
+                    {
+                        "key_id": "00000000-0000-0000-0000-000000000001",
+                        "signature": sig1,
+                        "public_key": "pubkey1"
+                    },
+                    {
+                        "key_id": "00000000-0000-0000-0000-000000000002",
+                        "error": "quota exceeded"
+                    },
+                    {
+                        "key_id": "00000000-0000-0000-0000-000000000003",
+                        "signature": sig3,
+                        "public_key": "pubkey3"
+                    }
+                ]
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let requests = (1..=3)
+        .map(|i| BatchSignItem {
+            key_id: uuid::Uuid::from_u128(i).into(),
+            data: format!("tx{}", i).into_bytes(),
+            prehashed: false,
+        })
+        .collect();
+
+    let batch = client
+        .sign()
+        .sign_batch(BatchSignRequest { requests })
+        .await
+        .unwrap();
+
+    assert_eq!(batch.len(), 3);
+    assert_eq!(batch.failed_indices(), vec![1]);
+    assert_eq!(
+        batch.results[0].as_ref().unwrap().signature,
+        vec![1, 2, 3, 4]
+    );
+    assert_eq!(
+        batch.results[2].as_ref().unwrap().signature,
+        vec![9, 10, 11, 12]
+    );
+
+    match batch.into_result() {
+        Err(popsigner::POPSignerError::BatchPartialFailure { failures, total }) => {
+            assert_eq!(total, 3);
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].0, 1);
+        }
+        _ => panic!("Expected BatchPartialFailure"),
+    }
+}
+
+#[tokio::test]
+async fn test_sign_multi() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+
+    Mock::given(method("POST"))
+        .and(path("/v1/sign/batch"))
+        .and(body_partial_json(serde_json::json!({
+            "requests": [
+                { "key_id": key_id, "data": BASE64.encode(b"m1") },
+                { "key_id": key_id, "data": BASE64.encode(b"m2") }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signatures": [
+                    { "key_id": key_id, "signature": BASE64.encode([1]), "public_key": "pk" },
+                    { "key_id": key_id, "signature": BASE64.encode([2]), "public_key": "pk" }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let messages = vec![b"m1".to_vec(), b"m2".to_vec()];
+    let signatures = client
+        .sign()
+        .sign_multi(&key_id, messages, false)
+        .await
+        .unwrap();
+
+    assert_eq!(signatures.len(), 2);
+    assert_eq!(signatures[0].signature, vec![1]);
+    assert_eq!(signatures[1].signature, vec![2]);
+}
+
+#[tokio::test]
+async fn test_validate_dry_run() {
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let missing_key = KeyId::from(uuid::Uuid::from_u128(2));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({ "dry_run": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "issues": [
+                    { "code": "payload_too_large", "message": "data exceeds 1 MiB" }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", missing_key)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "code": "key_not_found", "message": "Key does not exist" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let request = SignRequest::new(key_id, b"tx".to_vec()).dry_run(true);
+    let report = client.sign().validate(&request).await.unwrap();
+    assert!(!report.is_valid());
+    assert_eq!(report.issues[0].code, "payload_too_large");
+
+    // A dry-run request is never signed by sign_request.
+    let err = client.sign().sign_request(&request).await.unwrap_err();
+    assert!(matches!(err, popsigner::POPSignerError::InvalidRequest(_)));
+
+    let request = SignRequest::new(missing_key, b"tx".to_vec());
+    let report = client.sign().validate(&request).await.unwrap();
+    assert_eq!(report.key_id, missing_key);
+    assert_eq!(report.issues[0].code, "key_not_found");
+}
+
+#[tokio::test]
+async fn test_sign_stream_prehashed_hashes_incrementally() {
+    use sha2::{Digest, Sha256};
+
+    let mock_server = MockServer::start().await;
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let message = vec![0xabu8; 200 * 1024];
+    let digest = Sha256::digest(&message);
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", key_id)))
+        .and(body_partial_json(serde_json::json!({
+            "data": BASE64.encode(digest),
+            "prehashed": true,
+            "digest_algorithm": "sha256"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([9, 9]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let result = client
+        .sign()
+        .sign_stream_prehashed(&key_id, message.as_slice(), DigestAlgorithm::Sha256)
+        .await
+        .unwrap();
+    assert_eq!(result.signature, vec![9, 9]);
+}
+
+#[tokio::test]
+async fn test_sign_batch_concurrent() {
+    let mock_server = MockServer::start().await;
+    let good_key = KeyId::from(uuid::Uuid::from_u128(1));
+    let missing_key = KeyId::from(uuid::Uuid::from_u128(2));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", good_key)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([7, 7, 7]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", missing_key)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": {
+                "code": "key_not_found",
+                "message": "Key does not exist"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let requests = vec![
+        SignRequest::new(good_key, b"tx1".to_vec()),
+        SignRequest::new(missing_key, b"tx2".to_vec()),
+        SignRequest::new(good_key, b"tx3".to_vec()),
+        SignRequest::new(good_key, b"tx4".to_vec()),
+    ];
+
+    let batch = client.sign().sign_batch_concurrent(requests, 2).await;
+
+    assert_eq!(batch.len(), 4);
+    assert_eq!(batch.failed_indices(), vec![1]);
+    assert_eq!(batch.successes().count(), 3);
+    assert!(batch.into_result().is_err());
+}
+
+#[tokio::test]
+async fn test_sign_batch_concurrent_cancelled() {
+    let mock_server = MockServer::start().await;
+    let fast_key = KeyId::from(uuid::Uuid::from_u128(1));
+    let slow_key = KeyId::from(uuid::Uuid::from_u128(2));
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", fast_key)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "signature": BASE64.encode([7, 7, 7]),
+                "public_key": "pubkey1"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", slow_key)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(10))
+                .set_body_json(serde_json::json!({
+                    "data": {
+                        "signature": BASE64.encode([8, 8, 8]),
+                        "public_key": "pubkey2"
+                    }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        trigger.cancel();
+    });
+
+    let requests = vec![
+        SignRequest::new(fast_key, b"tx1".to_vec()),
+        SignRequest::new(slow_key, b"tx2".to_vec()),
+    ];
+    let batch = client
+        .sign()
+        .sign_batch_concurrent_with_cancel(requests, 2, cancel)
+        .await;
+
+    assert_eq!(batch.len(), 2);
+    assert!(batch.results[0].is_ok());
+    assert!(matches!(
+        batch.results[1],
+        Err(popsigner::POPSignerError::Cancelled)
+    ));
+}
+
+#[tokio::test]
+async fn test_sign_batch_stream_yields_in_completion_order() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    let fast_key = KeyId::from(uuid::Uuid::from_u128(1));
+    let slow_key = KeyId::from(uuid::Uuid::from_u128(2));
+    let signed = |byte: u8| {
+        serde_json::json!({
+            "data": { "signature": BASE64.encode([byte]), "public_key": "pk" }
+        })
+    };
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", fast_key)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(signed(1)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/keys/{}/sign", slow_key)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(300))
+                .set_body_json(signed(2)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let requests = vec![
+        SignRequest::new(slow_key, b"tx1".to_vec()),
+        SignRequest::new(fast_key, b"tx2".to_vec()),
+    ];
+    let sign = client.sign();
+    let results: Vec<_> = sign.sign_batch_stream(requests, 2).collect().await;
+
+    let order: Vec<_> = results.iter().map(|(index, _)| *index).collect();
+    assert_eq!(order, vec![1, 0]);
+    assert_eq!(results[0].1.as_ref().unwrap().signature, vec![1]);
+}
+
 #[tokio::test]
 async fn test_verify() {
     let mock_server = MockServer::start().await;
@@ -198,7 +868,7 @@ async fn test_verify() {
     let valid = client
         .sign()
         .verify(
-            &uuid::Uuid::parse_str(key_id).unwrap(),
+            &key_id.parse().unwrap(),
             b"hello world",
             &[0x30, 0x45, 0x02, 0x21],
             false,
@@ -235,7 +905,7 @@ async fn test_verify_invalid_signature() {
     let valid = client
         .sign()
         .verify(
-            &uuid::Uuid::parse_str(key_id).unwrap(),
+            &key_id.parse().unwrap(),
             b"tampered data",
             &[0x00, 0x00, 0x00, 0x00],
             false,
@@ -245,3 +915,53 @@ async fn test_verify_invalid_signature() {
 
     assert!(!valid);
 }
+
+#[tokio::test]
+async fn test_verify_batch() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/verify/batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "results": [
+                    { "key_id": "00000000-0000-0000-0000-000000000001", "valid": true },
+                    { "key_id": "00000000-0000-0000-0000-000000000001", "valid": false },
+                    {
+                        "key_id": "00000000-0000-0000-0000-000000000009",
+                        "error": "key not found"
+                    }
+                ]
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::with_config(
+        "test_key",
+        ClientConfig {
+            base_url: Some(mock_server.uri()),
+            ..Default::default()
+        },
+    );
+
+    let key_id = KeyId::from(uuid::Uuid::from_u128(1));
+    let batch = client
+        .sign()
+        .verify_batch(vec![
+            VerifyRequest::new(key_id, b"tx1".to_vec(), vec![1; 64]),
+            VerifyRequest::new(key_id, b"tx2".to_vec(), vec![2; 64]),
+            VerifyRequest::new(uuid::Uuid::from_u128(9), b"tx3".to_vec(), vec![3; 64]),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(batch.len(), 3);
+    assert!(batch.results[0].as_ref().unwrap().valid);
+    assert!(!batch.results[1].as_ref().unwrap().valid);
+    assert_eq!(batch.failed_indices(), vec![2]);
+    assert!(matches!(
+        batch.into_result(),
+        Err(popsigner::POPSignerError::BatchPartialFailure { total: 3, .. })
+    ));
+}
@@ -0,0 +1,38 @@
+//! Compile test for the `wasm32` build.
+//!
+//! `cargo check --target wasm32-unknown-unknown --test wasm_test` builds the
+//! SDK against reqwest's fetch backend without a browser; on native targets
+//! the test also runs.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use popsigner::testing::MockTransport;
+use popsigner::{Client, KeyId, POPSignerError, SignRequest, SigningAlgorithm};
+
+async fn sign(client: &Client, key_id: KeyId) -> popsigner::Result<Vec<u8>> {
+    let request = SignRequest::new(key_id, b"hello".to_vec()).algorithm(SigningAlgorithm::Ed25519);
+    let response = client.sign().sign_request(&request).await?;
+    Ok(response.signature)
+}
+
+#[test]
+fn test_wasm_api_surface() {
+    // The default transport builds on every target.
+    Client::builder().api_key("test_key").build().unwrap();
+
+    let mock = MockTransport::new();
+    mock.push_data(serde_json::json!({
+        "signature": BASE64.encode([1, 2, 3]),
+        "public_key": "pk"
+    }));
+    let client = Client::builder()
+        .api_key("test_key")
+        .transport(mock)
+        .build()
+        .unwrap();
+
+    let signature = futures::executor::block_on(sign(&client, KeyId::default())).unwrap();
+    assert_eq!(signature, vec![1, 2, 3]);
+
+    let err = futures::executor::block_on(sign(&client, KeyId::default())).unwrap_err();
+    assert!(matches!(err, POPSignerError::InvalidRequest(_)));
+}